@@ -0,0 +1,52 @@
+use std::process::Stdio;
+use tokio::process::Command;
+
+use crate::alert::AlertEvent;
+
+// The shell the command line is handed to, and the flag that makes it run one
+#[cfg(not(windows))]
+const SHELL: [&str; 2] = ["sh", "-c"];
+#[cfg(windows)]
+const SHELL: [&str; 2] = ["cmd", "/C"];
+
+/// Shell command spawned whenever a threshold is breached, by `sh` or by
+/// `cmd` on Windows. The event is passed through `HTOPUI_*` environment
+/// variables.
+pub struct BreachCommand {
+    command: String,
+}
+
+impl BreachCommand {
+    pub fn new(command: String) -> Self {
+        Self { command }
+    }
+
+    pub fn run(&self, event: &AlertEvent) {
+        let [shell, flag] = SHELL;
+        let mut command = Command::new(shell);
+        command
+            .arg(flag)
+            .arg(&self.command)
+            .env("HTOPUI_METRIC", event.metric.to_string())
+            .env("HTOPUI_SOURCE", &event.source)
+            .env("HTOPUI_VALUE", event.value.to_string())
+            .env("HTOPUI_THRESHOLD", event.threshold.to_string())
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        if let Some(pid) = event.pid {
+            command.env("HTOPUI_PID", pid.to_string());
+        }
+        if let Some(action) = &event.action {
+            command.env("HTOPUI_ACTION", action);
+        }
+        match command.spawn() {
+            Ok(mut child) => {
+                tokio::spawn(async move {
+                    let _ = child.wait().await;
+                });
+            }
+            Err(err) => log::warn!("Error running on_breach_command: {}", err),
+        }
+    }
+}
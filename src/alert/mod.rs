@@ -0,0 +1,366 @@
+pub mod command;
+pub mod smtp;
+pub mod watchdog;
+pub mod webhook;
+
+use std::{collections::{HashMap, HashSet, VecDeque}, fmt, time::{Duration, Instant, SystemTime, UNIX_EPOCH}};
+use serde::Serialize;
+
+use crate::{
+    alert::{command::BreachCommand, smtp::Mailer, webhook::Webhook},
+    app::config::{AlertsConfig, DiskFreeRule, ProcessRule},
+    cmd::{disk::Disk, process::Process, Message},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Metric {
+    Cpu,
+    Memory,
+    Disk,
+    /// A mount below the free space of its `disk_free` rule, in percent
+    /// or in GB.
+    DiskFree,
+    Temperature,
+    /// A sensor at the critical value it reports.
+    TemperatureCritical,
+    ProcessCpu,
+    ProcessMemory,
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Metric::Cpu => "cpu",
+            Metric::Memory => "memory",
+            Metric::Disk => "disk",
+            Metric::DiskFree => "disk_free",
+            Metric::Temperature => "temperature",
+            Metric::TemperatureCritical => "temperature_critical",
+            Metric::ProcessCpu => "process_cpu",
+            Metric::ProcessMemory => "process_memory",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertState {
+    Breach,
+    Resolved,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertEvent {
+    pub timestamp: u64,
+    pub metric: Metric,
+    /// What crossed the threshold, e.g. `total`, a disk name, a sensor label
+    /// or the name of a process rule
+    pub source: String,
+    /// Process using the most of the metric when the event was raised, or
+    /// the one a process rule matched
+    pub pid: Option<u32>,
+    pub state: AlertState,
+    pub value: f32,
+    pub threshold: f32,
+    /// What the watchdog did to the process, or why it couldn't.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+}
+
+/// Watches collector messages and raises an event whenever a value crosses
+/// its threshold in either direction.
+pub struct Alerts {
+    cpu_threshold: f32,
+    mem_threshold: f32,
+    disk_threshold: f32,
+    temperature_threshold: f32,
+    temperature_hysteresis: f32,
+    webhook: Option<Webhook>,
+    breach_command: Option<BreachCommand>,
+    mailer: Option<Mailer>,
+    breached: HashSet<(Metric, String)>,
+    top_cpu_pid: Option<u32>,
+    top_mem_pid: Option<u32>,
+    rules: Vec<ProcessRule>,
+    watchdog: bool,
+    /// Latest events, oldest first.
+    history: VecDeque<AlertEvent>,
+    disk_free: Vec<DiskFreeRule>,
+    ignored_mounts: HashSet<String>,
+    /// Since when a process has been above a rule's limit, by rule index.
+    above_since: HashMap<RuleKey, Instant>,
+    /// Processes that stayed above a rule's limit for long enough, with the limit.
+    rule_breaches: HashMap<RuleKey, f32>,
+}
+
+/// A rule, one of its limits and a process it matched.
+type RuleKey = (usize, Metric, u32);
+
+impl Alerts {
+    /// Events kept for the alerts view.
+    const HISTORY: usize = 200;
+
+    pub fn new(config: &AlertsConfig) -> Self {
+        Self {
+            cpu_threshold: config.cpu_threshold.unwrap(),
+            mem_threshold: config.mem_threshold.unwrap(),
+            disk_threshold: config.disk_threshold.unwrap(),
+            temperature_threshold: config.temperature_threshold.unwrap(),
+            temperature_hysteresis: config.temperature_hysteresis.unwrap(),
+            webhook: config.webhook_url.clone().map(Webhook::new),
+            breach_command: config.on_breach_command.clone().map(BreachCommand::new),
+            mailer: config.smtp.clone().map(Mailer::new),
+            breached: HashSet::new(),
+            top_cpu_pid: None,
+            top_mem_pid: None,
+            rules: config.rules.clone().unwrap_or_default(),
+            watchdog: config.watchdog.unwrap(),
+            history: VecDeque::with_capacity(Self::HISTORY),
+            disk_free: config.disk_free.clone().unwrap_or_default(),
+            ignored_mounts: config.ignored_mounts.iter().flatten().cloned().collect(),
+            above_since: HashMap::new(),
+            rule_breaches: HashMap::new(),
+        }
+    }
+
+    /// Whether the disk is over `disk_threshold` or below the free space of
+    /// its rule, unless its mount is ignored.
+    pub fn low_on_space(&self, disk: &Disk) -> bool {
+        if self.ignored_mounts.contains(&disk.mount_point) {
+            return false;
+        }
+        let below_rule = self.free_space(disk).is_some_and(|(free, min)| free < min);
+        below_rule || disk.percent_used_space() as f32 >= self.disk_threshold
+    }
+
+    /// Free space of the disk and the minimum of its rule, in the unit the
+    /// rule is written in.
+    fn free_space(&self, disk: &Disk) -> Option<(f32, f32)> {
+        let rule = self.disk_free.iter().find(|rule| rule.mount == disk.mount_point)?;
+        match (rule.min_free_percent, rule.min_free_gb) {
+            (Some(min), _) => {
+                let free = match disk.total_space {
+                    0 => 100.0,
+                    total => disk.available_space as f32 * 100.0 / total as f32,
+                };
+                Some((free, min))
+            }
+            (None, Some(min)) => Some((disk.available_space as f32 / 1e9, min)),
+            (None, None) => None,
+        }
+    }
+
+    pub fn history(&self) -> &VecDeque<AlertEvent> {
+        &self.history
+    }
+
+    /// Whether a process rule is breached by the process.
+    pub fn breaching(&self, pid: u32) -> bool {
+        self.rule_breaches.keys().any(|(_, _, breaching)| *breaching == pid)
+    }
+
+    /// Forgets what is breaching, for readings of another machine. The
+    /// history is kept.
+    pub fn reset(&mut self) {
+        self.breached.clear();
+        self.top_cpu_pid = None;
+        self.top_mem_pid = None;
+        self.above_since.clear();
+        self.rule_breaches.clear();
+    }
+
+    /// Checks the message against the thresholds and rules, sends out the
+    /// events it raises and returns them. The watchdog only acts on `local`
+    /// messages, a PID of another host names an unrelated process here.
+    pub fn evaluate(&mut self, msg: &Message, local: bool) -> Vec<AlertEvent> {
+        let mut events = Vec::new();
+        match msg {
+            Message::Processes(processes) => {
+                let top_by = |usage: fn(&Process) -> f32| {
+                    processes
+                        .iter()
+                        .max_by(|a, b| usage(a).partial_cmp(&usage(b)).unwrap_or(std::cmp::Ordering::Equal))
+                        .map(|process| process.pid)
+                };
+                self.top_cpu_pid = top_by(|process| process.cpu_usage);
+                self.top_mem_pid = top_by(|process| process.mem_usage);
+                events.extend(self.check_rules(processes, local));
+            }
+            Message::CpuUsage(cores_usage) if !cores_usage.is_empty() => {
+                let total = cores_usage.iter().sum::<f32>() / cores_usage.len() as f32;
+                events.extend(self.check(Metric::Cpu, "total", self.top_cpu_pid, total, self.cpu_threshold));
+            }
+            Message::MemUsage(mem_usage) => {
+                events.extend(self.check(Metric::Memory, "total", self.top_mem_pid, mem_usage.percent_used(), self.mem_threshold));
+            }
+            Message::DiskUsage(disks) => {
+                for disk in disks.iter() {
+                    if self.ignored_mounts.contains(&disk.mount_point) {
+                        continue;
+                    }
+                    let used = disk.percent_used_space() as f32;
+                    events.extend(self.check(Metric::Disk, &disk.name, None, used, self.disk_threshold));
+                    if let Some((free, min)) = self.free_space(disk) {
+                        events.extend(self.check_below(Metric::DiskFree, &disk.mount_point, free, min));
+                    }
+                }
+            }
+            Message::Temperature(temperatures) => {
+                for temperature in temperatures.iter() {
+                    let (threshold, hysteresis) = (self.temperature_threshold, self.temperature_hysteresis);
+                    events.extend(self.check_between(
+                        Metric::Temperature, &temperature.label, None, temperature.value, threshold, threshold - hysteresis
+                    ));
+                    // Sensors without a critical value report 0
+                    if temperature.critical > 0.0 {
+                        let critical = temperature.critical;
+                        events.extend(self.check_between(
+                            Metric::TemperatureCritical, &temperature.label, None, temperature.value, critical, critical - hysteresis
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+        for event in events.iter() {
+            if let Some(webhook) = &self.webhook {
+                webhook.send(event);
+            }
+            if let Some(breach_command) = &self.breach_command
+                && event.state == AlertState::Breach {
+                breach_command.run(event);
+            }
+            if let Some(mailer) = &self.mailer
+                && event.state == AlertState::Breach {
+                mailer.send(event);
+            }
+        }
+        for event in events.iter() {
+            if self.history.len() == Self::HISTORY {
+                self.history.pop_front();
+            }
+            self.history.push_back(event.clone());
+        }
+        events
+    }
+
+    /// Raises an event once a matching process has been above a limit of a
+    /// rule for its whole duration, and when it is back under or gone.
+    fn check_rules(&mut self, processes: &[Process], local: bool) -> Vec<AlertEvent> {
+        let now = Instant::now();
+        let mut events = Vec::new();
+        let mut seen: HashSet<RuleKey> = HashSet::new();
+        for (index, rule) in self.rules.iter().enumerate() {
+            let limits = [(Metric::ProcessCpu, rule.cpu_above), (Metric::ProcessMemory, rule.mem_above)];
+            let duration = rule.duration.unwrap_or(Duration::ZERO);
+            for process in processes.iter().filter(|process| process.process_name == rule.process) {
+                for (metric, limit) in limits.iter() {
+                    let Some(limit) = limit else {
+                        continue;
+                    };
+                    let key = (index, *metric, process.pid);
+                    let value = usage(*metric, process);
+                    if value <= *limit {
+                        continue;
+                    }
+                    seen.insert(key);
+                    let since = *self.above_since.entry(key).or_insert(now);
+                    if now.duration_since(since) >= duration && !self.rule_breaches.contains_key(&key) {
+                        self.rule_breaches.insert(key, *limit);
+                        let mut event = Self::event(*metric, rule.label(), Some(process.pid), AlertState::Breach, value, *limit);
+                        if self.watchdog && let Some(action) = rule.action {
+                            event.action = Some(match local {
+                                true => watchdog::act(action, rule.renice_by, process.pid),
+                                false => "not taken on a remote host".to_string(),
+                            });
+                        }
+                        events.push(event);
+                    }
+                }
+            }
+        }
+        self.above_since.retain(|key, _| seen.contains(key));
+        let resolved: Vec<RuleKey> = self.rule_breaches.keys().filter(|key| !seen.contains(key)).copied().collect();
+        for key in resolved {
+            let (index, metric, pid) = key;
+            let limit = self.rule_breaches.remove(&key).unwrap_or_default();
+            // Exited processes resolve with no usage
+            let value = processes.iter()
+                .find(|process| process.pid == pid)
+                .map(|process| usage(metric, process))
+                .unwrap_or_default();
+            events.push(Self::event(metric, self.rules[index].label(), Some(pid), AlertState::Resolved, value, limit));
+        }
+        events
+    }
+
+    fn check(
+        &mut self,
+        metric: Metric,
+        source: &str,
+        pid: Option<u32>,
+        value: f32,
+        threshold: f32,
+    ) -> Option<AlertEvent> {
+        self.check_between(metric, source, pid, value, threshold, threshold)
+    }
+
+    /// Like `check`, resolved only once below `resolve_below` so that a
+    /// reading hovering around the threshold doesn't flap.
+    fn check_between(
+        &mut self,
+        metric: Metric,
+        source: &str,
+        pid: Option<u32>,
+        value: f32,
+        threshold: f32,
+        resolve_below: f32,
+    ) -> Option<AlertEvent> {
+        let state = self.transition(metric, source, value >= threshold, value < resolve_below)?;
+        Some(Self::event(metric, source, pid, state, value, threshold))
+    }
+
+    /// Like `check` for limits that are breached from below.
+    fn check_below(&mut self, metric: Metric, source: &str, value: f32, threshold: f32) -> Option<AlertEvent> {
+        let state = self.transition(metric, source, value < threshold, value >= threshold)?;
+        Some(Self::event(metric, source, None, state, value, threshold))
+    }
+
+    fn transition(&mut self, metric: Metric, source: &str, breached: bool, resolved: bool) -> Option<AlertState> {
+        let key = (metric, source.to_string());
+        if breached && !self.breached.contains(&key) {
+            self.breached.insert(key);
+            Some(AlertState::Breach)
+        } else if resolved && self.breached.remove(&key) {
+            Some(AlertState::Resolved)
+        } else {
+            None
+        }
+    }
+
+    fn event(metric: Metric, source: &str, pid: Option<u32>, state: AlertState, value: f32, threshold: f32) -> AlertEvent {
+        AlertEvent {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            metric,
+            source: source.to_string(),
+            pid,
+            state,
+            value,
+            threshold,
+            action: None,
+        }
+    }
+}
+
+/// What a process rule compares to its limit for the metric.
+fn usage(metric: Metric, process: &Process) -> f32 {
+    match metric {
+        Metric::ProcessCpu => process.cpu_usage,
+        _ => process.mem_usage,
+    }
+}
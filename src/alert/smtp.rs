@@ -0,0 +1,285 @@
+use std::{io, sync::Arc, time::Duration};
+use base64::Engine;
+use rustls_platform_verifier::ConfigVerifierExt;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader},
+    net::TcpStream,
+};
+use tokio_rustls::{rustls::{pki_types::ServerName, ClientConfig}, TlsConnector};
+
+use crate::{
+    alert::AlertEvent,
+    app::config::{SmtpConfig, SmtpSecurity},
+    cmd::utils::local_time,
+};
+
+/// Mails every breach to the recipients of `alerts.smtp`.
+pub struct Mailer {
+    config: Arc<SmtpConfig>,
+    credentials: Option<Arc<(String, String)>>,
+    hostname: String,
+}
+
+impl Mailer {
+    /// Time a whole delivery may take, from connecting to `QUIT`.
+    const TIMEOUT: Duration = Duration::from_secs(30);
+
+    pub fn new(config: SmtpConfig) -> Self {
+        let credentials = std::env::var("HTOPUI_SMTP_USERNAME")
+            .ok()
+            .zip(std::env::var("HTOPUI_SMTP_PASSWORD").ok())
+            .map(Arc::new);
+        Self {
+            config: Arc::new(config),
+            credentials,
+            hostname: sysinfo::System::host_name().unwrap_or_else(|| "localhost".to_string()),
+        }
+    }
+
+    pub fn send(&self, event: &AlertEvent) {
+        let config = self.config.clone();
+        let credentials = self.credentials.clone();
+        let hostname = self.hostname.clone();
+        let message = message(&config, &hostname, event);
+        tokio::spawn(async move {
+            let delivery = deliver(&config, credentials.as_deref(), &hostname, &message);
+            match tokio::time::timeout(Self::TIMEOUT, delivery).await {
+                Ok(Ok(())) => {}
+                Ok(Err(err)) => log::warn!("Error mailing alert through {}: {}", config.server, err),
+                Err(_) => log::warn!("Mailing alert through {} timed out", config.server),
+            }
+        });
+    }
+}
+
+/// Headers and body of the mail, lines ending in CRLF and dot-stuffed for
+/// `DATA`. A process name can hold line breaks, which would otherwise add
+/// headers or end the data early.
+fn message(config: &SmtpConfig, hostname: &str, event: &AlertEvent) -> String {
+    let mut body = vec![
+        format!("{} {} on {}", event.metric, event.source, hostname),
+        format!("Value: {:.1}, threshold: {:.1}", event.value, event.threshold),
+    ];
+    if let Some(pid) = event.pid {
+        body.push(format!("Process: {}", pid));
+    }
+    if let Some(action) = &event.action {
+        body.push(format!("Watchdog: {}", action));
+    }
+    body.push(format!("Time: {}", local_time(event.timestamp, c"%Y-%m-%d %H:%M:%S")));
+    let headers = [
+        format!("From: {}", config.from),
+        format!("To: {}", config.to.join(", ")),
+        format!("Subject: [htop-ui] {} {} at {:.1} on {}", event.metric, event.source, event.value, hostname),
+        format!("Date: {}", date(event.timestamp)),
+        "Content-Type: text/plain; charset=utf-8".to_string(),
+    ];
+    let headers = headers.into_iter().map(|header| header.replace(['\r', '\n'], " "));
+    let body = body.iter().flat_map(|line| line.split(['\r', '\n']).filter(|line| !line.is_empty()));
+    headers
+        .chain(std::iter::once(String::new()))
+        .chain(body.map(str::to_string))
+        .map(|line| match line.starts_with('.') {
+            true => format!(".{}\r\n", line),
+            false => format!("{}\r\n", line),
+        })
+        .collect()
+}
+
+/// RFC 5322 date in UTC, such as `Wed, 14 Oct 2026 19:00:00 +0000`. Worked
+/// out here since `%z` of strftime is a zone name on Windows.
+fn date(epoch: u64) -> String {
+    const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+    const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+    let days = epoch / 86400;
+    let secs = epoch % 86400;
+    // Civil date from days since 1970-01-01, in eras of 400 years starting
+    // on March 1st
+    let shifted = days + 719468;
+    let era = shifted / 146097;
+    let day_of_era = shifted % 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 2 } else { month_index - 10 };
+    let year = year_of_era + era * 400 + (month < 2) as u64;
+    format!(
+        "{}, {:02} {} {} {:02}:{:02}:{:02} +0000",
+        WEEKDAYS[(days % 7) as usize], day, MONTHS[month as usize], year, secs / 3600, secs % 3600 / 60, secs % 60
+    )
+}
+
+async fn deliver(
+    config: &SmtpConfig,
+    credentials: Option<&(String, String)>,
+    hostname: &str,
+    message: &str,
+) -> io::Result<()> {
+    if credentials.is_some() && config.security() == SmtpSecurity::None {
+        return Err(io::Error::other("credentials are only sent over tls or starttls"));
+    }
+    let stream = TcpStream::connect((config.server.as_str(), config.port())).await?;
+    match config.security() {
+        SmtpSecurity::None => {
+            let mut session = Session::new(stream);
+            session.reply(2).await?;
+            session.transact(config, credentials, hostname, message).await
+        }
+        SmtpSecurity::Tls => {
+            let mut session = Session::new(tls(&config.server, stream).await?);
+            session.reply(2).await?;
+            session.transact(config, credentials, hostname, message).await
+        }
+        SmtpSecurity::StartTls => {
+            let mut session = Session::new(stream);
+            session.reply(2).await?;
+            session.command(&format!("EHLO {}", hostname), 2).await?;
+            session.command("STARTTLS", 2).await?;
+            let stream = session.stream.into_inner();
+            // The server forgets everything said before the upgrade
+            let mut session = Session::new(tls(&config.server, stream).await?);
+            session.transact(config, credentials, hostname, message).await
+        }
+    }
+}
+
+async fn tls(server: &str, stream: TcpStream) -> io::Result<tokio_rustls::client::TlsStream<TcpStream>> {
+    let config = ClientConfig::with_platform_verifier().map_err(io::Error::other)?;
+    let name = ServerName::try_from(server.to_string()).map_err(io::Error::other)?;
+    TlsConnector::from(Arc::new(config)).connect(name, stream).await
+}
+
+struct Session<S> {
+    stream: BufReader<S>,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> Session<S> {
+    fn new(stream: S) -> Self {
+        Self { stream: BufReader::new(stream) }
+    }
+
+    /// Reads a reply, whose lines but the last have a `-` after the code,
+    /// and fails unless the code starts with `class`.
+    async fn reply(&mut self, class: u8) -> io::Result<String> {
+        let mut reply = String::new();
+        loop {
+            let mut line = String::new();
+            if self.stream.read_line(&mut line).await? == 0 {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "server closed the connection"));
+            }
+            reply.push_str(&line);
+            if line.as_bytes().get(3) != Some(&b'-') {
+                break;
+            }
+        }
+        match reply.as_bytes().first() {
+            Some(code) if *code == b'0' + class => Ok(reply),
+            _ => Err(io::Error::other(format!("server answered {}", reply.trim_end()))),
+        }
+    }
+
+    async fn command(&mut self, command: &str, class: u8) -> io::Result<String> {
+        let stream = self.stream.get_mut();
+        stream.write_all(command.as_bytes()).await?;
+        stream.write_all(b"\r\n").await?;
+        stream.flush().await?;
+        self.reply(class).await
+    }
+
+    /// Greets, logs in when there are credentials and hands the message over.
+    async fn transact(
+        &mut self,
+        config: &SmtpConfig,
+        credentials: Option<&(String, String)>,
+        hostname: &str,
+        message: &str,
+    ) -> io::Result<()> {
+        self.command(&format!("EHLO {}", hostname), 2).await?;
+        if let Some((username, password)) = credentials {
+            let token = base64::engine::general_purpose::STANDARD.encode(format!("\0{}\0{}", username, password));
+            self.command(&format!("AUTH PLAIN {}", token), 2).await?;
+        }
+        self.command(&format!("MAIL FROM:<{}>", config.from), 2).await?;
+        for recipient in config.to.iter() {
+            self.command(&format!("RCPT TO:<{}>", recipient), 2).await?;
+        }
+        self.command("DATA", 3).await?;
+        let stream = self.stream.get_mut();
+        stream.write_all(message.as_bytes()).await?;
+        self.command(".", 2).await?;
+        self.command("QUIT", 2).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alert::{AlertState, Metric};
+
+    fn config() -> SmtpConfig {
+        SmtpConfig {
+            server: "smtp.example.com".to_string(),
+            port: None,
+            security: None,
+            from: "htop-ui@example.com".to_string(),
+            to: vec!["ops@example.com".to_string(), "dev@example.com".to_string()],
+        }
+    }
+
+    fn event(source: &str, action: Option<&str>) -> AlertEvent {
+        AlertEvent {
+            timestamp: 1791918000,
+            metric: Metric::ProcessCpu,
+            source: source.to_string(),
+            pid: Some(42),
+            state: AlertState::Breach,
+            value: 97.5,
+            threshold: 90.0,
+            action: action.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn lines_end_in_crlf() {
+        let message = message(&config(), "nas", &event("postgres", None));
+        assert!(message.ends_with("\r\n"));
+        assert!(!message.replace("\r\n", "").contains(['\r', '\n']));
+        let lines: Vec<&str> = message.split("\r\n").collect();
+        assert!(lines.contains(&"To: ops@example.com, dev@example.com"));
+        assert!(lines.contains(&"Date: Tue, 13 Oct 2026 19:00:00 +0000"));
+        // A blank line between the headers and the body
+        let blank = lines.iter().position(|line| line.is_empty()).unwrap();
+        assert!(lines[..blank].iter().all(|line| line.contains(": ")));
+        assert!(lines[blank + 1].contains("postgres on nas"));
+    }
+
+    #[test]
+    fn lines_starting_with_a_dot_are_stuffed() {
+        let message = message(&config(), "nas", &event("worker\n.hidden\n.", None));
+        assert!(message.contains("\r\n..hidden\r\n"));
+        assert!(message.contains("\r\n.. on nas\r\n"));
+    }
+
+    #[test]
+    fn line_breaks_in_a_process_name_add_no_headers_or_commands() {
+        let source = "evil\r\nBcc: victim@example.com\r\n.\r\nMAIL FROM:<spam@example.com>";
+        let message = message(&config(), "nas", &event(source, None));
+        let lines: Vec<&str> = message.split("\r\n").collect();
+        let blank = lines.iter().position(|line| line.is_empty()).unwrap();
+        assert!(!lines[..blank].iter().any(|line| line.starts_with("Bcc:")));
+        assert!(lines[..blank].iter().any(|line| line.starts_with("Subject: ") && line.contains("Bcc: victim")));
+        // The data only ends at the `.` sent after it
+        assert!(!lines.contains(&"."));
+        assert!(lines.contains(&".."));
+        assert!(!message.replace("\r\n", "").contains(['\r', '\n']));
+    }
+
+    #[test]
+    fn dates_are_in_utc() {
+        assert_eq!(date(0), "Thu, 01 Jan 1970 00:00:00 +0000");
+        assert_eq!(date(951782400), "Tue, 29 Feb 2000 00:00:00 +0000");
+        assert_eq!(date(4102444799), "Thu, 31 Dec 2099 23:59:59 +0000");
+    }
+}
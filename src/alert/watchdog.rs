@@ -0,0 +1,22 @@
+use crate::{app::config::RuleAction, cmd::control::{self, Signal}};
+
+/// Nice value added by `renice` when the rule doesn't say.
+const RENICE_BY: i32 = 10;
+
+/// Takes the action of a breached rule on the process and describes what
+/// happened, failures included, for the alert history.
+pub fn act(action: RuleAction, renice_by: Option<i32>, pid: u32) -> String {
+    let (name, result) = match action {
+        RuleAction::Term => ("SIGTERM", control::send_signal(pid, Signal::Term).map(|_| "sent SIGTERM".to_string())),
+        RuleAction::Kill => ("SIGKILL", control::send_signal(pid, Signal::Kill).map(|_| "sent SIGKILL".to_string())),
+        RuleAction::Renice => ("renice", control::renice(pid, renice_by.unwrap_or(RENICE_BY))
+            .map(|niceness| format!("reniced to {}", niceness))),
+    };
+    match result {
+        Ok(done) => done,
+        Err(err) => {
+            log::warn!("Watchdog {} of {} failed: {}", name, pid, err);
+            format!("{} failed: {}", name, err)
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use crate::alert::AlertEvent;
+
+/// Posts every alert event as JSON to a configured URL.
+pub struct Webhook {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl Webhook {
+    pub fn new(url: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+        }
+    }
+
+    pub fn send(&self, event: &AlertEvent) {
+        let request = self.client.post(&self.url).json(event);
+        tokio::spawn(async move {
+            match request.send().await {
+                Ok(response) if !response.status().is_success() => {
+                    log::warn!("Webhook responded with {}", response.status());
+                }
+                Err(err) => log::warn!("Error sending webhook: {}", err),
+                _ => {}
+            }
+        });
+    }
+}
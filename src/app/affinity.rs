@@ -0,0 +1,118 @@
+use crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{app::{cpu_ranges, App}, cmd::affinity};
+
+/// Cores shown on each line of the editor.
+const CORES_PER_LINE: usize = 8;
+
+/// Affinity of one process being edited in a popup over the main view.
+pub(super) struct AffinityEditor {
+    pid: u32,
+    process_name: String,
+    cores: Vec<bool>,
+    cursor: usize,
+}
+
+impl App {
+    /// Opens the editor with the current affinity of the selected process.
+    pub(super) fn open_affinity(&mut self) {
+        let Some(process) = self.state.selected().and_then(|row| self.processes.get(row)) else {
+            return;
+        };
+        if self.remote.is_some() {
+            self.status = Some("The affinity of remote processes cannot be changed".to_string());
+            return;
+        }
+        match affinity::get(process.pid) {
+            Ok(cores) => {
+                self.affinity = Some(AffinityEditor {
+                    pid: process.pid,
+                    process_name: process.process_name.clone(),
+                    cores,
+                    cursor: 0,
+                });
+            }
+            Err(err) => self.status = Some(format!("Error reading the affinity of {}: {}", process.pid, err)),
+        }
+    }
+
+    pub(super) fn handle_affinity_key(&mut self, code: KeyCode) {
+        let Some(editor) = self.affinity.as_mut() else {
+            return;
+        };
+        let last = editor.cores.len().saturating_sub(1);
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => self.affinity = None,
+            KeyCode::Char('l') | KeyCode::Right => editor.cursor = (editor.cursor + 1).min(last),
+            KeyCode::Char('h') | KeyCode::Left => editor.cursor = editor.cursor.saturating_sub(1),
+            KeyCode::Char('j') | KeyCode::Down => editor.cursor = (editor.cursor + CORES_PER_LINE).min(last),
+            KeyCode::Char('k') | KeyCode::Up => editor.cursor = editor.cursor.saturating_sub(CORES_PER_LINE),
+            KeyCode::Char(' ') => editor.cores[editor.cursor] = !editor.cores[editor.cursor],
+            KeyCode::Char('a') => {
+                let all = editor.cores.iter().all(|enabled| *enabled);
+                editor.cores.iter_mut().for_each(|enabled| *enabled = !all);
+            }
+            KeyCode::Enter => self.apply_affinity(),
+            _ => {}
+        }
+    }
+
+    fn apply_affinity(&mut self) {
+        let Some(editor) = self.affinity.as_ref() else {
+            return;
+        };
+        if !editor.cores.contains(&true) {
+            self.status = Some("Select at least one core".to_string());
+            return;
+        }
+        self.status = Some(match affinity::set(editor.pid, &editor.cores) {
+            Ok(()) => format!("Set the affinity of {} to {}", editor.pid, cpu_ranges(&enabled_cores(&editor.cores))),
+            Err(err) => format!("Error setting the affinity of {}: {}", editor.pid, err),
+        });
+        self.affinity = None;
+    }
+
+    pub(super) fn render_affinity(&self, frame: &mut Frame) {
+        let Some(editor) = self.affinity.as_ref() else {
+            return;
+        };
+        let mut text: Vec<Line> = editor.cores
+            .chunks(CORES_PER_LINE)
+            .enumerate()
+            .map(|(line, cores)| {
+                Line::from(cores.iter().enumerate().map(|(index, enabled)| {
+                    let core = line * CORES_PER_LINE + index;
+                    let span = Span::raw(format!(" [{}] {:<3}", if *enabled { "x" } else { " " }, core));
+                    match core == editor.cursor {
+                        true => span.add_modifier(Modifier::REVERSED),
+                        false => span,
+                    }
+                }).collect::<Vec<_>>())
+            })
+            .collect();
+        text.push(Line::from(""));
+        text.push(Line::from("space toggle, a all, enter apply, esc cancel").centered());
+
+        let width = (CORES_PER_LINE.min(editor.cores.len()) * 9) as u16 + 2;
+        let [area] = Layout::vertical([Constraint::Length(text.len() as u16 + 2)])
+            .flex(layout::Flex::Center)
+            .areas(frame.area());
+        let [area] = Layout::horizontal([Constraint::Length(width.max(48))])
+            .flex(layout::Flex::Center)
+            .areas(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(text)
+                .fg(self.style.cpu_frame_fg)
+                .block(Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("CPU affinity of {} {}", editor.pid, editor.process_name))),
+            area
+        );
+    }
+}
+
+fn enabled_cores(cores: &[bool]) -> Vec<usize> {
+    (0..cores.len()).filter(|core| cores[*core]).collect()
+}
@@ -0,0 +1,71 @@
+use crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{alert::AlertState, app::{App, View}, cmd::utils::local_time};
+
+impl App {
+    pub(super) fn handle_alerts_key(&mut self, code: KeyCode) {
+        let count = self.alerts.history().len();
+        let selected = self.alert_state.selected().unwrap_or(0);
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Char('A') => self.view = View::Main,
+            KeyCode::Char('j') | KeyCode::Down if count > 0 => {
+                self.alert_state.select(Some((selected + 1).min(count - 1)));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.alert_state.select(Some(selected.saturating_sub(1)));
+            }
+            _ => {}
+        }
+    }
+
+    /// Alerts raised and resolved since startup, newest first, along with
+    /// what the watchdog did about them.
+    pub(super) fn render_alerts(&mut self, frame: &mut Frame, area: Rect) {
+        let header = ["Time", "Metric", "Source", "PID", "State", "Value", "Threshold", "Action"]
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .height(1);
+        let rows = self.alerts.history().iter().rev().map(|event| {
+            let state = match event.state {
+                AlertState::Breach => Cell::from("breach").fg(self.style.exceed_threshold_cell),
+                AlertState::Resolved => Cell::from("resolved"),
+            };
+            Row::new(vec![
+                Cell::from(local_time(event.timestamp, c"%H:%M:%S")),
+                Cell::from(event.metric.to_string()),
+                Cell::from(event.source.clone()),
+                Cell::from(event.pid.map(|pid| pid.to_string()).unwrap_or_default()),
+                state,
+                Cell::from(format!("{:.1}", event.value)),
+                Cell::from(format!("{:.1}", event.threshold)),
+                Cell::from(event.action.clone().unwrap_or_default()),
+            ])
+        });
+        let title = match self.config.alerts.watchdog.unwrap() {
+            true => "Alerts, watchdog on (A to close)",
+            false => "Alerts (A to close)",
+        };
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(9),
+                Constraint::Length(21),
+                Constraint::Fill(1),
+                Constraint::Length(8),
+                Constraint::Length(9),
+                Constraint::Length(8),
+                Constraint::Length(10),
+                Constraint::Fill(2),
+            ],
+        )
+        .header(header)
+        .fg(self.style.table_fg)
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_spacing(HighlightSpacing::Always)
+        .block(Block::default().borders(Borders::ALL).title(title));
+
+        frame.render_stateful_widget(table, area, &mut self.alert_state);
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::{BTreeMap, HashSet};
+use crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{
+    app::App,
+    cmd::{process::Process, units},
+};
+
+/// Shown for processes whose cgroup could not be read.
+const UNKNOWN_CGROUP: &str = "(unknown)";
+
+/// A row of the grouped table, either a cgroup with its totals or one of
+/// its processes below an expanded group.
+enum CgroupRow<'a> {
+    Group {
+        path: &'a str,
+        count: usize,
+        cpu_usage: f32,
+        mem_usage: f32,
+        memory: u64,
+        expanded: bool,
+    },
+    Process(&'a Process),
+}
+
+impl App {
+    fn cgroup_rows<'a>(processes: &'a [Process], expanded: &HashSet<String>) -> Vec<CgroupRow<'a>> {
+        let mut groups: BTreeMap<&str, Vec<&Process>> = BTreeMap::new();
+        for process in processes {
+            groups
+                .entry(process.cgroup.as_deref().unwrap_or(UNKNOWN_CGROUP))
+                .or_default()
+                .push(process);
+        }
+        let mut groups: Vec<(&str, Vec<&Process>, f32)> = groups
+            .into_iter()
+            .map(|(path, members)| {
+                let cpu_usage = members.iter().map(|process| process.cpu_usage).sum();
+                (path, members, cpu_usage)
+            })
+            .collect();
+        groups.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut rows = Vec::new();
+        for (path, members, cpu_usage) in groups {
+            let expanded = expanded.contains(path);
+            rows.push(CgroupRow::Group {
+                path,
+                count: members.len(),
+                cpu_usage,
+                mem_usage: members.iter().map(|process| process.mem_usage).sum(),
+                memory: members.iter().map(|process| process.memory).sum(),
+                expanded,
+            });
+            if expanded {
+                // Members keep the CPU order of the process list
+                rows.extend(members.into_iter().map(CgroupRow::Process));
+            }
+        }
+        rows
+    }
+
+    /// Handles the keys the grouped table uses, returns false for the rest.
+    pub(super) fn handle_cgroup_key(&mut self, code: KeyCode) -> bool {
+        let count = Self::cgroup_rows(&self.processes, &self.expanded_cgroups).len();
+        let selected = self.cgroup_state.selected().unwrap_or(0);
+        let last = count.saturating_sub(1);
+        let row = match code {
+            KeyCode::Char('j') | KeyCode::Down => (selected + 1).min(last),
+            KeyCode::Char('k') | KeyCode::Up => selected.saturating_sub(1),
+            KeyCode::PageDown => (selected + self.page_size).min(last),
+            KeyCode::PageUp => selected.saturating_sub(self.page_size),
+            KeyCode::Home => 0,
+            KeyCode::End => last,
+            KeyCode::Enter => {
+                self.toggle_cgroup(selected);
+                selected
+            }
+            _ => return false,
+        };
+        self.cgroup_state.select(Some(row));
+        true
+    }
+
+    fn toggle_cgroup(&mut self, row: usize) {
+        let rows = Self::cgroup_rows(&self.processes, &self.expanded_cgroups);
+        let Some(CgroupRow::Group { path, .. }) = rows.get(row) else {
+            return;
+        };
+        let path = path.to_string();
+        if !self.expanded_cgroups.remove(&path) {
+            self.expanded_cgroups.insert(path);
+        }
+    }
+
+    pub(super) fn render_cgroups(&mut self, frame: &mut Frame, area: Rect) {
+        let header = ["Cgroup / Name", "Procs/PID", "CPU %", "Mem %", "RES"]
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .height(1);
+        let cgroup_rows = Self::cgroup_rows(&self.processes, &self.expanded_cgroups);
+        self.page_size = (area.height.saturating_sub(3) as usize).max(1);
+        if self.cgroup_state.selected().is_some_and(|row| row >= cgroup_rows.len()) {
+            self.cgroup_state.select(Some(cgroup_rows.len().saturating_sub(1)));
+        }
+        let rows: Vec<Row> = cgroup_rows.iter().map(|row| match row {
+            CgroupRow::Group { path, count, cpu_usage, mem_usage, memory, expanded } => {
+                let marker = if *expanded { "▾" } else { "▸" };
+                Row::new(vec![
+                    Cell::from(format!("{} {}", marker, path)),
+                    Cell::from(count.to_string()),
+                    Cell::from(format!("{:.1}%", cpu_usage)),
+                    Cell::from(format!("{:.1}%", mem_usage)),
+                    Cell::from(units::bytes(*memory)),
+                ]).bold()
+            }
+            CgroupRow::Process(process) => Row::new(vec![
+                Cell::from(format!("    {}", process.display_name())),
+                Cell::from(process.pid.to_string()),
+                Cell::from(format!("{:.1}%", process.cpu_usage)),
+                Cell::from(format!("{:.1}%", process.mem_usage)),
+                Cell::from(units::bytes(process.memory)),
+            ]).fg(self.state_color(&process.state).unwrap_or(self.style.table_fg)),
+        }).collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(20),
+                Constraint::Length(9),
+                Constraint::Length(7),
+                Constraint::Length(8),
+                Constraint::Length(10),
+            ],
+        )
+        .header(header)
+        .fg(self.style.table_fg)
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED).fg(self.style.selected_row))
+        .highlight_spacing(HighlightSpacing::Always)
+        .block(Block::default()
+                .borders(Borders::ALL)
+                .title("Processes by cgroup (enter to expand, G to ungroup)")
+                .title_bottom(Line::from(self.status.clone().unwrap_or_default()).right_aligned()));
+
+        frame.render_stateful_widget(table, area, &mut self.cgroup_state);
+    }
+}
@@ -0,0 +1,324 @@
+use crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+use std::{cmp::Ordering, time::{SystemTime, UNIX_EPOCH}};
+
+use crate::{
+    app::{config::{AppConfig, ColumnConfig, CpuMode, ProcessColumn}, App, View},
+    cmd::{process::Process, units, utils::{local_time, seconds_to_timestamp}},
+};
+
+impl ProcessColumn {
+    fn header(&self, cpu_mode: CpuMode) -> &'static str {
+        match self {
+            ProcessColumn::Pid => "PID",
+            ProcessColumn::Tgid => "TGID",
+            ProcessColumn::Name => "Name",
+            ProcessColumn::User => "User",
+            ProcessColumn::Cpu => match cpu_mode {
+                CpuMode::Irix => "CPU % (Irix)",
+                CpuMode::Solaris => "CPU % (Solaris)",
+            },
+            ProcessColumn::Mem => "Memory %",
+            ProcessColumn::Res => "RES",
+            ProcessColumn::State => "State",
+            ProcessColumn::Nice => "Nice",
+            ProcessColumn::DiskIo => "Disk I/O",
+            ProcessColumn::Start => "Start",
+            ProcessColumn::Time => "TIME+",
+            ProcessColumn::Command => "Command",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            ProcessColumn::Pid => "Process ID, the thread ID for threads",
+            ProcessColumn::Tgid => "Process a thread belongs to",
+            ProcessColumn::Name => "Executable name",
+            ProcessColumn::User => "Owner of the process",
+            ProcessColumn::Cpu => "CPU usage, Irix or Solaris mode",
+            ProcessColumn::Mem => "Share of the total memory",
+            ProcessColumn::Res => "Resident memory",
+            ProcessColumn::State => "R running, S sleeping, D disk wait, Z zombie, T stopped",
+            ProcessColumn::Nice => "Nice value, -20 to 19",
+            ProcessColumn::DiskIo => "Disk reads and writes per second",
+            ProcessColumn::Start => "Start time, the date for processes older than a day",
+            ProcessColumn::Time => "Time since the process started",
+            ProcessColumn::Command => "Full command line",
+        }
+    }
+
+    /// Text columns start out ascending, numbers with the largest first.
+    fn sorts_descending(&self) -> bool {
+        !matches!(self, ProcessColumn::Name | ProcessColumn::User | ProcessColumn::State | ProcessColumn::Command)
+    }
+
+    fn compare(&self, a: &Process, b: &Process) -> Ordering {
+        match self {
+            ProcessColumn::Pid => a.pid.cmp(&b.pid),
+            ProcessColumn::Tgid => a.tgid().cmp(&b.tgid()),
+            ProcessColumn::Name => a.process_name.to_lowercase().cmp(&b.process_name.to_lowercase()),
+            ProcessColumn::User => a.user.cmp(&b.user),
+            ProcessColumn::Cpu => a.cpu_usage.total_cmp(&b.cpu_usage),
+            // The percentage is rounded, the resident size tells close ones apart
+            ProcessColumn::Mem | ProcessColumn::Res => a.memory.cmp(&b.memory),
+            ProcessColumn::State => a.state.cmp(&b.state),
+            ProcessColumn::Nice => a.nice.cmp(&b.nice),
+            ProcessColumn::DiskIo => (a.disk_read + a.disk_write).total_cmp(&(b.disk_read + b.disk_write)),
+            ProcessColumn::Start => a.start_time.cmp(&b.start_time),
+            ProcessColumn::Time => a.run_time.cmp(&b.run_time),
+            ProcessColumn::Command => a.command.cmp(&b.command),
+        }
+    }
+
+    fn default_width(&self) -> Constraint {
+        match self {
+            ProcessColumn::Pid => Constraint::Length(8),
+            ProcessColumn::Tgid => Constraint::Length(8),
+            ProcessColumn::Name => Constraint::Min(15),
+            ProcessColumn::User => Constraint::Min(10),
+            ProcessColumn::Cpu => Constraint::Length(15),
+            ProcessColumn::Mem => Constraint::Length(10),
+            ProcessColumn::Res => Constraint::Length(10),
+            ProcessColumn::State => Constraint::Length(6),
+            ProcessColumn::Nice => Constraint::Length(5),
+            ProcessColumn::DiskIo => Constraint::Length(12),
+            ProcessColumn::Start => Constraint::Length(7),
+            ProcessColumn::Time => Constraint::Length(15),
+            ProcessColumn::Command => Constraint::Min(20),
+        }
+    }
+}
+
+impl ColumnConfig {
+    /// The configured size, `width` taking precedence over `min` and `max`.
+    pub(super) fn constraint(&self) -> Constraint {
+        match (self.width, self.min, self.max) {
+            (Some(width), _, _) => Constraint::Length(width),
+            (None, Some(min), _) => Constraint::Min(min),
+            (None, None, Some(max)) => Constraint::Max(max),
+            (None, None, None) => self.column.default_width(),
+        }
+    }
+}
+
+impl App {
+    pub(super) fn column_header(&self, column: ProcessColumn) -> &'static str {
+        column.header(self.cpu_mode)
+    }
+
+    /// The header with an arrow on the column the table is sorted by.
+    pub(super) fn sorted_column_header(&self, column: ProcessColumn) -> String {
+        match (column == self.sort_column, self.sort_descending) {
+            (true, true) => format!("{} ▼", self.column_header(column)),
+            (true, false) => format!("{} ▲", self.column_header(column)),
+            (false, _) => self.column_header(column).to_string(),
+        }
+    }
+
+    pub(super) fn sort_processes(&mut self) {
+        let column = self.sort_column;
+        let descending = self.sort_descending;
+        self.processes.sort_by(|a, b| {
+            let ordering = column.compare(a, b).then_with(|| a.pid.cmp(&b.pid));
+            if descending { ordering.reverse() } else { ordering }
+        });
+        if self.tree_view {
+            self.arrange_tree();
+        }
+    }
+
+    /// Sorts by `column`, flipping the direction when it is already sorted by.
+    pub(super) fn sort_by(&mut self, column: ProcessColumn) {
+        if self.sort_column == column {
+            self.sort_descending = !self.sort_descending;
+        } else {
+            self.sort_column = column;
+            self.sort_descending = column.sorts_descending();
+        }
+        self.sort_processes();
+    }
+
+    /// Moves the sort to the next or previous shown column.
+    pub(super) fn shift_sort_column(&mut self, forward: bool) {
+        let count = self.columns.len();
+        let current = self.columns.iter().position(|shown| shown.column == self.sort_column);
+        let index = match (current, forward) {
+            (Some(index), true) => (index + 1) % count,
+            (Some(index), false) => (index + count - 1) % count,
+            (None, _) => 0,
+        };
+        let column = self.columns[index].column;
+        if column != self.sort_column {
+            self.sort_by(column);
+        }
+    }
+
+    pub(super) fn column_cell(&self, column: ProcessColumn, process: &Process) -> Cell<'static> {
+        match column {
+            ProcessColumn::Cpu => self.alert_cell(self.column_text(column, process), self.cpu_highlight.contains(&process.pid)),
+            ProcessColumn::Mem => self.alert_cell(self.column_text(column, process), self.mem_highlight.contains(&process.pid)),
+            _ => Cell::from(self.column_text(column, process)),
+        }
+    }
+
+    pub(super) fn column_text(&self, column: ProcessColumn, process: &Process) -> String {
+        match column {
+            ProcessColumn::Pid => process.pid.to_string(),
+            ProcessColumn::Tgid => process.tgid().to_string(),
+            ProcessColumn::Name => match self.tree_prefixes.get(&process.pid) {
+                Some(prefix) if self.tree_view => format!("{}{}", prefix, process.display_name()),
+                _ => process.display_name(),
+            },
+            ProcessColumn::User => process.user.clone(),
+            ProcessColumn::Cpu => format!("{:.1}%", self.subtree_total(process).0),
+            ProcessColumn::Mem => format!("{:.1}%", self.subtree_total(process).1),
+            ProcessColumn::Res => units::bytes(process.memory),
+            ProcessColumn::State => process.state.clone(),
+            ProcessColumn::Nice => process.nice.to_string(),
+            ProcessColumn::DiskIo => units::bytes_per_second(process.disk_read + process.disk_write),
+            ProcessColumn::Start => start_time(process.start_time),
+            ProcessColumn::Time => seconds_to_timestamp(process.run_time),
+            // Kernel threads have no command line
+            ProcessColumn::Command if process.command.is_empty() => process.display_name(),
+            ProcessColumn::Command => process.command.clone(),
+        }
+    }
+
+    /// Indices of the columns drawn in `width`. Outside wide mode all of
+    /// them are squeezed in. In wide mode the first column stays and the
+    /// others start at `column_offset`, as many as fit at their full width.
+    pub(super) fn visible_columns(&mut self, widths: &[Constraint], width: u16) -> Vec<usize> {
+        if !self.wide || widths.len() < 2 {
+            return (0..widths.len()).collect();
+        }
+        self.column_offset = self.column_offset.min(widths.len() - 2);
+        let mut visible = vec![0];
+        let mut used = full_width(widths[0]);
+        for (index, constraint) in widths.iter().enumerate().skip(1 + self.column_offset) {
+            // Columns are separated by one space
+            used += 1 + full_width(*constraint);
+            if used > width && visible.len() > 1 {
+                break;
+            }
+            visible.push(index);
+        }
+        visible
+    }
+
+    /// Whether the columns are narrower than their full width in `width`.
+    pub(super) fn columns_squeezed(&self, widths: &[Constraint], width: u16) -> bool {
+        let needed: u16 = widths.iter().map(|constraint| full_width(*constraint)).sum::<u16>() + widths.len() as u16 - 1;
+        needed > width
+    }
+
+    /// Scrolls the columns in wide mode, returns whether the key was used.
+    pub(super) fn handle_wide_key(&mut self, code: KeyCode) -> bool {
+        match code {
+            KeyCode::Char('l') | KeyCode::Right => self.column_offset += 1,
+            KeyCode::Char('h') | KeyCode::Left => self.column_offset = self.column_offset.saturating_sub(1),
+            KeyCode::Char('w') | KeyCode::Esc => {
+                self.wide = false;
+                self.column_offset = 0;
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    pub(super) fn open_column_picker(&mut self) {
+        self.column_state.select(Some(0));
+        self.view = View::Columns;
+    }
+
+    pub(super) fn handle_columns_key(&mut self, code: KeyCode) {
+        let selected = self.column_state.selected().unwrap_or(0);
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Char('S') | KeyCode::F(2) => self.close_column_picker(),
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.column_state.select(Some((selected + 1).min(ProcessColumn::ALL.len() - 1)));
+            }
+            KeyCode::Char('k') | KeyCode::Up => self.column_state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Char(' ') | KeyCode::Enter => self.toggle_column(ProcessColumn::ALL[selected]),
+            _ => {}
+        }
+    }
+
+    /// Hides a shown column, or shows it after the closest shown column that
+    /// comes before it in the default order.
+    fn toggle_column(&mut self, column: ProcessColumn) {
+        if let Some(index) = self.columns.iter().position(|shown| shown.column == column) {
+            if self.columns.len() == 1 {
+                self.status = Some("At least one column must be shown".to_string());
+                return;
+            }
+            self.columns.remove(index);
+            return;
+        }
+        let preceding = &ProcessColumn::ALL[..ProcessColumn::ALL.iter().position(|c| *c == column).unwrap_or(0)];
+        let index = self.columns.iter()
+            .rposition(|shown| preceding.contains(&shown.column))
+            .map_or(0, |index| index + 1);
+        self.columns.insert(index, ColumnConfig::from(column));
+    }
+
+    /// Goes back to the main view, saving the columns when they changed.
+    fn close_column_picker(&mut self) {
+        self.view = View::Main;
+        if self.config.columns.as_ref() == Some(&self.columns) {
+            return;
+        }
+        let Some(path) = self.config_path.clone() else {
+            return;
+        };
+        self.status = Some(match AppConfig::save_columns(&path, &self.columns) {
+            Ok(()) => {
+                self.config.columns = Some(self.columns.clone());
+                format!("Saved the columns to {}", path)
+            }
+            Err(err) => format!("Error saving the columns to {}: {}", path, err),
+        });
+    }
+
+    pub(super) fn render_column_picker(&mut self, frame: &mut Frame, area: Rect) {
+        let header = ["Shown", "Column", "Description"]
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .height(1);
+        let rows = ProcessColumn::ALL.iter().map(|column| {
+            let shown = self.columns.iter().any(|shown| shown.column == *column);
+            Row::new(vec![
+                Cell::from(if shown { "[x]" } else { "[ ]" }),
+                Cell::from(self.column_header(*column)),
+                Cell::from(column.description()),
+            ])
+        });
+        let order = self.columns.iter().map(|shown| self.column_header(shown.column)).collect::<Vec<_>>().join(" | ");
+        let table = Table::new(rows, [Constraint::Length(6), Constraint::Length(16), Constraint::Min(20)])
+            .header(header)
+            .fg(self.style.table_fg)
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_spacing(HighlightSpacing::Always)
+            .block(Block::default()
+                    .borders(Borders::ALL)
+                    .title("Columns (space to toggle, q to save and close)")
+                    .title_bottom(Line::from(order)));
+
+        frame.render_stateful_widget(table, area, &mut self.column_state);
+    }
+}
+
+/// Width a column is drawn at when there is room for it.
+fn full_width(constraint: Constraint) -> u16 {
+    match constraint {
+        Constraint::Length(width) | Constraint::Min(width) | Constraint::Max(width) => width,
+        _ => 10,
+    }
+}
+
+/// `HH:MM` for processes started in the last day, `MonDD` before that.
+fn start_time(epoch: u64) -> String {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_secs());
+    let format = if now.saturating_sub(epoch) < 24 * 3600 { c"%H:%M" } else { c"%b%d" };
+    local_time(epoch, format)
+}
@@ -1,18 +1,588 @@
-use std::{fs, time::Duration};
-use serde::Deserialize;
+use std::{collections::HashSet, fs, time::Duration};
+use serde::{Deserialize, Serialize};
+
+use super::duration;
 
 #[derive(Debug, Default, Deserialize)]
 pub struct AppConfig {
-    #[serde(default)]
+    #[serde(default, deserialize_with = "duration::deserialize")]
     pub tick_rate: Option<Duration>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "duration::deserialize")]
     pub blink_threshold_rate: Option<Duration>,
     #[serde(default)]
     pub cpu_threshold: Option<f32>,
     #[serde(default)]
     pub single_cpu_threshold: Option<f32>,
     #[serde(default)]
-    pub mem_threshold: Option<f32>
+    pub mem_threshold: Option<f32>,
+    #[serde(default)]
+    pub pressure_threshold: Option<f32>,
+    /// Points below a threshold a value has to drop to lose its highlight.
+    #[serde(default)]
+    pub highlight_hysteresis: Option<f32>,
+    /// How long a value has to stay over a threshold to be highlighted.
+    #[serde(default, deserialize_with = "duration::deserialize")]
+    pub highlight_after: Option<Duration>,
+    #[serde(default)]
+    pub min_cpu_display: Option<f32>,
+    #[serde(default)]
+    pub min_mem_display: Option<f32>,
+    #[serde(default)]
+    pub show_kernel_threads: Option<bool>,
+    #[serde(default)]
+    pub show_threads: Option<bool>,
+    #[serde(default)]
+    pub process_network: Option<bool>,
+    #[serde(default)]
+    pub group_cpus_by_node: Option<bool>,
+    #[serde(default)]
+    pub cpu_mode: Option<CpuMode>,
+    #[serde(default)]
+    pub theme: Option<Theme>,
+    #[serde(default)]
+    pub alert_style: Option<AlertStyle>,
+    /// How long new processes are highlighted and exited ones kept dimmed.
+    #[serde(default, deserialize_with = "duration::deserialize")]
+    pub process_highlight: Option<Duration>,
+    #[serde(default)]
+    pub columns: Option<Vec<ColumnConfig>>,
+    /// Saves the panels shown, sorting, filters, columns and selection on
+    /// exit and restores them on launch.
+    #[serde(default)]
+    pub restore_session: Option<bool>,
+    #[serde(default)]
+    pub excluded_fs_types: Option<Vec<String>>,
+    #[serde(default)]
+    pub redacted_env_vars: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "duration::deserialize")]
+    pub process_refresh: Option<Duration>,
+    #[serde(default, deserialize_with = "duration::deserialize")]
+    pub network_refresh: Option<Duration>,
+    #[serde(default, deserialize_with = "duration::deserialize")]
+    pub connection_refresh: Option<Duration>,
+    #[serde(default, deserialize_with = "duration::deserialize")]
+    pub service_refresh: Option<Duration>,
+    #[serde(default, deserialize_with = "duration::deserialize")]
+    pub temperature_refresh: Option<Duration>,
+    #[serde(default, deserialize_with = "duration::deserialize")]
+    pub disk_refresh: Option<Duration>,
+    /// Space left on the disks, which changes far slower than their I/O.
+    #[serde(default, deserialize_with = "duration::deserialize")]
+    pub disk_usage_refresh: Option<Duration>,
+    #[serde(default, deserialize_with = "duration::deserialize")]
+    pub gpu_refresh: Option<Duration>,
+    #[serde(default, deserialize_with = "duration::deserialize")]
+    pub battery_refresh: Option<Duration>,
+    #[serde(default, deserialize_with = "duration::deserialize")]
+    pub reachability_refresh: Option<Duration>,
+    #[serde(default)]
+    pub layout: LayoutConfig,
+    #[serde(default)]
+    pub alerts: AlertsConfig,
+    #[serde(default)]
+    pub adaptive: AdaptiveConfig,
+    #[serde(default)]
+    pub ping: PingConfig,
+    #[serde(default)]
+    pub mqtt: MqttConfig,
+    #[serde(default)]
+    pub influx: InfluxConfig,
+    #[serde(default)]
+    pub statsd: StatsdConfig,
+    /// Machines shown next to this one in the hosts overview.
+    #[serde(default)]
+    pub hosts: Option<Vec<HostConfig>>,
+}
+
+/// How process CPU usage is normalized. In Irix mode a process using two
+/// cores shows 200%, in Solaris mode the usage is divided by the core count.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CpuMode {
+    Irix,
+    Solaris,
+}
+
+/// How table values over a threshold are drawn, all but `reverse` in the
+/// threshold color. Monochrome has none, so `color` shows nothing there.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertStyle {
+    /// Underlined every other `blink_threshold_rate`.
+    Blink,
+    Bold,
+    Reverse,
+    Color,
+    /// A `▲` in front of the value.
+    Icon,
+}
+
+/// Color presets, cycled at runtime with Ctrl-t.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    Dark,
+    Light,
+    Solarized,
+    Monochrome,
+}
+
+impl Theme {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Theme::Dark => "dark",
+            Theme::Light => "light",
+            Theme::Solarized => "solarized",
+            Theme::Monochrome => "monochrome",
+        }
+    }
+}
+
+/// A column of the process table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessColumn {
+    Pid,
+    Tgid,
+    Name,
+    User,
+    Cpu,
+    Mem,
+    Res,
+    State,
+    Nice,
+    DiskIo,
+    Start,
+    Time,
+    Command,
+}
+
+impl ProcessColumn {
+    pub const ALL: [ProcessColumn; 13] = [
+        ProcessColumn::Pid,
+        ProcessColumn::Tgid,
+        ProcessColumn::Name,
+        ProcessColumn::User,
+        ProcessColumn::Cpu,
+        ProcessColumn::Mem,
+        ProcessColumn::Res,
+        ProcessColumn::State,
+        ProcessColumn::Nice,
+        ProcessColumn::DiskIo,
+        ProcessColumn::Start,
+        ProcessColumn::Time,
+        ProcessColumn::Command,
+    ];
+
+    /// Name of the column in the config file.
+    pub fn key(&self) -> &'static str {
+        match self {
+            ProcessColumn::Pid => "pid",
+            ProcessColumn::Tgid => "tgid",
+            ProcessColumn::Name => "name",
+            ProcessColumn::User => "user",
+            ProcessColumn::Cpu => "cpu",
+            ProcessColumn::Mem => "mem",
+            ProcessColumn::Res => "res",
+            ProcessColumn::State => "state",
+            ProcessColumn::Nice => "nice",
+            ProcessColumn::DiskIo => "disk_io",
+            ProcessColumn::Start => "start",
+            ProcessColumn::Time => "time",
+            ProcessColumn::Command => "command",
+        }
+    }
+}
+
+/// A panel of the main view, placed in one of the `layout` regions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Meter {
+    Info,
+    Processes,
+    Cpu,
+    Memory,
+    Swap,
+    Network,
+    DiskIo,
+    Disks,
+    Sensors,
+    Reachability,
+    Ping,
+}
+
+impl Meter {
+    /// Name of the meter in the config file.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Meter::Info => "info",
+            Meter::Processes => "processes",
+            Meter::Cpu => "cpu",
+            Meter::Memory => "memory",
+            Meter::Swap => "swap",
+            Meter::Network => "network",
+            Meter::DiskIo => "disk_io",
+            Meter::Disks => "disks",
+            Meter::Sensors => "sensors",
+            Meter::Reachability => "reachability",
+            Meter::Ping => "ping",
+        }
+    }
+
+    /// Meters with a compact form drawn above the columns.
+    pub fn fits_header(&self) -> bool {
+        matches!(self, Meter::Cpu | Meter::Memory | Meter::Swap | Meter::Info)
+    }
+
+    /// Swap only has the one line form of the header.
+    pub fn fits_column(&self) -> bool {
+        *self != Meter::Swap
+    }
+}
+
+/// A column of the process table and how wide it is drawn: `width` fixes
+/// it, `min` or `max` bound it and the rest of the row is shared out.
+/// Written as a plain name like `pid`, or as a map like
+/// `{name: command, min: 30}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "ColumnEntry")]
+pub struct ColumnConfig {
+    /// Written back as the `name` of the map form.
+    #[serde(rename = "name")]
+    pub column: ProcessColumn,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<u16>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ColumnEntry {
+    Name(ProcessColumn),
+    Sized {
+        name: ProcessColumn,
+        #[serde(default)]
+        width: Option<u16>,
+        #[serde(default)]
+        min: Option<u16>,
+        #[serde(default)]
+        max: Option<u16>,
+    },
+}
+
+impl From<ColumnEntry> for ColumnConfig {
+    fn from(entry: ColumnEntry) -> Self {
+        match entry {
+            ColumnEntry::Name(column) => ColumnConfig::from(column),
+            ColumnEntry::Sized { name, width, min, max } => ColumnConfig { column: name, width, min, max },
+        }
+    }
+}
+
+impl From<ProcessColumn> for ColumnConfig {
+    fn from(column: ProcessColumn) -> Self {
+        ColumnConfig { column, width: None, min: None, max: None }
+    }
+}
+
+impl ColumnConfig {
+    /// The entry as written in the config file.
+    fn to_yaml(&self) -> String {
+        let sizes: Vec<String> = [("width", self.width), ("min", self.min), ("max", self.max)]
+            .into_iter()
+            .filter_map(|(key, value)| value.map(|value| format!("{}: {}", key, value)))
+            .collect();
+        match sizes.is_empty() {
+            true => self.column.key().to_string(),
+            false => format!("{{name: {}, {}}}", self.column.key(), sizes.join(", ")),
+        }
+    }
+}
+
+/// Backs off the render and collection intervals while the load average is
+/// above the number of cores, keeping them within the given bounds.
+#[derive(Debug, Default, Deserialize)]
+pub struct AdaptiveConfig {
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default, deserialize_with = "duration::deserialize")]
+    pub min_interval: Option<Duration>,
+    #[serde(default, deserialize_with = "duration::deserialize")]
+    pub max_interval: Option<Duration>,
+}
+
+/// System-wide thresholds that raise alert events when crossed.
+#[derive(Debug, Default, Deserialize)]
+pub struct AlertsConfig {
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    #[serde(default)]
+    pub on_breach_command: Option<String>,
+    #[serde(default)]
+    pub smtp: Option<SmtpConfig>,
+    /// Rings the terminal bell when an alert is raised.
+    #[serde(default)]
+    pub bell: Option<bool>,
+    /// Flashes the border of the panel an alert is about.
+    #[serde(default)]
+    pub flash: Option<bool>,
+    /// Lets process rules take their action, off so that a shared config
+    /// only alerts.
+    #[serde(default)]
+    pub watchdog: Option<bool>,
+    #[serde(default)]
+    pub cpu_threshold: Option<f32>,
+    #[serde(default)]
+    pub mem_threshold: Option<f32>,
+    #[serde(default)]
+    pub disk_threshold: Option<f32>,
+    #[serde(default)]
+    pub temperature_threshold: Option<f32>,
+    /// Degrees a sensor has to cool below a threshold, or its critical
+    /// value, before the alert is resolved.
+    #[serde(default)]
+    pub temperature_hysteresis: Option<f32>,
+    #[serde(default)]
+    pub rules: Option<Vec<ProcessRule>>,
+    #[serde(default)]
+    pub disk_free: Option<Vec<DiskFreeRule>>,
+    /// Mount points left out of the disk alerts.
+    #[serde(default)]
+    pub ignored_mounts: Option<Vec<String>>,
+}
+
+/// Mail server breaches are sent through. The user name and password, if
+/// the server wants them, come from `HTOPUI_SMTP_USERNAME` and
+/// `HTOPUI_SMTP_PASSWORD` and are only sent over TLS.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SmtpConfig {
+    pub server: String,
+    /// 587 for `starttls`, 465 for `tls` and 25 for `none` when left out.
+    #[serde(default)]
+    pub port: Option<u16>,
+    #[serde(default)]
+    pub security: Option<SmtpSecurity>,
+    pub from: String,
+    pub to: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpSecurity {
+    /// Upgrades a plain connection, the default.
+    StartTls,
+    /// TLS from the start.
+    Tls,
+    /// Plain text, only for relays on the same host or network that take
+    /// mail without logging in.
+    None,
+}
+
+impl SmtpConfig {
+    pub fn security(&self) -> SmtpSecurity {
+        self.security.unwrap_or(SmtpSecurity::StartTls)
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port.unwrap_or(match self.security() {
+            SmtpSecurity::StartTls => 587,
+            SmtpSecurity::Tls => 465,
+            SmtpSecurity::None => 25,
+        })
+    }
+}
+
+/// Free space the file system mounted at `mount` needs, either in percent
+/// of its size or in GB. The bar of the mount turns red below it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DiskFreeRule {
+    pub mount: String,
+    #[serde(default)]
+    pub min_free_percent: Option<f32>,
+    #[serde(default)]
+    pub min_free_gb: Option<f32>,
+}
+
+/// Alert raised once a process of the given name stays above one of the
+/// limits for `for`, and resolved when it is back under or exits.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProcessRule {
+    /// Names the events, `process` otherwise.
+    #[serde(default)]
+    pub name: Option<String>,
+    pub process: String,
+    /// CPU percentage in Irix mode, so a process can go over 100.
+    #[serde(default)]
+    pub cpu_above: Option<f32>,
+    #[serde(default)]
+    pub mem_above: Option<f32>,
+    #[serde(default, deserialize_with = "duration::deserialize", rename = "for")]
+    pub duration: Option<Duration>,
+    /// Taken on the process when the rule is breached, with `alerts.watchdog` on.
+    #[serde(default)]
+    pub action: Option<RuleAction>,
+    /// Nice value added by the `renice` action, 10 when left out.
+    #[serde(default)]
+    pub renice_by: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleAction {
+    /// Sends SIGTERM.
+    Term,
+    /// Sends SIGKILL.
+    Kill,
+    /// Lowers the priority by `renice_by`.
+    Renice,
+}
+
+impl ProcessRule {
+    pub fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.process)
+    }
+}
+
+/// Hosts whose round trip the ping panel follows, each probed once per
+/// `refresh` by opening a TCP connection.
+#[derive(Debug, Default, Deserialize)]
+pub struct PingConfig {
+    #[serde(default)]
+    pub hosts: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "duration::deserialize")]
+    pub refresh: Option<Duration>,
+}
+
+/// Publishes readings to an MQTT broker every `interval`, as JSON on one
+/// topic per kind of reading. Off unless `broker` is set. The username and
+/// password, when the broker wants them, come from `HTOPUI_MQTT_USERNAME`
+/// and `HTOPUI_MQTT_PASSWORD`.
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct MqttConfig {
+    /// A host with an optional port, 1883 otherwise or 8883 over TLS.
+    #[serde(default)]
+    pub broker: Option<String>,
+    /// Connects over TLS, checking the broker's certificate against the
+    /// system's roots.
+    #[serde(default)]
+    pub tls: Option<bool>,
+    /// `htop-ui-<hostname>` when left out.
+    #[serde(default)]
+    pub client_id: Option<String>,
+    #[serde(default, deserialize_with = "duration::deserialize")]
+    pub interval: Option<Duration>,
+    /// Has the broker keep the last readings for clients that subscribe later.
+    #[serde(default)]
+    pub retain: Option<bool>,
+    #[serde(default)]
+    pub topics: MqttTopics,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct MqttTopics {
+    #[serde(default)]
+    pub cpu: Option<String>,
+    #[serde(default)]
+    pub memory: Option<String>,
+    #[serde(default)]
+    pub disk: Option<String>,
+    #[serde(default)]
+    pub network: Option<String>,
+    #[serde(default)]
+    pub temperature: Option<String>,
+}
+
+/// Writes readings in InfluxDB line protocol every `interval`, appended to
+/// `file`, posted to `url` or both. Off unless one of them is set. A token
+/// for the write endpoint comes from `HTOPUI_INFLUX_TOKEN`.
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct InfluxConfig {
+    #[serde(default)]
+    pub file: Option<String>,
+    /// A write endpoint such as
+    /// `http://localhost:8086/api/v2/write?org=home&bucket=htop-ui`, given
+    /// timestamps in nanoseconds.
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default, deserialize_with = "duration::deserialize")]
+    pub interval: Option<Duration>,
+    /// Value of the `host` tag on every point, the hostname when left out.
+    #[serde(default)]
+    pub host: Option<String>,
+}
+
+/// Sends readings as StatsD gauges over UDP every `interval`, named
+/// `<prefix>.<reading>` such as `htop-ui.desktop.memory.used`. Off unless
+/// `address` is set.
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct StatsdConfig {
+    /// A host with an optional port, 8125 otherwise.
+    #[serde(default)]
+    pub address: Option<String>,
+    /// `htop-ui.<hostname>` when left out.
+    #[serde(default)]
+    pub prefix: Option<String>,
+    #[serde(default, deserialize_with = "duration::deserialize")]
+    pub interval: Option<Duration>,
+}
+
+/// Another machine of the hosts overview, reached through an agent
+/// listening on `agent`, as with `--remote`, or one started over SSH on
+/// `ssh`, as with `--ssh`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct HostConfig {
+    pub name: String,
+    #[serde(default)]
+    pub agent: Option<String>,
+    #[serde(default)]
+    pub ssh: Option<String>,
+    /// `htop-ui --agent -` when left out.
+    #[serde(default)]
+    pub ssh_command: Option<String>,
+}
+
+/// Which meters go in the header and the two columns, top to bottom, and
+/// the percentages used to split the screen. A panel takes its height from
+/// the `*_height` key of the same name wherever it is placed.
+#[derive(Debug, Default, Deserialize)]
+pub struct LayoutConfig {
+    #[serde(default)]
+    pub header: Option<Vec<Meter>>,
+    #[serde(default)]
+    pub left: Option<Vec<Meter>>,
+    #[serde(default)]
+    pub right: Option<Vec<Meter>>,
+    #[serde(default)]
+    pub left_width: Option<u16>,
+    #[serde(default)]
+    pub info_height: Option<u16>,
+    #[serde(default)]
+    pub process_height: Option<u16>,
+    #[serde(default)]
+    pub cpu_height: Option<u16>,
+    #[serde(default)]
+    pub network_height: Option<u16>,
+    #[serde(default)]
+    pub disk_io_height: Option<u16>,
+    #[serde(default)]
+    pub mem_height: Option<u16>,
+    #[serde(default)]
+    pub disk_height: Option<u16>,
+    #[serde(default)]
+    pub temperature_height: Option<u16>,
+    #[serde(default)]
+    pub reachability_height: Option<u16>,
+    #[serde(default)]
+    pub ping_height: Option<u16>,
+    /// Draws meters above the process table instead of the side panels.
+    #[serde(default)]
+    pub header_meters: Option<bool>,
+    /// Terminals narrower than this get the header meters regardless.
+    #[serde(default)]
+    pub compact_width: Option<u16>,
 }
 
 impl AppConfig {
@@ -21,18 +591,102 @@ impl AppConfig {
     const CPU_THRESHOLD: f32 = 10.0;
     const SINGLE_CPU_THRESHOLD: f32 = 50.0;
     const MEM_THRESHOLD: f32 = 20.0;
+    const PRESSURE_THRESHOLD: f32 = 10.0;
+    const HIGHLIGHT_HYSTERESIS: f32 = 0.0;
+    const HIGHLIGHT_AFTER: Duration = Duration::ZERO;
+    const MIN_CPU_DISPLAY: f32 = 0.2;
+    const MIN_MEM_DISPLAY: f32 = 0.0;
+    const SHOW_KERNEL_THREADS: bool = false;
+    const SHOW_THREADS: bool = false;
+    const PROCESS_NETWORK: bool = false;
+    const GROUP_CPUS_BY_NODE: bool = false;
+    const CPU_MODE: CpuMode = CpuMode::Irix;
+    const THEME: Theme = Theme::Dark;
+    const ALERT_STYLE: AlertStyle = AlertStyle::Blink;
+    const PROCESS_HIGHLIGHT: Duration = Duration::from_secs(2);
+    const RESTORE_SESSION: bool = true;
+    const COLUMNS: [ProcessColumn; 6] = [
+        ProcessColumn::Pid, ProcessColumn::Name, ProcessColumn::User, ProcessColumn::Cpu, ProcessColumn::Mem, ProcessColumn::Res,
+    ];
+    const EXCLUDED_FS_TYPES: [&str; 8] = [
+        "tmpfs", "devtmpfs", "overlay", "squashfs", "proc", "sysfs", "cgroup2", "efivarfs",
+    ];
+    const REDACTED_ENV_VARS: [&str; 6] = ["PASSWORD", "SECRET", "TOKEN", "KEY", "CREDENTIAL", "AUTH"];
+    const PROCESS_REFRESH: Duration = Duration::from_secs(1);
+    const NETWORK_REFRESH: Duration = Duration::from_secs(1);
+    const CONNECTION_REFRESH: Duration = Duration::from_secs(2);
+    const SERVICE_REFRESH: Duration = Duration::from_secs(5);
+    const TEMPERATURE_REFRESH: Duration = Duration::from_secs(5);
+    const DISK_REFRESH: Duration = Duration::from_secs(1);
+    const DISK_USAGE_REFRESH: Duration = Duration::from_secs(30);
+    const GPU_REFRESH: Duration = Duration::from_secs(1);
+    const BATTERY_REFRESH: Duration = Duration::from_secs(10);
+    const REACHABILITY_REFRESH: Duration = Duration::from_secs(10);
     
     pub fn new(config_path: &str) -> Self {
         let config_yml = Self::load_config(config_path);
         Self {
-            tick_rate: Some(config_yml.tick_rate.unwrap_or(Self::TICK_RATE)),
+            tick_rate: Self::non_zero("tick_rate", config_yml.tick_rate, Self::TICK_RATE),
             blink_threshold_rate: Some(config_yml.blink_threshold_rate.unwrap_or(Self::BLINK_THRESHOLD_RATE)),
             cpu_threshold: Some(config_yml.cpu_threshold.unwrap_or(Self::CPU_THRESHOLD)),
             single_cpu_threshold: Some(config_yml.single_cpu_threshold.unwrap_or(Self::SINGLE_CPU_THRESHOLD)),
-            mem_threshold: Some(config_yml.mem_threshold.unwrap_or(Self::MEM_THRESHOLD))
+            mem_threshold: Some(config_yml.mem_threshold.unwrap_or(Self::MEM_THRESHOLD)),
+            pressure_threshold: Some(config_yml.pressure_threshold.unwrap_or(Self::PRESSURE_THRESHOLD)),
+            highlight_hysteresis: Some(config_yml.highlight_hysteresis.unwrap_or(Self::HIGHLIGHT_HYSTERESIS)),
+            highlight_after: Some(config_yml.highlight_after.unwrap_or(Self::HIGHLIGHT_AFTER)),
+            min_cpu_display: Some(config_yml.min_cpu_display.unwrap_or(Self::MIN_CPU_DISPLAY)),
+            min_mem_display: Some(config_yml.min_mem_display.unwrap_or(Self::MIN_MEM_DISPLAY)),
+            show_kernel_threads: Some(config_yml.show_kernel_threads.unwrap_or(Self::SHOW_KERNEL_THREADS)),
+            show_threads: Some(config_yml.show_threads.unwrap_or(Self::SHOW_THREADS)),
+            process_network: Some(config_yml.process_network.unwrap_or(Self::PROCESS_NETWORK)),
+            group_cpus_by_node: Some(config_yml.group_cpus_by_node.unwrap_or(Self::GROUP_CPUS_BY_NODE)),
+            cpu_mode: Some(config_yml.cpu_mode.unwrap_or(Self::CPU_MODE)),
+            theme: Some(config_yml.theme.unwrap_or(Self::THEME)),
+            alert_style: Some(config_yml.alert_style.unwrap_or(Self::ALERT_STYLE)),
+            process_highlight: Some(config_yml.process_highlight.unwrap_or(Self::PROCESS_HIGHLIGHT)),
+            columns: Some(config_yml.columns.filter(|columns| !columns.is_empty()).unwrap_or_else(|| {
+                Self::COLUMNS.into_iter().map(ColumnConfig::from).collect()
+            })),
+            restore_session: Some(config_yml.restore_session.unwrap_or(Self::RESTORE_SESSION)),
+            excluded_fs_types: Some(config_yml.excluded_fs_types.unwrap_or_else(|| {
+                Self::EXCLUDED_FS_TYPES.iter().map(|fs_type| fs_type.to_string()).collect()
+            })),
+            redacted_env_vars: Some(config_yml.redacted_env_vars.unwrap_or_else(|| {
+                Self::REDACTED_ENV_VARS.iter().map(|pattern| pattern.to_string()).collect()
+            })),
+            process_refresh: Self::non_zero("process_refresh", config_yml.process_refresh, Self::PROCESS_REFRESH),
+            network_refresh: Some(config_yml.network_refresh.unwrap_or(Self::NETWORK_REFRESH)),
+            connection_refresh: Some(config_yml.connection_refresh.unwrap_or(Self::CONNECTION_REFRESH)),
+            service_refresh: Some(config_yml.service_refresh.unwrap_or(Self::SERVICE_REFRESH)),
+            temperature_refresh: Some(config_yml.temperature_refresh.unwrap_or(Self::TEMPERATURE_REFRESH)),
+            disk_refresh: Self::non_zero("disk_refresh", config_yml.disk_refresh, Self::DISK_REFRESH),
+            disk_usage_refresh: Some(config_yml.disk_usage_refresh.unwrap_or(Self::DISK_USAGE_REFRESH)),
+            gpu_refresh: Some(config_yml.gpu_refresh.unwrap_or(Self::GPU_REFRESH)),
+            battery_refresh: Some(config_yml.battery_refresh.unwrap_or(Self::BATTERY_REFRESH)),
+            reachability_refresh: Some(config_yml.reachability_refresh.unwrap_or(Self::REACHABILITY_REFRESH)),
+            layout: LayoutConfig::new(config_yml.layout),
+            alerts: AlertsConfig::new(config_yml.alerts),
+            adaptive: AdaptiveConfig::new(config_yml.adaptive),
+            ping: PingConfig::new(config_yml.ping),
+            mqtt: MqttConfig::new(config_yml.mqtt),
+            influx: InfluxConfig::new(config_yml.influx),
+            statsd: StatsdConfig::new(config_yml.statsd),
+            hosts: Some(config_yml.hosts.unwrap_or_default()),
         }
     }
     
+    /// The duration of `key`, or `default` when it is missing or zero. A zero
+    /// period would make the interval it drives panic.
+    fn non_zero(key: &str, value: Option<Duration>, default: Duration) -> Option<Duration> {
+        match value {
+            Some(value) if value.is_zero() => {
+                eprintln!("`{}` must be greater than zero, using {:?}", key, default);
+                Some(default)
+            }
+            value => Some(value.unwrap_or(default)),
+        }
+    }
+
     fn load_config(config_path: &str) -> Self {
         let config_str = match fs::read_to_string(config_path){
             Ok(s) => s,
@@ -45,8 +699,454 @@ impl AppConfig {
             Ok(config) => config,
             Err(err) => {
                 eprintln!("Error serializing config file: {}", err);
-                return AppConfig::default();
+                eprintln!("Run with --check-config for details");
+                AppConfig::default()
+            }
+        }
+    }
+
+    /// Writes the process table columns to the config file. Only the
+    /// `columns` entry is rewritten so comments and other keys are kept.
+    pub fn save_columns(config_path: &str, columns: &[ColumnConfig]) -> Result<(), std::io::Error> {
+        let config_str = match fs::read_to_string(config_path) {
+            Ok(s) => s,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err),
+        };
+        let entry = format!(
+            "columns: [{}]",
+            columns.iter().map(ColumnConfig::to_yaml).collect::<Vec<_>>().join(", ")
+        );
+        let mut lines: Vec<&str> = config_str.lines().collect();
+        match lines.iter().position(|line| line.starts_with("columns:")) {
+            Some(start) => {
+                // A block list continues below the key until the next
+                // unindented line, blank lines and comments included. Those
+                // after its last item belong to what follows.
+                let block = lines[start + 1..]
+                    .iter()
+                    .take_while(|line| line.trim().is_empty() || line.starts_with([' ', '\t', '-', '#']))
+                    .collect::<Vec<_>>();
+                let items = block
+                    .iter()
+                    .rposition(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+                    .map_or(0, |last| last + 1);
+                lines.splice(start..start + 1 + items, [entry.as_str()]);
+            }
+            None => lines.push(&entry),
+        }
+        // Written next to the config and renamed over it, so that a crash
+        // never leaves it truncated
+        let temp_path = format!("{}.tmp", config_path);
+        fs::write(&temp_path, lines.join("\n") + "\n")?;
+        fs::rename(&temp_path, config_path)
+    }
+
+    /// Parses the config file and reports unknown keys, type errors and
+    /// out-of-range values as `path:line: message` entries.
+    pub fn check(config_path: &str) -> Vec<String> {
+        let config_str = match fs::read_to_string(config_path) {
+            Ok(s) => s,
+            Err(err) => return vec![format!("{}: {}", config_path, err)],
+        };
+        let mut unknown_keys = Vec::new();
+        let deserializer = serde_yml::Deserializer::from_str(&config_str);
+        let result: Result<Self, _> = serde_ignored::deserialize(deserializer, |path| {
+            unknown_keys.push(path.to_string());
+        });
+        let mut issues: Vec<String> = unknown_keys
+            .iter()
+            .map(|key| Self::issue(config_path, &config_str, key, format!("unknown key `{}`", key)))
+            .collect();
+        let config_yml = match result {
+            Ok(config) => config,
+            Err(err) => {
+                let line = err.location().map_or(0, |location| location.line());
+                issues.push(format!("{}:{}: {}", config_path, line, err));
+                return issues;
+            }
+        };
+        for (key, value, max) in [
+            ("cpu_threshold", config_yml.cpu_threshold, f32::MAX),
+            ("single_cpu_threshold", config_yml.single_cpu_threshold, 100.0),
+            ("mem_threshold", config_yml.mem_threshold, 100.0),
+            ("pressure_threshold", config_yml.pressure_threshold, 100.0),
+            ("highlight_hysteresis", config_yml.highlight_hysteresis, 100.0),
+            ("min_cpu_display", config_yml.min_cpu_display, f32::MAX),
+            ("min_mem_display", config_yml.min_mem_display, 100.0),
+            ("alerts.cpu_threshold", config_yml.alerts.cpu_threshold, 100.0),
+            ("alerts.mem_threshold", config_yml.alerts.mem_threshold, 100.0),
+            ("alerts.disk_threshold", config_yml.alerts.disk_threshold, 100.0),
+            ("alerts.temperature_threshold", config_yml.alerts.temperature_threshold, f32::MAX),
+            ("alerts.temperature_hysteresis", config_yml.alerts.temperature_hysteresis, f32::MAX),
+        ] {
+            if let Some(value) = value
+                && !(0.0..=max).contains(&value) {
+                let message = if max == f32::MAX {
+                    format!("`{}` must not be negative, got {}", key, value)
+                } else {
+                    format!("`{}` must be between 0 and {}, got {}", key, max, value)
+                };
+                issues.push(Self::issue(config_path, &config_str, key, message));
+            }
+        }
+        for (key, value) in [
+            ("tick_rate", config_yml.tick_rate),
+            ("blink_threshold_rate", config_yml.blink_threshold_rate),
+            ("process_refresh", config_yml.process_refresh),
+            ("network_refresh", config_yml.network_refresh),
+            ("connection_refresh", config_yml.connection_refresh),
+            ("service_refresh", config_yml.service_refresh),
+            ("temperature_refresh", config_yml.temperature_refresh),
+            ("disk_refresh", config_yml.disk_refresh),
+            ("disk_usage_refresh", config_yml.disk_usage_refresh),
+            ("gpu_refresh", config_yml.gpu_refresh),
+            ("battery_refresh", config_yml.battery_refresh),
+            ("reachability_refresh", config_yml.reachability_refresh),
+            ("adaptive.min_interval", config_yml.adaptive.min_interval),
+            ("adaptive.max_interval", config_yml.adaptive.max_interval),
+            ("ping.refresh", config_yml.ping.refresh),
+            ("mqtt.interval", config_yml.mqtt.interval),
+            ("influx.interval", config_yml.influx.interval),
+            ("statsd.interval", config_yml.statsd.interval),
+        ] {
+            if value.is_some_and(|value| value.is_zero()) {
+                issues.push(Self::issue(config_path, &config_str, key, format!("`{}` must be greater than zero", key)));
+            }
+        }
+        if let (Some(min), Some(max)) = (config_yml.adaptive.min_interval, config_yml.adaptive.max_interval)
+            && min > max {
+            issues.push(Self::issue(
+                config_path, &config_str, "adaptive.max_interval",
+                "`adaptive.max_interval` must not be shorter than `adaptive.min_interval`".to_string()
+            ));
+        }
+        if config_yml.columns.as_ref().is_some_and(|columns| columns.is_empty()) {
+            issues.push(Self::issue(config_path, &config_str, "columns", "`columns` must list at least one column".to_string()));
+        }
+        for column in config_yml.columns.iter().flatten() {
+            let sizes = [column.width, column.min, column.max];
+            let message = if sizes.iter().flatten().count() > 1 {
+                Some(format!("column `{}` takes only one of `width`, `min` and `max`", column.column.key()))
+            } else if sizes.contains(&Some(0)) {
+                Some(format!("column `{}` must be wider than zero", column.column.key()))
+            } else {
+                None
+            };
+            if let Some(message) = message {
+                issues.push(Self::issue(config_path, &config_str, "columns", message));
+            }
+        }
+        if config_yml.layout.left_width.is_some_and(|width| width > 100) {
+            issues.push(Self::issue(
+                config_path, &config_str, "layout.left_width", "`layout.left_width` must be at most 100".to_string()
+            ));
+        }
+        let layout = LayoutConfig::new(config_yml.layout);
+        for meter in layout.header.iter().flatten().filter(|meter| !meter.fits_header()) {
+            issues.push(Self::issue(
+                config_path, &config_str, "layout.header",
+                format!("`{}` cannot be drawn in the header, only cpu, memory, swap and info", meter.key())
+            ));
+        }
+        for (side, key, meters) in [("left", "layout.left", &layout.left), ("right", "layout.right", &layout.right)] {
+            let meters = meters.as_ref().unwrap();
+            for meter in meters.iter().filter(|meter| !meter.fits_column()) {
+                issues.push(Self::issue(
+                    config_path, &config_str, key,
+                    format!("`{}` can only be drawn in the header", meter.key())
+                ));
+            }
+            // An empty column leaves the whole width to the other one
+            let total: u32 = meters.iter().map(|meter| layout.height(*meter) as u32).sum();
+            if !meters.is_empty() && total != 100 {
+                issues.push(Self::issue(
+                    config_path, &config_str, "layout",
+                    format!("{} panel heights add up to {}%, expected 100%", side, total)
+                ));
             }
         }
+        if !layout.places(Meter::Processes) {
+            issues.push(Self::issue(
+                config_path, &config_str, "layout",
+                "`processes` is in neither `layout.left` nor `layout.right`".to_string()
+            ));
+        }
+        if layout.places(Meter::Ping) && config_yml.ping.hosts.is_none_or(|hosts| hosts.is_empty()) {
+            issues.push(Self::issue(
+                config_path, &config_str, "layout",
+                "`ping` is placed but `ping.hosts` lists no hosts".to_string()
+            ));
+        }
+        for rule in config_yml.alerts.rules.iter().flatten() {
+            let message = match (rule.cpu_above, rule.mem_above) {
+                (None, None) => format!("rule `{}` sets neither `cpu_above` nor `mem_above`", rule.label()),
+                (Some(cpu), _) if cpu < 0.0 => format!("rule `{}` has a negative `cpu_above`", rule.label()),
+                (_, Some(mem)) if !(0.0..=100.0).contains(&mem) => {
+                    format!("rule `{}` needs a `mem_above` between 0 and 100, got {}", rule.label(), mem)
+                }
+                _ => match (rule.action, rule.renice_by) {
+                    (Some(_), _) if !config_yml.alerts.watchdog.unwrap_or(false) => {
+                        format!("rule `{}` has an action, which only runs with `alerts.watchdog` on", rule.label())
+                    }
+                    (Some(RuleAction::Renice), Some(delta)) if !(1..=39).contains(&delta) => {
+                        format!("rule `{}` needs a `renice_by` between 1 and 39, got {}", rule.label(), delta)
+                    }
+                    (action, Some(_)) if action != Some(RuleAction::Renice) => {
+                        format!("rule `{}` sets `renice_by` without the `renice` action", rule.label())
+                    }
+                    _ => continue,
+                },
+            };
+            issues.push(Self::issue(config_path, &config_str, "alerts.rules", message));
+        }
+        if let Some(smtp) = &config_yml.alerts.smtp
+            && smtp.to.is_empty() {
+            issues.push(Self::issue(config_path, &config_str, "alerts.smtp", "`smtp.to` lists no recipients".to_string()));
+        }
+        let topics = &config_yml.mqtt.topics;
+        for (key, topic) in [
+            ("mqtt.topics.cpu", &topics.cpu),
+            ("mqtt.topics.memory", &topics.memory),
+            ("mqtt.topics.disk", &topics.disk),
+            ("mqtt.topics.network", &topics.network),
+            ("mqtt.topics.temperature", &topics.temperature),
+        ] {
+            if let Some(topic) = topic
+                && (topic.is_empty() || topic.contains(['+', '#'])) {
+                issues.push(Self::issue(
+                    config_path, &config_str, key,
+                    format!("`{}` must be a non-empty topic without `+` or `#` wildcards", key)
+                ));
+            }
+        }
+        if let Some(url) = &config_yml.influx.url
+            && !url.starts_with("http://") && !url.starts_with("https://") {
+            issues.push(Self::issue(
+                config_path, &config_str, "influx.url",
+                "`influx.url` must be an http:// or https:// URL".to_string()
+            ));
+        }
+        if let Some(prefix) = &config_yml.statsd.prefix
+            && (prefix.is_empty() || prefix.contains([':', '|', '\n'])) {
+            issues.push(Self::issue(
+                config_path, &config_str, "statsd.prefix",
+                "`statsd.prefix` must be non-empty and can't hold `:`, `|` or newlines".to_string()
+            ));
+        }
+        let mut host_names = HashSet::new();
+        for host in config_yml.hosts.iter().flatten() {
+            let duplicate = !host_names.insert(&host.name);
+            let message = match (&host.agent, &host.ssh) {
+                (Some(_), Some(_)) | (None, None) => format!("host `{}` needs exactly one of `agent` and `ssh`", host.name),
+                (Some(_), None) if host.ssh_command.is_some() => {
+                    format!("host `{}` sets `ssh_command` without `ssh`", host.name)
+                }
+                _ if duplicate => format!("host `{}` is listed twice", host.name),
+                _ => continue,
+            };
+            issues.push(Self::issue(config_path, &config_str, "hosts", message));
+        }
+        for rule in config_yml.alerts.disk_free.iter().flatten() {
+            let message = match (rule.min_free_percent, rule.min_free_gb) {
+                (Some(_), Some(_)) | (None, None) => {
+                    format!("`{}` needs exactly one of `min_free_percent` and `min_free_gb`", rule.mount)
+                }
+                (Some(percent), None) if !(0.0..=100.0).contains(&percent) => {
+                    format!("`{}` needs a `min_free_percent` between 0 and 100, got {}", rule.mount, percent)
+                }
+                (None, Some(gb)) if gb < 0.0 => format!("`{}` has a negative `min_free_gb`", rule.mount),
+                _ => continue,
+            };
+            issues.push(Self::issue(config_path, &config_str, "alerts.disk_free", message));
+        }
+        issues
+    }
+
+    fn issue(config_path: &str, config_str: &str, key: &str, message: String) -> String {
+        match Self::key_line(config_str, key) {
+            Some(line) => format!("{}:{}: {}", config_path, line, message),
+            None => format!("{}: {}", config_path, message),
+        }
+    }
+
+    /// Finds the 1-based line of a dotted key such as `layout.left_width`.
+    fn key_line(config_str: &str, key: &str) -> Option<usize> {
+        let mut line = 0;
+        for segment in key.split('.') {
+            let pattern = format!("{}:", segment);
+            line += config_str
+                .lines()
+                .skip(line)
+                .position(|l| l.trim_start().starts_with(&pattern))?;
+        }
+        Some(line + 1)
+    }
+}
+
+
+impl AlertsConfig {
+    const CPU_THRESHOLD: f32 = 90.0;
+    const MEM_THRESHOLD: f32 = 90.0;
+    const DISK_THRESHOLD: f32 = 90.0;
+    const TEMPERATURE_THRESHOLD: f32 = 85.0;
+    const TEMPERATURE_HYSTERESIS: f32 = 5.0;
+
+    fn new(alerts_yml: Self) -> Self {
+        Self {
+            webhook_url: alerts_yml.webhook_url,
+            on_breach_command: alerts_yml.on_breach_command,
+            smtp: alerts_yml.smtp,
+            bell: Some(alerts_yml.bell.unwrap_or(false)),
+            flash: Some(alerts_yml.flash.unwrap_or(false)),
+            watchdog: Some(alerts_yml.watchdog.unwrap_or(false)),
+            cpu_threshold: Some(alerts_yml.cpu_threshold.unwrap_or(Self::CPU_THRESHOLD)),
+            mem_threshold: Some(alerts_yml.mem_threshold.unwrap_or(Self::MEM_THRESHOLD)),
+            disk_threshold: Some(alerts_yml.disk_threshold.unwrap_or(Self::DISK_THRESHOLD)),
+            temperature_threshold: Some(alerts_yml.temperature_threshold.unwrap_or(Self::TEMPERATURE_THRESHOLD)),
+            temperature_hysteresis: Some(alerts_yml.temperature_hysteresis.unwrap_or(Self::TEMPERATURE_HYSTERESIS)),
+            rules: Some(alerts_yml.rules.unwrap_or_default()),
+            disk_free: Some(alerts_yml.disk_free.unwrap_or_default()),
+            ignored_mounts: Some(alerts_yml.ignored_mounts.unwrap_or_default()),
+        }
+    }
+}
+
+impl PingConfig {
+    const REFRESH: Duration = Duration::from_secs(1);
+
+    fn new(ping_yml: Self) -> Self {
+        Self {
+            hosts: Some(ping_yml.hosts.unwrap_or_default()),
+            refresh: AppConfig::non_zero("ping.refresh", ping_yml.refresh, Self::REFRESH),
+        }
+    }
+}
+
+impl MqttConfig {
+    const INTERVAL: Duration = Duration::from_secs(10);
+    const RETAIN: bool = false;
+    const TLS: bool = false;
+
+    fn new(mqtt_yml: Self) -> Self {
+        let topic = |topic: Option<String>, name: &str| Some(topic.unwrap_or(format!("htop-ui/{}", name)));
+        let topics = mqtt_yml.topics;
+        Self {
+            broker: mqtt_yml.broker,
+            tls: Some(mqtt_yml.tls.unwrap_or(Self::TLS)),
+            client_id: mqtt_yml.client_id,
+            interval: AppConfig::non_zero("mqtt.interval", mqtt_yml.interval, Self::INTERVAL),
+            retain: Some(mqtt_yml.retain.unwrap_or(Self::RETAIN)),
+            topics: MqttTopics {
+                cpu: topic(topics.cpu, "cpu"),
+                memory: topic(topics.memory, "memory"),
+                disk: topic(topics.disk, "disk"),
+                network: topic(topics.network, "network"),
+                temperature: topic(topics.temperature, "temperature"),
+            },
+        }
+    }
+}
+
+impl InfluxConfig {
+    const INTERVAL: Duration = Duration::from_secs(10);
+
+    fn new(influx_yml: Self) -> Self {
+        Self {
+            file: influx_yml.file,
+            url: influx_yml.url,
+            interval: AppConfig::non_zero("influx.interval", influx_yml.interval, Self::INTERVAL),
+            host: influx_yml.host,
+        }
+    }
+}
+
+impl StatsdConfig {
+    const INTERVAL: Duration = Duration::from_secs(10);
+
+    fn new(statsd_yml: Self) -> Self {
+        Self {
+            address: statsd_yml.address,
+            prefix: statsd_yml.prefix,
+            interval: AppConfig::non_zero("statsd.interval", statsd_yml.interval, Self::INTERVAL),
+        }
+    }
+}
+
+impl AdaptiveConfig {
+    const ENABLED: bool = false;
+    const MIN_INTERVAL: Duration = Duration::from_millis(100);
+    const MAX_INTERVAL: Duration = Duration::from_secs(10);
+
+    fn new(adaptive_yml: Self) -> Self {
+        Self {
+            enabled: Some(adaptive_yml.enabled.unwrap_or(Self::ENABLED)),
+            min_interval: AppConfig::non_zero("adaptive.min_interval", adaptive_yml.min_interval, Self::MIN_INTERVAL),
+            max_interval: AppConfig::non_zero("adaptive.max_interval", adaptive_yml.max_interval, Self::MAX_INTERVAL),
+        }
+    }
+}
+
+impl LayoutConfig {
+    const LEFT: [Meter; 3] = [Meter::Info, Meter::Processes, Meter::Cpu];
+    const RIGHT: [Meter; 5] = [Meter::Network, Meter::DiskIo, Meter::Memory, Meter::Disks, Meter::Sensors];
+    /// Used by the header meters when `header` lists nothing.
+    pub const COMPACT_HEADER: [Meter; 4] = [Meter::Cpu, Meter::Memory, Meter::Swap, Meter::Info];
+    const LEFT_WIDTH: u16 = 50;
+    const INFO_HEIGHT: u16 = 20;
+    const PROCESS_HEIGHT: u16 = 60;
+    const CPU_HEIGHT: u16 = 20;
+    const NETWORK_HEIGHT: u16 = 15;
+    const DISK_IO_HEIGHT: u16 = 15;
+    const MEM_HEIGHT: u16 = 15;
+    const DISK_HEIGHT: u16 = 15;
+    const TEMPERATURE_HEIGHT: u16 = 40;
+    const REACHABILITY_HEIGHT: u16 = 15;
+    const PING_HEIGHT: u16 = 15;
+    const HEADER_METERS: bool = false;
+    const COMPACT_WIDTH: u16 = 100;
+
+    fn new(layout_yml: Self) -> Self {
+        Self {
+            header: Some(layout_yml.header.unwrap_or_default()),
+            left: Some(layout_yml.left.unwrap_or_else(|| Self::LEFT.to_vec())),
+            right: Some(layout_yml.right.unwrap_or_else(|| Self::RIGHT.to_vec())),
+            left_width: Some(layout_yml.left_width.unwrap_or(Self::LEFT_WIDTH).min(100)),
+            info_height: Some(layout_yml.info_height.unwrap_or(Self::INFO_HEIGHT)),
+            process_height: Some(layout_yml.process_height.unwrap_or(Self::PROCESS_HEIGHT)),
+            cpu_height: Some(layout_yml.cpu_height.unwrap_or(Self::CPU_HEIGHT)),
+            network_height: Some(layout_yml.network_height.unwrap_or(Self::NETWORK_HEIGHT)),
+            disk_io_height: Some(layout_yml.disk_io_height.unwrap_or(Self::DISK_IO_HEIGHT)),
+            mem_height: Some(layout_yml.mem_height.unwrap_or(Self::MEM_HEIGHT)),
+            disk_height: Some(layout_yml.disk_height.unwrap_or(Self::DISK_HEIGHT)),
+            temperature_height: Some(layout_yml.temperature_height.unwrap_or(Self::TEMPERATURE_HEIGHT)),
+            reachability_height: Some(layout_yml.reachability_height.unwrap_or(Self::REACHABILITY_HEIGHT)),
+            ping_height: Some(layout_yml.ping_height.unwrap_or(Self::PING_HEIGHT)),
+            header_meters: Some(layout_yml.header_meters.unwrap_or(Self::HEADER_METERS)),
+            compact_width: Some(layout_yml.compact_width.unwrap_or(Self::COMPACT_WIDTH)),
+        }
+    }
+
+    /// Percentage of its column a panel takes.
+    pub fn height(&self, meter: Meter) -> u16 {
+        let height = match meter {
+            Meter::Info => self.info_height,
+            Meter::Processes => self.process_height,
+            Meter::Cpu => self.cpu_height,
+            Meter::Memory => self.mem_height,
+            Meter::Network => self.network_height,
+            Meter::DiskIo => self.disk_io_height,
+            Meter::Disks => self.disk_height,
+            Meter::Sensors => self.temperature_height,
+            Meter::Reachability => self.reachability_height,
+            Meter::Ping => self.ping_height,
+            Meter::Swap => None,
+        };
+        height.unwrap_or_default()
+    }
+
+    /// Whether one of the columns shows `meter`.
+    pub fn places(&self, meter: Meter) -> bool {
+        [&self.left, &self.right].into_iter().flatten().any(|column| column.contains(&meter))
     }
 }
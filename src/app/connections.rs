@@ -0,0 +1,96 @@
+use crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{app::{App, View}, cmd::units};
+
+impl App {
+    pub(super) fn handle_connections_key(&mut self, code: KeyCode) {
+        let count = self.connections.len();
+        let selected = self.connection_state.selected().unwrap_or(0);
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Char('c') => self.view = View::Main,
+            KeyCode::Char('j') | KeyCode::Down if count > 0 => {
+                self.connection_state.select(Some((selected + 1).min(count - 1)));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.connection_state.select(Some(selected.saturating_sub(1)));
+            }
+            KeyCode::Enter => self.jump_to_connection_owner(selected),
+            KeyCode::Char('r') => {
+                self.connections_by_rate = !self.connections_by_rate;
+                self.sort_connections();
+            }
+            _ => {}
+        }
+    }
+
+    /// Busiest connections first when sorting by rate. Otherwise they are
+    /// left in the kernel's order, back from the next refresh.
+    pub(super) fn sort_connections(&mut self) {
+        if self.connections_by_rate {
+            self.connections.sort_by(|a, b| b.total_rate().total_cmp(&a.total_rate()));
+        }
+    }
+
+    /// Selects the process owning the connection in the main table.
+    fn jump_to_connection_owner(&mut self, row: usize) {
+        let Some(pid) = self.connections.get(row).and_then(|connection| connection.pid) else {
+            self.status = Some("The connection has no known owner".to_string());
+            return;
+        };
+        match self.processes.iter().position(|process| process.pid == pid) {
+            Some(row) => {
+                self.select_row(row);
+                self.view = View::Main;
+            }
+            None => self.status = Some(format!("Process {} is hidden or has exited", pid)),
+        }
+    }
+
+    pub(super) fn render_connections(&mut self, frame: &mut Frame, area: Rect) {
+        let header = ["Proto", "Local address", "Remote address", "State", "Down", "Up", "PID", "Process"]
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .height(1);
+        let rows = self.connections.iter().map(|connection| {
+            Row::new(vec![
+                Cell::from(connection.protocol.clone()),
+                Cell::from(connection.local.clone()),
+                Cell::from(connection.remote.clone()),
+                Cell::from(connection.state.clone()),
+                Cell::from(connection.rates.map(|(rx, _)| units::bytes_per_second(rx)).unwrap_or_else(|| "-".to_string())),
+                Cell::from(connection.rates.map(|(_, tx)| units::bytes_per_second(tx)).unwrap_or_else(|| "-".to_string())),
+                Cell::from(connection.pid.map(|pid| pid.to_string()).unwrap_or_default()),
+                Cell::from(connection.process_name.clone()),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(6),
+                Constraint::Min(24),
+                Constraint::Min(24),
+                Constraint::Length(12),
+                Constraint::Length(11),
+                Constraint::Length(11),
+                Constraint::Length(8),
+                Constraint::Min(15),
+            ],
+        )
+        .header(header)
+        .fg(self.style.net_frame_fg)
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_spacing(HighlightSpacing::Always)
+        .block(Block::default()
+                .borders(Borders::ALL)
+                .title(match self.connections_by_rate {
+                    _ if self.connections.is_empty() => "Connections (not available on this system, c to close)",
+                    true => "Connections by rate (enter to show the process, r for the kernel order, c to close)",
+                    false => "Connections (enter to show the process, r to sort by rate, c to close)",
+                })
+                .title_bottom(Line::from(self.status.clone().unwrap_or_default()).right_aligned()));
+
+        frame.render_stateful_widget(table, area, &mut self.connection_state);
+    }
+}
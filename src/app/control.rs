@@ -0,0 +1,96 @@
+use crate::{
+    app::{modal::PendingAction, App},
+    cmd::control::{self, Signal},
+};
+
+impl App {
+    /// Tags or untags the selected process, actions apply to every tagged
+    /// process instead of the selected one.
+    pub(super) fn toggle_tag(&mut self) {
+        let Some(process) = self.state.selected().and_then(|row| self.processes.get(row)) else {
+            return;
+        };
+        if !self.tagged.remove(&process.pid) {
+            self.tagged.insert(process.pid);
+        }
+        self.next_row();
+    }
+
+    fn target_pids(&self) -> Vec<u32> {
+        if !self.tagged.is_empty() {
+            let mut pids: Vec<u32> = self.tagged.iter().copied().collect();
+            pids.sort_unstable();
+            return pids;
+        }
+        self.state.selected()
+            .and_then(|row| self.processes.get(row))
+            .map(|process| vec![process.pid])
+            .unwrap_or_default()
+    }
+
+    /// `1234 (name)` for a single process, `3 processes` otherwise.
+    fn describe_targets(&self, pids: &[u32]) -> String {
+        match pids {
+            [pid] => match self.processes.iter().find(|process| process.pid == *pid) {
+                Some(process) => format!("{} ({})", pid, process.process_name),
+                None => pid.to_string(),
+            },
+            _ => format!("{} processes", pids.len()),
+        }
+    }
+
+    fn targets_for_action(&mut self) -> Option<Vec<u32>> {
+        if self.remote.is_some() {
+            self.status = Some("Processes of a remote agent cannot be controlled".to_string());
+            return None;
+        }
+        let pids = self.target_pids();
+        (!pids.is_empty()).then_some(pids)
+    }
+
+    pub(super) fn request_signal(&mut self, signal: Signal) {
+        let Some(pids) = self.targets_for_action() else {
+            return;
+        };
+        let question = format!("Send {} to {}?", signal.name(), self.describe_targets(&pids));
+        self.confirm(question, PendingAction::Signal { pids, signal });
+    }
+
+    /// A positive `delta` raises the nice value, lowering the priority.
+    pub(super) fn request_renice(&mut self, delta: i32) {
+        let Some(pids) = self.targets_for_action() else {
+            return;
+        };
+        let direction = if delta > 0 { "Lower" } else { "Raise" };
+        let question = format!("{} the priority of {}?", direction, self.describe_targets(&pids));
+        self.confirm(question, PendingAction::Renice { pids, delta });
+    }
+
+    pub(super) fn send_signal(&mut self, pids: &[u32], signal: Signal) {
+        let errors: Vec<String> = pids.iter()
+            .filter_map(|pid| control::send_signal(*pid, signal).err().map(|err| format!("{}: {}", pid, err)))
+            .collect();
+        self.status = Some(match errors.is_empty() {
+            true => format!("Sent {} to {}", signal.name(), self.describe_targets(pids)),
+            false => format!("Error sending {} to {}", signal.name(), errors.join(", ")),
+        });
+        self.tagged.clear();
+    }
+
+    pub(super) fn renice(&mut self, pids: &[u32], delta: i32) {
+        let mut niceness = None;
+        let mut errors = Vec::new();
+        for pid in pids {
+            match control::renice(*pid, delta) {
+                Ok(value) => niceness = Some(value),
+                Err(err) => errors.push(format!("{}: {}", pid, err)),
+            }
+        }
+        self.status = Some(match (errors.is_empty(), niceness) {
+            (true, Some(value)) if pids.len() == 1 => format!("Set the nice value of {} to {}", self.describe_targets(pids), value),
+            (true, _) => format!("Reniced {}", self.describe_targets(pids)),
+            (false, _) => format!("Error renicing {}", errors.join(", ")),
+        });
+        self.tagged.clear();
+    }
+}
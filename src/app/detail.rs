@@ -0,0 +1,208 @@
+use crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{
+    app::{filter::TextFilter, App, View},
+    cmd::{environ::EnvVar, limits::ResourceLimit, units},
+};
+
+/// Shown instead of the value of environment variables that look secret.
+const REDACTED: &str = "********";
+/// Share of a resource limit from which it is highlighted.
+const LIMIT_WARNING_RATIO: f64 = 0.8;
+
+/// State of the detail view of one process.
+pub(super) struct ProcessDetail {
+    pid: u32,
+    /// Read once when the view opens, an error explains why it is missing.
+    env: Result<Vec<EnvVar>, String>,
+    env_state: TableState,
+    env_page_size: usize,
+    filter: TextFilter,
+    limits: Result<Vec<ResourceLimit>, String>,
+}
+
+impl ProcessDetail {
+    fn filtered_env(&self) -> Vec<&EnvVar> {
+        let Ok(env) = &self.env else {
+            return Vec::new();
+        };
+        env.iter()
+            .filter(|var| self.filter.matches(&var.name) || self.filter.matches(&var.value))
+            .collect()
+    }
+}
+
+impl ProcessDetail {
+    pub(super) fn is_searching(&self) -> bool {
+        self.filter.is_editing()
+    }
+}
+
+impl App {
+    /// Opens the detail view of the selected process.
+    pub(super) fn open_detail(&mut self) {
+        let Some(process) = self.state.selected().and_then(|row| self.processes.get(row)) else {
+            return;
+        };
+        let pid = process.pid;
+        let (env, limits) = if self.remote.is_some() {
+            let err = "Not available for a remote agent".to_string();
+            (Err(err.clone()), Err(err))
+        } else {
+            let patterns = self.config.redacted_env_vars.as_deref().unwrap_or_default();
+            let env = EnvVar::read_all(pid)
+                .map(|vars| {
+                    vars.into_iter()
+                        .map(|var| match var.is_secret(patterns) {
+                            true => EnvVar { value: REDACTED.to_string(), ..var },
+                            false => var,
+                        })
+                        .collect()
+                })
+                .map_err(|err| err.to_string());
+            (env, ResourceLimit::read_all(pid).map_err(|err| err.to_string()))
+        };
+        self.detail = Some(ProcessDetail {
+            pid,
+            env,
+            env_state: TableState::default().with_selected(0),
+            env_page_size: 1,
+            filter: TextFilter::default(),
+            limits,
+        });
+        self.view = View::Detail;
+    }
+
+    pub(super) fn handle_detail_key(&mut self, code: KeyCode) {
+        let Some(detail) = self.detail.as_mut() else {
+            self.view = View::Main;
+            return;
+        };
+        if detail.filter.is_editing() {
+            detail.filter.handle_key(code);
+            detail.env_state.select(Some(0));
+            return;
+        }
+        let last = detail.filtered_env().len().saturating_sub(1);
+        let selected = detail.env_state.selected().unwrap_or(0);
+        let row = match code {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.detail = None;
+                self.view = View::Main;
+                return;
+            }
+            KeyCode::Char('/') => {
+                detail.filter.start();
+                return;
+            }
+            KeyCode::Char('r') if self.remote.is_none() => {
+                // Usage moves while the view is open, limits rarely do
+                detail.limits = ResourceLimit::read_all(detail.pid).map_err(|err| err.to_string());
+                return;
+            }
+            KeyCode::Char('j') | KeyCode::Down => (selected + 1).min(last),
+            KeyCode::Char('k') | KeyCode::Up => selected.saturating_sub(1),
+            KeyCode::PageDown => (selected + detail.env_page_size).min(last),
+            KeyCode::PageUp => selected.saturating_sub(detail.env_page_size),
+            KeyCode::Home => 0,
+            KeyCode::End => last,
+            _ => return,
+        };
+        detail.env_state.select(Some(row));
+    }
+
+    pub(super) fn render_detail(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(detail) = self.detail.as_mut() else {
+            return;
+        };
+        let [top_area, env_area] = Layout::vertical([Constraint::Length(8), Constraint::Min(0)])
+            .areas(area);
+        let [summary_area, limits_area] = Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .areas(top_area);
+
+        let summary = match self.processes.iter().find(|process| process.pid == detail.pid) {
+            Some(process) => vec![
+                Line::from(format!("Name: {}", process.process_name)),
+                Line::from(format!("User: {}", process.user)),
+                Line::from(format!(
+                    "CPU: {:.1}%  Memory: {:.1}% ({})",
+                    process.cpu_usage, process.mem_usage, units::bytes(process.memory)
+                )),
+                Line::from(format!("Container: {}", process.container.as_deref().unwrap_or("-"))),
+                Line::from(format!("Cgroup: {}", process.cgroup.as_deref().unwrap_or("-"))),
+            ],
+            None => vec![Line::from("The process has exited or is hidden")],
+        };
+        frame.render_widget(
+            Paragraph::new(summary)
+                .fg(self.style.info_fg)
+                .block(Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("Process {} (q to close)", detail.pid))),
+            summary_area
+        );
+        self.render_limits(frame, limits_area);
+
+        let Some(detail) = self.detail.as_mut() else {
+            return;
+        };
+        detail.env_page_size = (env_area.height.saturating_sub(2) as usize).max(1);
+        let title = match &detail.env {
+            Err(err) => format!("Environment ({})", err),
+            Ok(_) => detail.filter.title("Environment", "/ to search"),
+        };
+        let rows: Vec<Row> = detail.filtered_env().into_iter().map(|var| {
+            Row::new(vec![Cell::from(var.name.clone()), Cell::from(var.value.clone())])
+        }).collect();
+        let table = Table::new(rows, [Constraint::Length(32), Constraint::Min(0)])
+            .fg(self.style.table_fg)
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_spacing(HighlightSpacing::Always)
+            .block(Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .title_bottom(Line::from(detail.filter.prompt())));
+
+        frame.render_stateful_widget(table, env_area, &mut detail.env_state);
+    }
+
+    fn render_limits(&self, frame: &mut Frame, area: Rect) {
+        let Some(detail) = self.detail.as_ref() else {
+            return;
+        };
+        let format = |limit: &ResourceLimit, value: Option<u64>, missing: &str| match (value, limit.in_bytes) {
+            (None, _) => missing.to_string(),
+            (Some(value), true) => units::bytes(value),
+            (Some(value), false) => value.to_string(),
+        };
+        let (title, rows) = match &detail.limits {
+            Err(err) => (format!("Limits ({})", err), Vec::new()),
+            Ok(limits) => ("Limits (r to refresh)".to_string(), limits.iter().map(|limit| {
+                let row = Row::new(vec![
+                    Cell::from(limit.name),
+                    Cell::from(format(limit, limit.usage, "-")),
+                    Cell::from(format(limit, limit.soft, "unlimited")),
+                    Cell::from(format(limit, limit.hard, "unlimited")),
+                ]);
+                match limit.usage_ratio() {
+                    Some(ratio) if ratio >= LIMIT_WARNING_RATIO => row.fg(self.style.exceed_threshold_cell).bold(),
+                    _ => row,
+                }
+            }).collect()),
+        };
+        let header = ["Resource", "Used", "Soft", "Hard"]
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .height(1);
+        let table = Table::new(
+            rows,
+            [Constraint::Min(16), Constraint::Length(10), Constraint::Length(10), Constraint::Length(10)],
+        )
+        .header(header)
+        .fg(self.style.info_fg)
+        .block(Block::default().borders(Borders::ALL).title(title));
+        frame.render_widget(table, area);
+    }
+}
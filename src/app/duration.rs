@@ -0,0 +1,145 @@
+//! Durations in the config file, written as `500ms`, `10s` or `1m 30s`, or
+//! as plain milliseconds. The `{secs, nanos}` maps of older config files
+//! are still read.
+use std::{fmt, time::Duration};
+use serde::{
+    de::{self, value::MapAccessDeserializer, MapAccess, Visitor},
+    Deserialize, Deserializer,
+};
+
+/// For `Option<Duration>` fields, with `#[serde(default)]` for missing keys.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Duration>, D::Error> {
+    deserializer.deserialize_any(DurationVisitor)
+}
+
+struct DurationVisitor;
+
+impl<'de> Visitor<'de> for DurationVisitor {
+    type Value = Option<Duration>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a duration like \"500ms\" or \"10s\", or milliseconds")
+    }
+
+    fn visit_u64<E: de::Error>(self, millis: u64) -> Result<Self::Value, E> {
+        Ok(Some(Duration::from_millis(millis)))
+    }
+
+    fn visit_i64<E: de::Error>(self, millis: i64) -> Result<Self::Value, E> {
+        match u64::try_from(millis) {
+            Ok(millis) => self.visit_u64(millis),
+            Err(_) => Err(E::custom(format!("duration must not be negative, got {}", millis))),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<Self::Value, E> {
+        parse(text).map(Some).map_err(E::custom)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        Duration::deserialize(MapAccessDeserializer::new(map)).map(Some)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserialize(deserializer)
+    }
+}
+
+/// Numbers each followed by a unit out of `ns`, `us`, `ms`, `s`, `m`, `h`
+/// and `d`, summed up. A bare number is milliseconds.
+fn parse(text: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid duration `{}`, expected e.g. \"500ms\" or \"10s\"", text);
+    let text = text.trim();
+    if let Ok(millis) = text.parse() {
+        return Ok(Duration::from_millis(millis));
+    }
+    if text.is_empty() {
+        return Err(invalid());
+    }
+    let mut total = Duration::ZERO;
+    let mut rest = text;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let value: u64 = rest[..digits].parse().map_err(|_| invalid())?;
+        rest = &rest[digits..];
+        let letters = rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len());
+        let duration = match &rest[..letters] {
+            "ns" => Some(Duration::from_nanos(value)),
+            "us" => Some(Duration::from_micros(value)),
+            "ms" => Some(Duration::from_millis(value)),
+            "s" => Some(Duration::from_secs(value)),
+            "m" => value.checked_mul(60).map(Duration::from_secs),
+            "h" => value.checked_mul(3600).map(Duration::from_secs),
+            "d" => value.checked_mul(86400).map(Duration::from_secs),
+            _ => return Err(invalid()),
+        };
+        total = duration.and_then(|duration| total.checked_add(duration)).ok_or_else(invalid)?;
+        rest = rest[letters..].trim_start();
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Config {
+        #[serde(default, deserialize_with = "deserialize")]
+        refresh: Option<Duration>,
+    }
+
+    fn refresh(yaml: &str) -> Result<Option<Duration>, serde_yml::Error> {
+        serde_yml::from_str::<Config>(yaml).map(|config| config.refresh)
+    }
+
+    #[test]
+    fn units() {
+        assert_eq!(parse("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse("10s"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(parse("1h"), Ok(Duration::from_secs(3600)));
+        assert_eq!(parse("1d"), Ok(Duration::from_secs(86400)));
+        assert_eq!(parse("250us"), Ok(Duration::from_micros(250)));
+        assert_eq!(parse("20ns"), Ok(Duration::from_nanos(20)));
+    }
+
+    #[test]
+    fn parts_add_up() {
+        assert_eq!(parse("1m30s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse("1m 30s 500ms"), Ok(Duration::from_millis(90_500)));
+    }
+
+    #[test]
+    fn a_bare_number_is_milliseconds() {
+        assert_eq!(parse("1500"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse(" 0 "), Ok(Duration::ZERO));
+    }
+
+    #[test]
+    fn invalid_durations() {
+        for text in ["", "s", "10x", "1.5s", "-1s", "ms10", "10 s", "18446744073709551615h"] {
+            assert!(parse(text).is_err(), "{:?} parsed", text);
+        }
+    }
+
+    #[test]
+    fn config_values() {
+        assert_eq!(refresh("refresh: 500ms").unwrap(), Some(Duration::from_millis(500)));
+        assert_eq!(refresh("refresh: \"10s\"").unwrap(), Some(Duration::from_secs(10)));
+        assert_eq!(refresh("refresh: 250").unwrap(), Some(Duration::from_millis(250)));
+        assert_eq!(refresh("refresh: {secs: 0, nanos: 500000000}").unwrap(), Some(Duration::from_millis(500)));
+        assert_eq!(refresh("refresh: ~").unwrap(), None);
+        assert_eq!(refresh("other: 1").unwrap(), None);
+        assert!(refresh("refresh: -5").is_err());
+        assert!(refresh("refresh: soon").is_err());
+    }
+}
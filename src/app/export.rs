@@ -0,0 +1,236 @@
+use std::{fmt::Write as _, fs, io::Write, time::{SystemTime, UNIX_EPOCH}};
+use base64::Engine;
+use ratatui::{buffer::Buffer, style::{Color, Modifier}};
+use serde::Serialize;
+
+use crate::{
+    app::App,
+    cmd::{disk::{DeviceIo, Disk, TotalIo}, memory::Memory, network::Network, process::Process, temperature::Temperature},
+};
+
+#[derive(Serialize)]
+struct Snapshot<'a> {
+    timestamp: u64,
+    processes: &'a [Process],
+    cores_usage: &'a [f32],
+    mem_usage: Memory,
+    disks: &'a [Disk],
+    disk_io: &'a TotalIo,
+    device_io: &'a [DeviceIo],
+    network: &'a Network,
+    temperatures: &'a [Temperature],
+}
+
+fn timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Writes the data currently shown by the app to `htop-ui-<timestamp>.json`
+/// and returns the file name.
+pub fn export_snapshot(app: &App) -> Result<String, std::io::Error> {
+    let timestamp = timestamp();
+    let snapshot = Snapshot {
+        timestamp,
+        processes: &app.processes,
+        cores_usage: &app.cores_usage,
+        mem_usage: app.mem_usage,
+        disks: &app.disks_usage,
+        disk_io: &app.disk_io,
+        device_io: &app.device_io,
+        network: &app.network,
+        temperatures: &app.temperatures,
+    };
+    let file_name = format!("htop-ui-{}.json", timestamp);
+    let json = serde_json::to_string_pretty(&snapshot)?;
+    fs::write(&file_name, json)?;
+    Ok(file_name)
+}
+
+/// The process table as filtered and sorted on screen, every shown column
+/// as tab separated values under a header row.
+pub fn process_table(app: &App) -> String {
+    let header: Vec<&str> = app.columns.iter().map(|shown| app.column_header(shown.column)).collect();
+    let mut table = header.join("\t");
+    for process in app.processes.iter() {
+        let cells: Vec<String> = app.columns.iter()
+            .map(|shown| app.column_text(shown.column, process).replace(['\t', '\n'], " "))
+            .collect();
+        table.push('\n');
+        table.push_str(&cells.join("\t"));
+    }
+    table.push('\n');
+    table
+}
+
+/// Hands `text` to the terminal with an OSC 52 sequence, which sets the
+/// system clipboard from over SSH too. tmux passes it on with
+/// `set-clipboard on`. Terminals that don't support it, or have it turned
+/// off, drop the sequence without an answer, so success only means it was
+/// sent.
+pub fn copy_to_clipboard(text: &str) -> Result<(), std::io::Error> {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", encoded)?;
+    stdout.flush()
+}
+
+/// Writes a drawn frame with its colors to `htop-ui-<timestamp>.ans`, to be
+/// shown with `cat` or `less -R`, and to a standalone
+/// `htop-ui-<timestamp>.html`. Returns both file names.
+pub fn export_screen(buffer: &Buffer) -> Result<(String, String), std::io::Error> {
+    let timestamp = timestamp();
+    let ansi_name = format!("htop-ui-{}.ans", timestamp);
+    let html_name = format!("htop-ui-{}.html", timestamp);
+    fs::write(&ansi_name, ansi(buffer))?;
+    fs::write(&html_name, html(buffer))?;
+    Ok((ansi_name, html_name))
+}
+
+/// Runs of cells in the same colors and modifiers, row by row.
+fn runs(buffer: &Buffer) -> Vec<Vec<(String, Color, Color, Modifier)>> {
+    let width = buffer.area.width as usize;
+    buffer.content.chunks(width.max(1)).map(|row| {
+        let mut runs: Vec<(String, Color, Color, Modifier)> = Vec::new();
+        // The cells covered by a wide character are skipped
+        for cell in row.iter().filter(|cell| !cell.skip) {
+            match runs.last_mut() {
+                Some((text, fg, bg, modifier)) if (*fg, *bg, *modifier) == (cell.fg, cell.bg, cell.modifier) => {
+                    text.push_str(cell.symbol());
+                }
+                _ => runs.push((cell.symbol().to_string(), cell.fg, cell.bg, cell.modifier)),
+            }
+        }
+        runs
+    }).collect()
+}
+
+fn ansi(buffer: &Buffer) -> String {
+    let mut out = String::new();
+    for row in runs(buffer) {
+        for (text, fg, bg, modifier) in row {
+            let mut codes = Vec::new();
+            for (flag, code) in [
+                (Modifier::BOLD, "1"), (Modifier::DIM, "2"), (Modifier::ITALIC, "3"), (Modifier::UNDERLINED, "4"),
+                (Modifier::SLOW_BLINK, "5"), (Modifier::REVERSED, "7"), (Modifier::CROSSED_OUT, "9"),
+            ] {
+                if modifier.contains(flag) {
+                    codes.push(code.to_string());
+                }
+            }
+            codes.extend(ansi_color(fg, false));
+            codes.extend(ansi_color(bg, true));
+            let _ = write!(out, "\x1b[0;{}m{}", codes.join(";"), text);
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+fn ansi_color(color: Color, background: bool) -> Option<String> {
+    let offset = if background { 10 } else { 0 };
+    let code = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => return Some(format!("{};2;{};{};{}", 38 + offset, r, g, b)),
+        Color::Indexed(index) => return Some(format!("{};5;{}", 38 + offset, index)),
+        Color::Black => 30,
+        Color::Red => 31,
+        Color::Green => 32,
+        Color::Yellow => 33,
+        Color::Blue => 34,
+        Color::Magenta => 35,
+        Color::Cyan => 36,
+        Color::Gray => 37,
+        Color::DarkGray => 90,
+        Color::LightRed => 91,
+        Color::LightGreen => 92,
+        Color::LightYellow => 93,
+        Color::LightBlue => 94,
+        Color::LightMagenta => 95,
+        Color::LightCyan => 96,
+        Color::White => 97,
+    };
+    Some((code + offset).to_string())
+}
+
+/// Colors of xterm, which the named colors are usually close to.
+const PALETTE: [(u8, u8, u8); 16] = [
+    (0, 0, 0), (205, 0, 0), (0, 205, 0), (205, 205, 0), (0, 0, 238), (205, 0, 205), (0, 205, 205), (229, 229, 229),
+    (127, 127, 127), (255, 0, 0), (0, 255, 0), (255, 255, 0), (92, 92, 255), (255, 0, 255), (0, 255, 255), (255, 255, 255),
+];
+const DEFAULT_FG: (u8, u8, u8) = PALETTE[7];
+const DEFAULT_BG: (u8, u8, u8) = PALETTE[0];
+
+fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    let index = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => return Some((r, g, b)),
+        Color::Indexed(index @ 16..=231) => {
+            let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+            let index = index - 16;
+            return Some((level(index / 36), level(index / 6 % 6), level(index % 6)));
+        }
+        Color::Indexed(index @ 232..) => {
+            let gray = 8 + (index - 232) * 10;
+            return Some((gray, gray, gray));
+        }
+        Color::Indexed(index) => index,
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+    };
+    Some(PALETTE[index as usize])
+}
+
+fn html(buffer: &Buffer) -> String {
+    let hex = |(r, g, b): (u8, u8, u8)| format!("#{:02x}{:02x}{:02x}", r, g, b);
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>htop-ui</title></head>\n<body>\n\
+         <pre style=\"background:{};color:{};font-family:monospace;line-height:1.2\">\n",
+        hex(DEFAULT_BG), hex(DEFAULT_FG)
+    );
+    for row in runs(buffer) {
+        for (text, fg, bg, modifier) in row {
+            let (mut fg, mut bg) = (rgb(fg), rgb(bg));
+            if modifier.contains(Modifier::REVERSED) {
+                (fg, bg) = (Some(bg.unwrap_or(DEFAULT_BG)), Some(fg.unwrap_or(DEFAULT_FG)));
+            }
+            let mut style = Vec::new();
+            style.extend(fg.map(|fg| format!("color:{}", hex(fg))));
+            style.extend(bg.map(|bg| format!("background:{}", hex(bg))));
+            for (flag, css) in [
+                (Modifier::BOLD, "font-weight:bold"), (Modifier::DIM, "opacity:0.6"), (Modifier::ITALIC, "font-style:italic"),
+                (Modifier::UNDERLINED, "text-decoration:underline"), (Modifier::CROSSED_OUT, "text-decoration:line-through"),
+            ] {
+                if modifier.contains(flag) {
+                    style.push(css.to_string());
+                }
+            }
+            let text = text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+            match style.is_empty() {
+                true => out.push_str(&text),
+                false => {
+                    let _ = write!(out, "<span style=\"{}\">{}</span>", style.join(";"), text);
+                }
+            }
+        }
+        out.push('\n');
+    }
+    out.push_str("</pre>\n</body>\n</html>\n");
+    out
+}
@@ -0,0 +1,64 @@
+use crossterm::event::KeyCode;
+
+/// Text typed after `/` to filter a list, matched ignoring case.
+#[derive(Default)]
+pub(super) struct TextFilter {
+    text: String,
+    editing: bool,
+}
+
+impl TextFilter {
+    pub fn is_editing(&self) -> bool {
+        self.editing
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn set_text(&mut self, text: String) {
+        self.text = text;
+    }
+
+    pub fn start(&mut self) {
+        self.editing = true;
+    }
+
+    /// Edits the text while typing. Enter keeps it, Esc clears it.
+    pub fn handle_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char(c) => self.text.push(c),
+            KeyCode::Backspace => { self.text.pop(); }
+            KeyCode::Esc => {
+                self.text.clear();
+                self.editing = false;
+            }
+            KeyCode::Enter => self.editing = false,
+            _ => {}
+        }
+    }
+
+    pub fn matches(&self, haystack: &str) -> bool {
+        haystack.to_lowercase().contains(&self.text.to_lowercase())
+    }
+
+    /// Title describing the active filter, or `hint` when there is none.
+    pub fn title(&self, name: &str, hint: &str) -> String {
+        match self.text.is_empty() {
+            true => format!("{} ({})", name, hint),
+            false => format!("{} matching \"{}\"", name, self.text),
+        }
+    }
+
+    /// Text shown at the bottom of the list while typing.
+    pub fn prompt(&self) -> String {
+        match self.editing {
+            true => format!("/{}_", self.text),
+            false => String::new(),
+        }
+    }
+}
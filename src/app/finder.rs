@@ -0,0 +1,177 @@
+use crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::app::App;
+
+/// Matches shown in the finder popup.
+const MAX_MATCHES: usize = 50;
+
+/// Ctrl-P popup that jumps to a process by fuzzy matching its name and
+/// command line.
+#[derive(Default)]
+pub(super) struct Finder {
+    query: String,
+    state: TableState,
+}
+
+/// Scores `candidate` when every character of `query` appears in it in
+/// order. Consecutive characters and word starts score higher.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+    for wanted in query.to_lowercase().chars() {
+        let found = position + candidate[position..].iter().position(|c| *c == wanted)?;
+        score += 1;
+        if found > 0 && previous == Some(found - 1) {
+            score += 5;
+        }
+        if found == 0 || !candidate[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+    Some(score)
+}
+
+impl App {
+    pub(super) fn open_finder(&mut self) {
+        self.finder = Some(Finder { state: TableState::default().with_selected(0), ..Default::default() });
+    }
+
+    /// Rows of the process table matching the query, best first. Names
+    /// weigh more than command lines.
+    fn finder_matches(&self, query: &str) -> Vec<usize> {
+        if query.is_empty() {
+            return (0..self.processes.len().min(MAX_MATCHES)).collect();
+        }
+        let mut matches: Vec<(i64, usize)> = self.processes.iter().enumerate()
+            .filter_map(|(row, process)| {
+                let name = fuzzy_score(query, &process.process_name).map(|score| score * 2);
+                let command = fuzzy_score(query, &process.command);
+                Some((name.max(command)?, row))
+            })
+            .collect();
+        matches.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| {
+            self.processes[a.1].process_name.len().cmp(&self.processes[b.1].process_name.len())
+        }));
+        matches.into_iter().take(MAX_MATCHES).map(|(_, row)| row).collect()
+    }
+
+    pub(super) fn handle_finder_key(&mut self, code: KeyCode) {
+        let Some(finder) = self.finder.as_mut() else {
+            return;
+        };
+        let selected = finder.state.selected().unwrap_or(0);
+        match code {
+            KeyCode::Esc => self.finder = None,
+            KeyCode::Down => finder.state.select(Some(selected + 1)),
+            KeyCode::Up => finder.state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Char(c) => {
+                finder.query.push(c);
+                finder.state.select(Some(0));
+            }
+            KeyCode::Backspace => {
+                finder.query.pop();
+                finder.state.select(Some(0));
+            }
+            KeyCode::Enter => {
+                let query = finder.query.clone();
+                if let Some(row) = self.finder_matches(&query).get(selected) {
+                    self.select_row(*row);
+                }
+                self.finder = None;
+            }
+            _ => {}
+        }
+    }
+
+    pub(super) fn render_finder(&mut self, frame: &mut Frame) {
+        let Some(query) = self.finder.as_ref().map(|finder| finder.query.clone()) else {
+            return;
+        };
+        let matches = self.finder_matches(&query);
+        let [area] = Layout::vertical([Constraint::Percentage(60)])
+            .flex(layout::Flex::Center)
+            .areas(frame.area());
+        let [area] = Layout::horizontal([Constraint::Percentage(70)])
+            .flex(layout::Flex::Center)
+            .areas(area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title("Jump to process (enter to select, esc to cancel)")
+            .fg(self.style.table_fg);
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let [input_area, list_area] = Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(inner);
+        frame.render_widget(Line::from(format!("> {}_", query)), input_area);
+        let rows = matches.iter().map(|row| {
+            let process = &self.processes[*row];
+            Row::new(vec![
+                Cell::from(process.pid.to_string()),
+                Cell::from(process.display_name()),
+                Cell::from(process.command.clone()),
+            ])
+        });
+        let table = Table::new(rows, [Constraint::Length(8), Constraint::Length(16), Constraint::Min(0)])
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let Some(finder) = self.finder.as_mut() else {
+            return;
+        };
+        if finder.state.selected().is_some_and(|selected| selected >= matches.len()) {
+            finder.state.select(Some(matches.len().saturating_sub(1)));
+        }
+        frame.render_stateful_widget(table, list_area, &mut finder.state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn characters_match_in_order() {
+        assert!(fuzzy_score("ffx", "firefox").is_some());
+        assert!(fuzzy_score("xff", "firefox").is_none());
+        assert!(fuzzy_score("of", "firefox").is_none());
+    }
+
+    #[test]
+    fn non_matches() {
+        assert_eq!(fuzzy_score("chrome", "firefox"), None);
+        assert_eq!(fuzzy_score("firefoxes", "firefox"), None);
+        assert_eq!(fuzzy_score("a", ""), None);
+    }
+
+    #[test]
+    fn case_is_ignored() {
+        assert_eq!(fuzzy_score("FIRE", "firefox"), fuzzy_score("fire", "Firefox"));
+    }
+
+    #[test]
+    fn consecutive_characters_score_higher() {
+        // f at the word start scores 1 + 3, every following one 1 + 5
+        assert_eq!(fuzzy_score("fire", "firefox"), Some(4 + 6 + 6 + 6));
+        // The second f is neither consecutive nor at a word start
+        assert_eq!(fuzzy_score("ff", "firefox"), Some(4 + 1));
+        assert!(fuzzy_score("fox", "firefox") > fuzzy_score("frx", "firefox"));
+    }
+
+    #[test]
+    fn word_starts_score_higher() {
+        // j follows the dash, d is inside a word
+        assert_eq!(fuzzy_score("jd", "systemd-journald"), Some(4 + 1));
+        assert_eq!(fuzzy_score("od", "systemd-journald"), Some(1 + 1));
+        assert!(fuzzy_score("sj", "systemd-journald") > fuzzy_score("su", "systemd-journald"));
+    }
+
+    #[test]
+    fn an_empty_query_matches_everything() {
+        assert_eq!(fuzzy_score("", "firefox"), Some(0));
+        assert_eq!(fuzzy_score("", ""), Some(0));
+    }
+}
@@ -0,0 +1,194 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{
+    app::{config::{LayoutConfig, Meter}, App},
+    cmd::units,
+};
+
+impl App {
+    /// Narrowest a single meter gets before the CPUs wrap onto more rows.
+    const METER_MIN_WIDTH: u16 = 16;
+    /// CPU rows aimed for before the meters spread over more columns.
+    const CPU_METER_ROWS: usize = 4;
+
+    pub(super) fn toggle_header_meters(&mut self) {
+        self.header_meters = !self.header_meters;
+    }
+
+    /// Header meters replace the side panels when asked for or when the
+    /// terminal is too narrow for the panels.
+    pub(super) fn compact_layout(&self, area: Rect) -> bool {
+        self.header_meters || area.width < self.config.layout.compact_width.unwrap()
+    }
+
+    /// `layout.header`, or CPU, memory and swap meters and the general
+    /// info when it is empty, above the process table as in htop.
+    pub(super) fn render_compact(&mut self, frame: &mut Frame, area: Rect) {
+        let header = match self.config.layout.header.as_ref().unwrap() {
+            header if header.is_empty() => LayoutConfig::COMPACT_HEADER.to_vec(),
+            header => header.clone(),
+        };
+        // The table keeps at least half of the screen
+        let header_height = self.header_height(&header, area.width).min(area.height / 2);
+        let [header_area, process_area] = Layout::vertical([
+            Constraint::Length(header_height),
+            Constraint::Min(0),
+        ]).areas(area);
+        self.render_header(frame, header_area, &header);
+        if self.group_by_cgroup {
+            self.render_cgroups(frame, process_area);
+        } else {
+            self.render_processes_table(frame, process_area);
+        }
+    }
+
+    /// Rows of the header in order with their height. Memory and swap
+    /// share a row when they follow each other.
+    fn header_rows(&self, meters: &[Meter], width: u16) -> Vec<(Vec<Meter>, u16)> {
+        let mut rows: Vec<(Vec<Meter>, u16)> = Vec::new();
+        for meter in meters.iter().copied().filter(Meter::fits_header) {
+            let height = match meter {
+                Meter::Cpu => self.cores_usage.len().div_ceil(self.cpu_meter_columns(width)) as u16,
+                Meter::Info => self.header_info().len() as u16,
+                _ => 1,
+            };
+            let one_line = |meter: &Meter| matches!(meter, Meter::Memory | Meter::Swap);
+            match rows.last_mut() {
+                Some((row, _)) if one_line(&meter) && row.len() == 1 && one_line(&row[0]) => row.push(meter),
+                _ => rows.push((vec![meter], height)),
+            }
+        }
+        rows
+    }
+
+    pub(super) fn header_height(&self, meters: &[Meter], width: u16) -> u16 {
+        self.header_rows(meters, width).iter().map(|(_, height)| height).sum()
+    }
+
+    pub(super) fn render_header(&self, frame: &mut Frame, area: Rect, meters: &[Meter]) {
+        let rows = self.header_rows(meters, area.width);
+        let areas = Layout::vertical(rows.iter().map(|(_, height)| Constraint::Length(*height))).split(area);
+        for ((row, _), row_area) in rows.iter().zip(areas.iter()) {
+            let cells = Layout::horizontal(vec![Constraint::Ratio(1, row.len() as u32); row.len()])
+                .spacing(1)
+                .split(*row_area);
+            for (meter, cell) in row.iter().zip(cells.iter()) {
+                match meter {
+                    Meter::Cpu => self.render_cpu_meters(frame, *cell, self.cpu_meter_columns(cell.width)),
+                    Meter::Memory => frame.render_widget(self.memory_meter(cell.width), *cell),
+                    Meter::Swap => frame.render_widget(self.swap_meter(cell.width), *cell),
+                    Meter::Info => frame.render_widget(Paragraph::new(self.header_info()).fg(self.style.info_fg), *cell),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// The info panel without its borders.
+    fn header_info(&self) -> Vec<Line<'static>> {
+        let mut text: Vec<Line> = self.general_infos.iter().map(|info| Line::from(info.trim_end().to_string())).collect();
+        text.extend(self.throttling_badge());
+        if !self.pressures.is_empty() {
+            text.push(self.pressure_line());
+        }
+        let batteries = self.battery_summary();
+        if !batteries.is_empty() {
+            text.push(Line::from(batteries));
+        }
+        text
+    }
+
+    fn cpu_meter_columns(&self, width: u16) -> usize {
+        let fitting = (width / Self::METER_MIN_WIDTH).max(1) as usize;
+        self.cores_usage.len().div_ceil(Self::CPU_METER_ROWS).clamp(1, fitting)
+    }
+
+    /// CPUs fill the columns top to bottom, left to right.
+    fn render_cpu_meters(&self, frame: &mut Frame, area: Rect, columns: usize) {
+        let rows = self.cores_usage.len().div_ceil(columns).max(1);
+        let label_width = self.cores_usage.len().saturating_sub(1).to_string().len();
+        let areas = Layout::horizontal(vec![Constraint::Ratio(1, columns as u32); columns])
+            .spacing(1)
+            .split(area);
+        for (column, column_area) in areas.iter().enumerate() {
+            let lines: Vec<Line> = self.cores_usage.iter()
+                .enumerate()
+                .skip(column * rows)
+                .take(rows)
+                .map(|(idx, usage)| {
+                    let color = match self.core_highlight.contains(&idx) {
+                        true => self.style.exceed_threshold_cell,
+                        false => self.style.cpu_frame_fg,
+                    };
+                    let text = match self.cores_frequency.get(idx) {
+                        Some(mhz) if self.show_frequency => format!("{:.1}GHz", *mhz as f64 / 1000.0),
+                        _ => format!("{:.1}%", usage),
+                    };
+                    let label = format!("{:>width$}", idx, width = label_width);
+                    self.meter(&label, &[(*usage as f64 / 100.0, color)], &text, column_area.width)
+                })
+                .collect();
+            frame.render_widget(Paragraph::new(lines), *column_area);
+        }
+    }
+
+    /// Used, buffers, shared and cached memory in the colors of the memory panel.
+    fn memory_meter(&self, width: u16) -> Line<'static> {
+        let total = self.mem_usage.total.max(1) as f64;
+        let segments: Vec<(f64, Color)> = self.mem_usage.segments().into_iter()
+            .filter_map(|(name, bytes)| {
+                let color = match name {
+                    "used" => self.style.mem_frame_fg,
+                    "buffers" => self.style.mem_buffers_fg,
+                    "shared" => self.style.mem_shared_fg,
+                    "cached" => self.style.mem_cached_fg,
+                    _ => return None,
+                };
+                Some((bytes as f64 / total, color))
+            })
+            .collect();
+        let text = format!("{}/{}", units::bytes(self.mem_usage.used), units::bytes(self.mem_usage.total));
+        self.meter("Mem", &segments, &text, width)
+    }
+
+    fn swap_meter(&self, width: u16) -> Line<'static> {
+        let fraction = self.mem_usage.swap_used as f64 / self.mem_usage.swap_total.max(1) as f64;
+        let text = format!("{}/{}", units::bytes(self.mem_usage.swap_used), units::bytes(self.mem_usage.swap_total));
+        self.meter("Swp", &[(fraction, self.style.mem_frame_fg)], &text, width)
+    }
+
+    /// `label[||||||      text]`, one run of bars per segment, each given as
+    /// a fraction of the meter. The text is drawn over the right end.
+    pub(super) fn meter(&self, label: &str, segments: &[(f64, Color)], text: &str, width: u16) -> Line<'static> {
+        let inner = (width as usize).saturating_sub(label.len() + 2);
+        let mut cells: Vec<(char, Style)> = Vec::with_capacity(inner);
+        for (fraction, color) in segments {
+            let count = ((fraction.clamp(0.0, 1.0) * inner as f64) as usize).min(inner - cells.len());
+            cells.extend(std::iter::repeat_n(('|', Style::default().fg(*color)), count));
+        }
+        cells.resize(inner, (' ', Style::default()));
+        let text_start = inner.saturating_sub(text.chars().count());
+        let text_style = Style::default().fg(self.style.table_fg);
+        for (cell, char) in cells[text_start..].iter_mut().zip(text.chars()) {
+            *cell = (char, text_style);
+        }
+
+        let mut spans = vec![
+            Span::styled(label.to_string(), Style::default().fg(self.style.info_fg)),
+            Span::raw("[").bold(),
+        ];
+        let mut run: Option<(String, Style)> = None;
+        for (char, style) in cells {
+            match run.as_mut() {
+                Some((content, run_style)) if *run_style == style => content.push(char),
+                _ => {
+                    spans.extend(run.take().map(|(content, style)| Span::styled(content, style)));
+                    run = Some((char.to_string(), style));
+                }
+            }
+        }
+        spans.extend(run.map(|(content, style)| Span::styled(content, style)));
+        spans.push(Span::raw("]").bold());
+        Line::from(spans)
+    }
+}
@@ -0,0 +1,140 @@
+use crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::app::{modal::InputMode, App, View};
+
+/// Function keys shown along the bottom of the main view, as in htop.
+const FUNCTION_KEYS: [(&str, &str); 8] = [
+    ("F1", "Help"),
+    ("F2", "Setup"),
+    ("F3", "Search"),
+    ("F4", "Filter"),
+    ("F5", "Tree"),
+    ("F6", "SortBy"),
+    ("F9", "Kill"),
+    ("F10", "Quit"),
+];
+
+const CONFIRM_KEYS: [(&str, &str); 2] = [("y", "Confirm"), ("other", "Cancel")];
+const TYPING_KEYS: [(&str, &str); 3] = [("Enter", "Keep"), ("Esc", "Clear"), ("Backspace", "Delete")];
+const FINDER_KEYS: [(&str, &str); 4] = [("type", "Search"), ("Up Down", "Select"), ("Enter", "Jump"), ("Esc", "Cancel")];
+const AFFINITY_KEYS: [(&str, &str); 5] = [
+    ("h j k l", "Move"), ("Space", "Toggle"), ("a", "All"), ("Enter", "Apply"), ("Esc", "Cancel"),
+];
+const WIDE_KEYS: [(&str, &str); 3] = [("h l", "Scroll columns"), ("w", "Leave wide mode"), ("F10", "Quit")];
+const CGROUP_KEYS: [(&str, &str); 4] = [("j k", "Move"), ("Enter", "Expand"), ("G", "Ungroup"), ("F10", "Quit")];
+const DETAIL_KEYS: [(&str, &str); 4] = [("/", "Search"), ("r", "Reload limits"), ("j k", "Scroll"), ("q", "Back")];
+const OPEN_FILES_KEYS: [(&str, &str); 4] = [("/", "Search"), ("r", "Reload"), ("j k", "Scroll"), ("q", "Back")];
+const INTERFACES_KEYS: [(&str, &str); 3] = [("j k", "Move"), ("Space", "Show or hide"), ("q", "Back")];
+const CONNECTIONS_KEYS: [(&str, &str); 4] = [("j k", "Move"), ("Enter", "Go to owner"), ("r", "Sort by rate"), ("q", "Back")];
+const SERVICES_KEYS: [(&str, &str); 5] = [("j k", "Move"), ("S", "Start"), ("T", "Stop"), ("R", "Restart"), ("q", "Back")];
+const STORAGE_KEYS: [(&str, &str); 2] = [("j k", "Move"), ("q", "Back")];
+const HOSTS_KEYS: [(&str, &str); 3] = [("h j k l", "Move"), ("Enter", "Show"), ("q", "Back")];
+const COLUMNS_KEYS: [(&str, &str); 3] = [("j k", "Move"), ("Space", "Show or hide"), ("q", "Save and close")];
+#[cfg(feature = "history")]
+const HISTORY_KEYS: [(&str, &str); 2] = [("h l", "Scroll"), ("q", "Back")];
+const CLOSE_KEYS: [(&str, &str); 1] = [("q", "Back")];
+
+const MAIN_KEYS: [(&str, &str); 42] = [
+    ("j k, arrows", "Move the selection"),
+    ("PgUp PgDn, Ctrl-u Ctrl-d", "Move by a page or half a page"),
+    ("Home End", "First or last process"),
+    ("F3, Ctrl-p", "Jump to a process by name or command line"),
+    ("F4, \\", "Filter the processes by name or command line"),
+    ("F5", "Tree view"),
+    ("- +", "Collapse or expand the selected subtree"),
+    ("F6, < >", "Sort by the previous or next column"),
+    ("P m T", "Sort by CPU, memory or start time, again to reverse"),
+    ("F2, S", "Pick the process columns"),
+    ("w", "Wide mode, h l scroll the columns"),
+    ("Enter", "Process details and environment"),
+    ("l", "Open files of the process"),
+    ("a", "CPU affinity of the process"),
+    ("t", "Tag the process, actions apply to tagged processes"),
+    ("F9, x", "Send SIGTERM"),
+    ("X", "Send SIGKILL"),
+    ("[ ]", "Lower or raise the priority"),
+    ("K", "Show or hide kernel threads, drawn as [name]"),
+    ("u", "Show or hide userland threads, listed by TID"),
+    ("I", "Irix or Solaris CPU mode"),
+    ("C", "All, container or host processes"),
+    ("G", "Group by cgroup"),
+    ("F", "CPU frequency instead of usage"),
+    ("i", "Interrupts panel"),
+    ("v", "Header meters instead of the side panels"),
+    ("n", "Network interfaces with their addresses"),
+    ("c", "Connections"),
+    ("s", "Services"),
+    ("M", "Kernel memory"),
+    ("N", "NUMA nodes"),
+    ("D", "Storage, every mount with its inode usage"),
+    ("A", "Alerts and watchdog actions"),
+    ("O", "Hosts overview, Enter shows a host"),
+    ("H", "History, when recording it"),
+    ("e", "Export a snapshot"),
+    ("E", "Save the screen as ANSI text and HTML"),
+    ("y", "Send the process table as TSV to the terminal clipboard (OSC 52)"),
+    ("p, Space", "Pause updates"),
+    ("Ctrl-t", "Next theme"),
+    ("F1, ?", "This help"),
+    ("F10, q, Esc", "Quit"),
+];
+
+impl App {
+    pub(super) fn handle_help_key(&mut self, code: KeyCode) {
+        if matches!(code, KeyCode::Char('q') | KeyCode::Esc | KeyCode::F(1) | KeyCode::Char('?')) {
+            self.view = View::Main;
+        }
+    }
+
+    pub(super) fn render_help(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default().borders(Borders::ALL).title("Help (q to close)").fg(self.style.info_fg);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        // Split in two halves side by side when the terminal is too short
+        let halves = if MAIN_KEYS.len() > inner.height as usize { 2 } else { 1 };
+        let areas = Layout::horizontal(vec![Constraint::Ratio(1, halves); halves as usize]).split(inner);
+        for (keys, area) in MAIN_KEYS.chunks(MAIN_KEYS.len().div_ceil(halves as usize)).zip(areas.iter()) {
+            let rows = keys.iter().map(|(keys, action)| {
+                Row::new(vec![Cell::from(*keys).bold(), Cell::from(*action)])
+            });
+            frame.render_widget(Table::new(rows, [Constraint::Length(26), Constraint::Min(20)]), *area);
+        }
+    }
+
+    /// Keys that do something right now, modals and typing first.
+    fn footer_keys(&self) -> &'static [(&'static str, &'static str)] {
+        if let InputMode::Confirm(_) = self.input_mode {
+            return &CONFIRM_KEYS;
+        }
+        match self.view {
+            View::Main if self.affinity.is_some() => &AFFINITY_KEYS,
+            View::Main if self.finder.is_some() => &FINDER_KEYS,
+            View::Main if self.process_filter.is_editing() => &TYPING_KEYS,
+            View::Main if self.wide => &WIDE_KEYS,
+            View::Main if self.group_by_cgroup => &CGROUP_KEYS,
+            View::Main => &FUNCTION_KEYS,
+            View::Detail if self.detail.as_ref().is_some_and(|detail| detail.is_searching()) => &TYPING_KEYS,
+            View::Detail => &DETAIL_KEYS,
+            View::OpenFiles if self.open_files.as_ref().is_some_and(|open_files| open_files.is_searching()) => &TYPING_KEYS,
+            View::OpenFiles => &OPEN_FILES_KEYS,
+            View::Interfaces => &INTERFACES_KEYS,
+            View::Connections => &CONNECTIONS_KEYS,
+            View::Services => &SERVICES_KEYS,
+            View::Storage | View::Alerts => &STORAGE_KEYS,
+            View::Hosts => &HOSTS_KEYS,
+            View::Columns => &COLUMNS_KEYS,
+            View::KernelMemory | View::Numa | View::Help => &CLOSE_KEYS,
+            #[cfg(feature = "history")]
+            View::History => &HISTORY_KEYS,
+        }
+    }
+
+    pub(super) fn render_footer(&self, frame: &mut Frame, area: Rect) {
+        let label = Style::default().fg(self.style.bar_text_fg).bg(self.style.info_fg);
+        let spans: Vec<Span> = self.footer_keys().iter()
+            .flat_map(|(key, action)| [Span::raw(*key), Span::styled(format!("{:<6} ", action), label)])
+            .collect();
+        frame.render_widget(Line::from(spans), area);
+    }
+}
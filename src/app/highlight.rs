@@ -0,0 +1,130 @@
+use std::{collections::{HashMap, HashSet}, hash::Hash, time::{Duration, Instant}};
+
+/// Values highlighted over a threshold. A value is highlighted once it has
+/// stayed over the threshold for `after`, and keeps it until it drops
+/// `hysteresis` points below, so readings hovering around the threshold
+/// don't flicker.
+pub(super) struct Highlight<K> {
+    threshold: f32,
+    hysteresis: f32,
+    after: Duration,
+    above_since: HashMap<K, Instant>,
+    active: HashSet<K>,
+}
+
+impl<K: Clone + Eq + Hash> Highlight<K> {
+    pub(super) fn new(threshold: f32, hysteresis: f32, after: Duration) -> Self {
+        Self {
+            threshold,
+            hysteresis,
+            after,
+            above_since: HashMap::new(),
+            active: HashSet::new(),
+        }
+    }
+
+    /// Takes the latest value of every key, keys left out are forgotten.
+    pub(super) fn update(&mut self, values: impl IntoIterator<Item = (K, f32)>) {
+        self.update_at(Instant::now(), values);
+    }
+
+    fn update_at(&mut self, now: Instant, values: impl IntoIterator<Item = (K, f32)>) {
+        let mut seen = HashSet::new();
+        for (key, value) in values {
+            if self.active.contains(&key) {
+                if value < self.threshold - self.hysteresis {
+                    self.active.remove(&key);
+                }
+            } else if value >= self.threshold {
+                let since = *self.above_since.entry(key.clone()).or_insert(now);
+                if now.duration_since(since) >= self.after {
+                    self.active.insert(key.clone());
+                }
+            }
+            if value < self.threshold {
+                self.above_since.remove(&key);
+            }
+            seen.insert(key);
+        }
+        self.above_since.retain(|key, _| seen.contains(key));
+        self.active.retain(|key| seen.contains(key));
+    }
+
+    pub(super) fn contains(&self, key: &K) -> bool {
+        self.active.contains(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECOND: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn highlighted_only_after_staying_above() {
+        let start = Instant::now();
+        let mut highlight = Highlight::new(50.0, 0.0, 2 * SECOND);
+        highlight.update_at(start, [("cpu", 60.0)]);
+        assert!(!highlight.contains(&"cpu"));
+        highlight.update_at(start + SECOND, [("cpu", 70.0)]);
+        assert!(!highlight.contains(&"cpu"));
+        highlight.update_at(start + 2 * SECOND, [("cpu", 55.0)]);
+        assert!(highlight.contains(&"cpu"));
+    }
+
+    #[test]
+    fn dropping_below_restarts_the_wait() {
+        let start = Instant::now();
+        let mut highlight = Highlight::new(50.0, 0.0, 2 * SECOND);
+        highlight.update_at(start, [("cpu", 60.0)]);
+        highlight.update_at(start + SECOND, [("cpu", 40.0)]);
+        highlight.update_at(start + 2 * SECOND, [("cpu", 60.0)]);
+        highlight.update_at(start + 3 * SECOND, [("cpu", 60.0)]);
+        assert!(!highlight.contains(&"cpu"));
+        highlight.update_at(start + 4 * SECOND, [("cpu", 60.0)]);
+        assert!(highlight.contains(&"cpu"));
+    }
+
+    #[test]
+    fn without_a_wait_the_threshold_itself_highlights() {
+        let mut highlight = Highlight::new(50.0, 0.0, Duration::ZERO);
+        highlight.update_at(Instant::now(), [("cpu", 49.9), ("mem", 50.0)]);
+        assert!(!highlight.contains(&"cpu"));
+        assert!(highlight.contains(&"mem"));
+    }
+
+    #[test]
+    fn cleared_only_below_the_hysteresis() {
+        let start = Instant::now();
+        let mut highlight = Highlight::new(50.0, 5.0, Duration::ZERO);
+        highlight.update_at(start, [("cpu", 60.0)]);
+        assert!(highlight.contains(&"cpu"));
+        highlight.update_at(start + SECOND, [("cpu", 48.0)]);
+        assert!(highlight.contains(&"cpu"));
+        highlight.update_at(start + 2 * SECOND, [("cpu", 45.0)]);
+        assert!(highlight.contains(&"cpu"));
+        highlight.update_at(start + 3 * SECOND, [("cpu", 44.9)]);
+        assert!(!highlight.contains(&"cpu"));
+        // Within the hysteresis band but under the threshold it stays off
+        highlight.update_at(start + 4 * SECOND, [("cpu", 48.0)]);
+        assert!(!highlight.contains(&"cpu"));
+    }
+
+    #[test]
+    fn missing_keys_are_forgotten() {
+        let start = Instant::now();
+        let mut highlight = Highlight::new(50.0, 5.0, SECOND);
+        highlight.update_at(start, [(1, 60.0), (2, 60.0)]);
+        highlight.update_at(start + SECOND, [(1, 60.0), (2, 60.0)]);
+        assert!(highlight.contains(&1) && highlight.contains(&2));
+        highlight.update_at(start + 2 * SECOND, [(2, 60.0)]);
+        assert!(!highlight.contains(&1));
+        assert!(highlight.contains(&2));
+        // A key that comes back waits again, its earlier time above is gone
+        highlight.update_at(start + 3 * SECOND, [(1, 60.0), (2, 60.0)]);
+        assert!(!highlight.contains(&1));
+        highlight.update_at(start + 4 * SECOND, [(1, 60.0), (2, 60.0)]);
+        assert!(highlight.contains(&1));
+    }
+}
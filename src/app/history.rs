@@ -0,0 +1,228 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+use rusqlite::{params, Connection};
+
+use crate::app::{App, View};
+
+/// Samples older than this are pruned from the database.
+const RETENTION_SECS: i64 = 24 * 3600;
+/// Width of the time window shown in the history view.
+const WINDOW_SECS: i64 = 3600;
+/// How far a single scroll key moves the window.
+const SCROLL_SECS: i64 = 600;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Sample {
+    pub timestamp: i64,
+    pub cpu: f64,
+    pub mem: f64,
+    pub upload: f64,
+    pub download: f64,
+}
+
+/// Metrics samples persisted to SQLite and the window of them shown in the
+/// history view.
+pub struct History {
+    conn: Connection,
+    /// Seconds between now and the end of the shown window
+    offset: i64,
+    samples: Vec<Sample>,
+}
+
+impl History {
+    pub fn open(path: &str) -> Result<Self, rusqlite::Error> {
+        let conn = Connection::open(path)?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS samples (
+                timestamp INTEGER NOT NULL,
+                cpu REAL NOT NULL,
+                mem REAL NOT NULL,
+                upload REAL NOT NULL,
+                download REAL NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute("CREATE INDEX IF NOT EXISTS samples_timestamp ON samples (timestamp)", [])?;
+        let mut history = Self {
+            conn,
+            offset: 0,
+            samples: Vec::new(),
+        };
+        history.reload()?;
+        Ok(history)
+    }
+
+    pub fn record(&mut self, cpu: f64, mem: f64, upload: f64, download: f64) -> Result<(), rusqlite::Error> {
+        let timestamp = now();
+        self.conn.execute(
+            "INSERT INTO samples (timestamp, cpu, mem, upload, download) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![timestamp, cpu, mem, upload, download],
+        )?;
+        self.conn.execute(
+            "DELETE FROM samples WHERE timestamp < ?1",
+            params![timestamp - RETENTION_SECS],
+        )?;
+        if self.offset == 0 {
+            self.reload()?;
+        }
+        Ok(())
+    }
+
+    pub fn scroll_back(&mut self) -> Result<(), rusqlite::Error> {
+        self.offset = (self.offset + SCROLL_SECS).min(RETENTION_SECS - WINDOW_SECS);
+        self.reload()
+    }
+
+    pub fn scroll_forward(&mut self) -> Result<(), rusqlite::Error> {
+        self.offset = (self.offset - SCROLL_SECS).max(0);
+        self.reload()
+    }
+
+    fn window(&self) -> (i64, i64) {
+        let end = now() - self.offset;
+        (end - WINDOW_SECS, end)
+    }
+
+    fn reload(&mut self) -> Result<(), rusqlite::Error> {
+        let (start, end) = self.window();
+        let mut stmt = self.conn.prepare(
+            "SELECT timestamp, cpu, mem, upload, download FROM samples
+             WHERE timestamp BETWEEN ?1 AND ?2 ORDER BY timestamp",
+        )?;
+        self.samples = stmt
+            .query_map(params![start, end], |row| {
+                Ok(Sample {
+                    timestamp: row.get(0)?,
+                    cpu: row.get(1)?,
+                    mem: row.get(2)?,
+                    upload: row.get(3)?,
+                    download: row.get(4)?,
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+        Ok(())
+    }
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+impl App {
+    pub(super) fn record_history(&mut self) {
+        let Some(history) = self.history.as_mut() else {
+            return;
+        };
+        let snapshot = self.subscriber.snapshot();
+        let cpu = if snapshot.cores_usage.is_empty() {
+            0.0
+        } else {
+            snapshot.cores_usage.iter().sum::<f32>() / snapshot.cores_usage.len() as f32
+        };
+        if let Err(err) = history.record(cpu as f64, snapshot.mem_usage.percent_used() as f64, snapshot.network.upload, snapshot.network.download) {
+            self.status = Some(format!("Error recording history: {}", err));
+        }
+    }
+
+    pub(super) fn handle_history_key(&mut self, code: KeyCode) {
+        let Some(history) = self.history.as_mut() else {
+            return;
+        };
+        let result = match code {
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Char('H') => {
+                self.view = View::Main;
+                Ok(())
+            }
+            KeyCode::Char('h') | KeyCode::Left => history.scroll_back(),
+            KeyCode::Char('l') | KeyCode::Right => history.scroll_forward(),
+            _ => Ok(()),
+        };
+        if let Err(err) = result {
+            self.status = Some(format!("Error reading history: {}", err));
+        }
+    }
+
+    pub(super) fn render_history(&self, frame: &mut Frame, area: Rect) {
+        let Some(history) = self.history.as_ref() else {
+            return;
+        };
+        let (start, _) = history.window();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Percentage(33),
+                Constraint::Percentage(33),
+                Constraint::Percentage(34),
+            ])
+            .split(area);
+        let points = |value: fn(&Sample) -> f64| -> Vec<(f64, f64)> {
+            history.samples
+                .iter()
+                .map(|sample| ((sample.timestamp - start) as f64, value(sample)))
+                .collect()
+        };
+        let cpu = points(|sample| sample.cpu);
+        let mem = points(|sample| sample.mem);
+        let upload = points(|sample| sample.upload);
+        let download = points(|sample| sample.download);
+        let net_max = upload
+            .iter()
+            .chain(download.iter())
+            .map(|(_, value)| *value)
+            .fold(1.0, f64::max);
+        let x_labels = vec![
+            Line::from(format!("-{}m", (history.offset + WINDOW_SECS) / 60)),
+            Line::from(format!("-{}m", history.offset / 60)),
+        ];
+        let title = "History (h/l to scroll, H to close)";
+
+        let cpu_chart = Self::history_chart(
+            vec![Self::history_dataset("CPU %", &cpu, self.style.cpu_frame_fg)],
+            title,
+            x_labels.clone(),
+            100.0,
+        );
+        let mem_chart = Self::history_chart(
+            vec![Self::history_dataset("Memory %", &mem, self.style.mem_frame_fg)],
+            "",
+            x_labels.clone(),
+            100.0,
+        );
+        let net_chart = Self::history_chart(
+            vec![
+                Self::history_dataset("Upload Kbps", &upload, self.style.net_frame_fg),
+                Self::history_dataset("Download Kbps", &download, self.style.disk_io_frame_fg),
+            ],
+            "",
+            x_labels,
+            net_max,
+        );
+        frame.render_widget(cpu_chart, chunks[0]);
+        frame.render_widget(mem_chart, chunks[1]);
+        frame.render_widget(net_chart, chunks[2]);
+    }
+
+    fn history_dataset<'a>(name: &'a str, data: &'a [(f64, f64)], color: Color) -> Dataset<'a> {
+        Dataset::default()
+            .name(name)
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(color))
+            .data(data)
+    }
+
+    fn history_chart<'a>(datasets: Vec<Dataset<'a>>, title: &'a str, x_labels: Vec<Line<'a>>, max: f64) -> Chart<'a> {
+        Chart::new(datasets)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .x_axis(Axis::default()
+                .bounds([0.0, WINDOW_SECS as f64])
+                .labels(x_labels))
+            .y_axis(Axis::default()
+                .bounds([0.0, max])
+                .labels(vec![Line::from("0"), Line::from(format!("{:.0}", max))]))
+    }
+}
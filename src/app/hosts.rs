@@ -0,0 +1,165 @@
+use crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{
+    app::{App, View},
+    cmd::{hub::{self, Subscriber}, remote::{self, Remote}, snapshot::Snapshot},
+};
+
+/// A machine of the hosts overview and where its data comes from.
+pub(super) struct Host {
+    name: String,
+    remote: Option<Remote>,
+    subscriber: Subscriber,
+}
+
+impl App {
+    /// Width a host card is given before the grid adds a column.
+    const HOST_CARD_WIDTH: u16 = 40;
+    /// A line each for CPU, memory and disk inside the border.
+    const HOST_CARD_HEIGHT: u16 = 5;
+
+    /// Lists the machine the app started on first, then connects to every
+    /// host of the config with a hub of its own.
+    pub(super) fn connect_hosts(&mut self) {
+        let name = match &self.remote {
+            Some(Remote::Agent(addr)) => addr.clone(),
+            Some(Remote::Ssh { destination, .. }) => destination.clone(),
+            None => sysinfo::System::host_name().unwrap_or_else(|| "localhost".to_string()),
+        };
+        self.hosts.push(Host { name, remote: self.remote.clone(), subscriber: self.subscriber.clone() });
+        for host in self.config.hosts.clone().unwrap() {
+            let remote = match (host.agent, host.ssh) {
+                (Some(addr), _) => Remote::Agent(addr),
+                (None, Some(destination)) => Remote::Ssh {
+                    destination,
+                    command: host.ssh_command.unwrap_or_else(|| "htop-ui --agent -".to_string()),
+                },
+                (None, None) => continue,
+            };
+            let (publisher, subscriber) = hub::channel();
+            remote::connect(publisher, remote.clone(), &self.shutdown);
+            self.hosts.push(Host { name: host.name, remote: Some(remote), subscriber });
+        }
+    }
+
+    pub(super) fn handle_hosts_key(&mut self, code: KeyCode) {
+        let count = self.hosts.len();
+        let columns = self.host_columns as usize;
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Char('O') => self.view = View::Main,
+            KeyCode::Char('l') | KeyCode::Right => self.selected_host = (self.selected_host + 1).min(count - 1),
+            KeyCode::Char('h') | KeyCode::Left => self.selected_host = self.selected_host.saturating_sub(1),
+            KeyCode::Char('j') | KeyCode::Down if self.selected_host + columns < count => {
+                self.selected_host += columns;
+            }
+            KeyCode::Char('k') | KeyCode::Up => self.selected_host = self.selected_host.saturating_sub(columns),
+            KeyCode::Enter => {
+                self.show_host(self.selected_host);
+                self.view = View::Main;
+            }
+            _ => {}
+        }
+    }
+
+    /// Feeds the views from another host, starting from its latest values
+    /// so nothing of the previous host stays on screen.
+    fn show_host(&mut self, idx: usize) {
+        if idx == self.active_host {
+            return;
+        }
+        let host = &self.hosts[idx];
+        self.subscriber = host.subscriber.clone();
+        self.remote = host.remote.clone();
+        self.status = Some(format!("Showing {}", host.name));
+        self.active_host = idx;
+        // Processes of the previous host would look born or gone
+        self.known_pids.clear();
+        self.born.clear();
+        self.dying.clear();
+        self.tagged.clear();
+        self.collapsed.clear();
+        self.network_peaks.clear();
+        self.ping_history.clear();
+        // Breaches of the previous host would resolve against this one
+        self.alerts.reset();
+        self.select_row(0);
+        // The snapshot repeats earlier readings, only new ones raise alerts
+        // and get logged
+        for msg in self.subscriber.snapshot().messages() {
+            self.apply_message(msg);
+        }
+    }
+
+    /// A card per host with its CPU, memory and fullest disk, the host on
+    /// screen marked and the selected one drawn bold.
+    pub(super) fn render_hosts(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Hosts, showing {} (O to close)", self.hosts[self.active_host].name))
+            .fg(self.style.info_fg);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        self.host_columns = (inner.width / Self::HOST_CARD_WIDTH).max(1);
+        let columns = self.host_columns as usize;
+        let rows = Layout::vertical(vec![Constraint::Length(Self::HOST_CARD_HEIGHT); self.hosts.len().div_ceil(columns)])
+            .split(inner);
+        for (idx, host) in self.hosts.iter().enumerate() {
+            let Some(row) = rows.get(idx / columns) else { break };
+            let cards = Layout::horizontal(vec![Constraint::Ratio(1, columns as u32); columns]).split(*row);
+            let title = match idx == self.active_host {
+                true => format!("{} *", host.name),
+                false => host.name.clone(),
+            };
+            let mut card = Block::default().borders(Borders::ALL).title(title).fg(self.style.table_fg);
+            if idx == self.selected_host {
+                card = card.border_type(BorderType::Thick).bold();
+            }
+            let area = card.inner(cards[idx % columns]);
+            frame.render_widget(card, cards[idx % columns]);
+            frame.render_widget(Paragraph::new(self.host_summary(&host.subscriber.snapshot(), area.width)), area);
+        }
+    }
+
+    fn host_summary(&self, snapshot: &Snapshot, width: u16) -> Vec<Line<'static>> {
+        if snapshot.cores_usage.is_empty() {
+            return vec![Line::from("Waiting for data").fg(self.style.table_fg)];
+        }
+        let color = |percent: f32, threshold: f32, color: Color| match percent >= threshold {
+            true => self.style.exceed_threshold_cell,
+            false => color,
+        };
+        let cpu = snapshot.cores_usage.iter().sum::<f32>() / snapshot.cores_usage.len() as f32;
+        let mem = snapshot.mem_usage.percent_used();
+        let mut lines = vec![
+            self.meter(
+                "CPU ",
+                &[(cpu as f64 / 100.0, color(cpu, self.config.cpu_threshold.unwrap(), self.style.cpu_frame_fg))],
+                &format!("{:.1}%", cpu),
+                width,
+            ),
+            self.meter(
+                "Mem ",
+                &[(mem as f64 / 100.0, color(mem, self.config.mem_threshold.unwrap(), self.style.mem_frame_fg))],
+                &format!("{:.1}%", mem),
+                width,
+            ),
+        ];
+        if let Some(disk) = snapshot.disks.iter().max_by_key(|disk| disk.percent_used_space()) {
+            let used = disk.percent_used_space() as f32;
+            let threshold = self.config.alerts.disk_threshold.unwrap();
+            // Long mount points would cover the whole bar
+            let text = match disk.mount_point.len() + 6 <= width as usize / 2 {
+                true => format!("{} {}%", disk.mount_point, used),
+                false => format!("{}%", used),
+            };
+            lines.push(self.meter(
+                "Disk",
+                &[(used as f64 / 100.0, color(used, threshold, self.style.disk_frame_fg))],
+                &text,
+                width,
+            ));
+        }
+        lines
+    }
+}
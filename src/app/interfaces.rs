@@ -0,0 +1,116 @@
+use crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{
+    app::{App, View},
+    cmd::{network::{Interface, Network}, units},
+};
+
+impl App {
+    /// Whether `name` counts towards the network totals, the user's choice
+    /// taking precedence over the collector's default.
+    pub(super) fn interface_counted(&self, name: &str) -> bool {
+        self.interface_overrides
+            .get(name)
+            .copied()
+            .unwrap_or_else(|| Interface::counted_by_default(name))
+    }
+
+    /// Upload and download totals over the counted interfaces. Agents that
+    /// don't report interfaces keep their own totals.
+    pub(super) fn network_totals(&self, network: &Network) -> (f64, f64) {
+        if network.interfaces.is_empty() {
+            return (network.upload, network.download);
+        }
+        network.interfaces
+            .iter()
+            .filter(|interface| self.interface_counted(&interface.name))
+            .fold((0.0, 0.0), |(upload, download), interface| {
+                (upload + interface.upload, download + interface.download)
+            })
+    }
+
+    /// Sum of `volume` in bytes over the counted interfaces.
+    pub(super) fn counted_volume(&self, volume: impl Fn(&Interface) -> u64) -> u64 {
+        self.network.interfaces
+            .iter()
+            .filter(|interface| self.interface_counted(&interface.name))
+            .map(volume)
+            .sum()
+    }
+
+    pub(super) fn handle_interfaces_key(&mut self, code: KeyCode) {
+        let count = self.network.interfaces.len();
+        let selected = self.interface_state.selected().unwrap_or(0);
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Char('n') => self.view = View::Main,
+            KeyCode::Char('j') | KeyCode::Down if count > 0 => {
+                self.interface_state.select(Some((selected + 1).min(count - 1)));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.interface_state.select(Some(selected.saturating_sub(1)));
+            }
+            KeyCode::Char(' ') | KeyCode::Enter => self.toggle_interface(selected),
+            _ => {}
+        }
+    }
+
+    fn toggle_interface(&mut self, row: usize) {
+        let Some(name) = self.network.interfaces.get(row).map(|interface| interface.name.clone()) else {
+            return;
+        };
+        let counted = !self.interface_counted(&name);
+        self.status = Some(if counted {
+            format!("Counting {} in the network totals", name)
+        } else {
+            format!("Leaving {} out of the network totals", name)
+        });
+        self.interface_overrides.insert(name, counted);
+        let (upload, download) = self.network_totals(&self.network);
+        self.network.update(upload, download);
+    }
+
+    pub(super) fn render_interfaces(&mut self, frame: &mut Frame, area: Rect) {
+        let header = ["Total", "Name", "Upload", "Download", "Session sent/recv", "Since boot sent/recv", "MAC", "IP addresses"]
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .height(1);
+        let rows = self.network.interfaces.iter().map(|interface| {
+            let counted = if self.interface_counted(&interface.name) { "[x]" } else { "[ ]" };
+            Row::new(vec![
+                Cell::from(counted),
+                Cell::from(interface.name.clone()),
+                Cell::from(units::kilobits_per_second(interface.upload)),
+                Cell::from(units::kilobits_per_second(interface.download)),
+                Cell::from(format!("{} / {}", units::bytes(interface.session_transmitted), units::bytes(interface.session_received))),
+                Cell::from(format!("{} / {}", units::bytes(interface.total_transmitted), units::bytes(interface.total_received))),
+                Cell::from(interface.mac_address.clone()),
+                Cell::from(interface.ip_addresses.join(", ")),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(6),
+                Constraint::Min(12),
+                Constraint::Length(12),
+                Constraint::Length(12),
+                Constraint::Length(22),
+                Constraint::Length(22),
+                Constraint::Length(18),
+                Constraint::Min(20),
+            ],
+        )
+        .header(header)
+        .fg(self.style.net_frame_fg)
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_spacing(HighlightSpacing::Always)
+        .block(Block::default()
+                .borders(Borders::ALL)
+                .title("Network interfaces (space to count in totals, n to close)")
+                .title_bottom(Line::from(self.status.clone().unwrap_or_default()).right_aligned()));
+
+        frame.render_stateful_widget(table, area, &mut self.interface_state);
+    }
+}
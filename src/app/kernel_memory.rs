@@ -0,0 +1,52 @@
+use crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{app::{App, View}, cmd::units};
+
+impl App {
+    pub(super) fn handle_kernel_memory_key(&mut self, code: KeyCode) {
+        if let KeyCode::Char('q') | KeyCode::Esc | KeyCode::Char('M') = code {
+            self.view = View::Main;
+        }
+    }
+
+    pub(super) fn render_kernel_memory(&self, frame: &mut Frame, area: Rect) {
+        let memory = &self.kernel_memory;
+        let huge_pages = |pages: u64| format!("{} ({})", pages, units::bytes(pages * memory.huge_page_size));
+        let rows = [
+            ("Slab", units::bytes(memory.slab), "kernel object caches"),
+            ("  reclaimable", units::bytes(memory.slab_reclaimable), "dentries and inodes, freed under pressure"),
+            ("  unreclaimable", units::bytes(memory.slab_unreclaimable), "growing here hints at a kernel leak"),
+            ("Kernel stacks", units::bytes(memory.kernel_stack), "one per thread"),
+            ("Page tables", units::bytes(memory.page_tables), "virtual to physical mappings"),
+            ("Vmalloc used", units::bytes(memory.vmalloc_used), "virtually contiguous kernel allocations"),
+            ("Dirty", units::bytes(memory.dirty), "modified pages waiting to be written"),
+            ("Writeback", units::bytes(memory.writeback), "pages being written right now"),
+            ("Committed", units::bytes(memory.committed), "memory promised to processes"),
+            ("Commit limit", units::bytes(memory.commit_limit), "what can be promised under strict overcommit"),
+            ("Anon huge pages", units::bytes(memory.anon_huge_pages), "transparent huge pages"),
+            ("Huge pages total", huge_pages(memory.huge_pages_total), "preallocated, unusable for anything else"),
+            ("  free", huge_pages(memory.huge_pages_free), ""),
+            ("  reserved", huge_pages(memory.huge_pages_reserved), "promised but not faulted in yet"),
+            ("  surplus", huge_pages(memory.huge_pages_surplus), "allocated beyond the pool size"),
+        ];
+        let header = ["Field", "Value", "Meaning"]
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .height(1);
+        let rows = rows.into_iter().map(|(name, value, meaning)| {
+            Row::new(vec![Cell::from(name), Cell::from(value), Cell::from(meaning)])
+        });
+        let table = Table::new(rows, [Constraint::Length(18), Constraint::Length(20), Constraint::Min(0)])
+            .header(header)
+            .fg(self.style.mem_frame_fg)
+            .block(Block::default()
+                    .borders(Borders::ALL)
+                    .title(match memory.commit_limit {
+                        0 => "Kernel memory (not available on this system, M to close)",
+                        _ => "Kernel memory (M to close)",
+                    }));
+        frame.render_widget(table, area);
+    }
+}
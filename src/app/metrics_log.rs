@@ -0,0 +1,54 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::Write,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::cmd::snapshot::Snapshot;
+
+/// Appends one CSV row of system metrics per collector refresh. A new
+/// header line is written whenever the columns change, e.g. once the disks
+/// are known or when appending to a log of another machine.
+pub struct MetricsLog {
+    file: File,
+    header: Option<String>,
+}
+
+impl MetricsLog {
+    pub fn open(path: &str) -> Result<Self, std::io::Error> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        let header = fs::read_to_string(path)?
+            .lines()
+            .rfind(|line| line.starts_with("timestamp"))
+            .map(str::to_string);
+        Ok(Self { file, header })
+    }
+
+    pub fn write_row(&mut self, snapshot: &Snapshot) -> Result<(), std::io::Error> {
+        let mut header = vec!["timestamp".to_string()];
+        header.extend((0..snapshot.cores_usage.len()).map(|idx| format!("cpu{}", idx)));
+        header.push("mem".to_string());
+        header.push("net_up_kbps".to_string());
+        header.push("net_down_kbps".to_string());
+        header.extend(snapshot.disks.iter().map(|disk| format!("disk_{}", disk.name)));
+        let header = header.join(",");
+        if self.header.as_ref() != Some(&header) {
+            writeln!(self.file, "{}", header)?;
+            self.header = Some(header);
+        }
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let mut row = vec![timestamp.to_string()];
+        row.extend(snapshot.cores_usage.iter().map(|usage| format!("{:.1}", usage)));
+        row.push(format!("{:.1}", snapshot.mem_usage.percent_used()));
+        row.push(format!("{:.1}", snapshot.network.upload));
+        row.push(format!("{:.1}", snapshot.network.download));
+        row.extend(snapshot.disks.iter().map(|disk| disk.percent_used_space().to_string()));
+        writeln!(self.file, "{}", row.join(","))
+    }
+}
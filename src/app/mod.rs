@@ -1,321 +1,1107 @@
-mod config;
-
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
-use ratatui::{prelude::*, style::palette::tailwind, widgets::*, DefaultTerminal};
-use sysinfo::{DiskUsage, System};
-use tokio::sync::Mutex;
-use std::{
-    sync::{mpsc::{self, Receiver, Sender}, Arc}, time::{Duration, Instant}
-};
+pub mod config;
+mod affinity;
+mod alerts;
+mod cgroups;
+mod columns;
+mod connections;
+mod control;
+mod detail;
+mod duration;
+mod export;
+mod filter;
+mod finder;
+mod header;
+mod help;
+mod highlight;
+mod hosts;
+mod interfaces;
+mod kernel_memory;
+mod modal;
+mod notify;
+mod numa;
+mod open_files;
+mod ping;
+mod services;
+mod session;
+mod storage;
+mod theme;
+mod tree;
+#[cfg(feature = "history")]
+pub mod history;
+pub mod metrics_log;
+
+use crossterm::event::{Event, EventStream, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use futures_util::StreamExt;
+use ratatui::{prelude::*, widgets::*, DefaultTerminal};
+use serde::{Deserialize, Serialize};
+use std::{collections::{HashMap, HashSet, VecDeque}, time::Instant};
+use tokio::sync::mpsc;
 
 use crate::{
-    app::config::AppConfig,
-    cmd::{disk::Disk, get_disk_io, get_disk_usage, get_general_info, get_network_info, get_temperature, list_all_processes, network::Network, process, temperature::Temperature, Message}
+    alert::Alerts,
+    app::config::{AlertStyle, AppConfig, ColumnConfig, CpuMode, Meter, ProcessColumn, Theme},
+    app::theme::AppStyle,
+    app::metrics_log::MetricsLog,
+    cmd::{backoff::{self, Backoff}, battery::Battery, connection::Connection, control::Signal, disk::{DeviceIo, Disk, TotalIo}, fan::Fan, gpu::Gpu, interrupts::Interrupt, hub::{self, Publisher, Subscriber}, memory::{KernelMemory, Memory}, units, remote::{self, Remote}, shutdown::Shutdown, spawn_collectors, network::Network, numa::NumaNode, ping::PingTarget, pressure::Pressure, process, reachability::{Endpoint, Reachability}, service::Service, temperature::{Level, Temperature}, throttle::Throttling, traffic::ProcessTraffic, wsl::Wsl, Message}
 };
 
-struct AppStyle {
-    info_fg: Color,
-    table_fg: Color,
-    cpu_frame_fg: Color,
-    mem_frame_fg: Color,
-    disk_frame_fg: Color,
-    disk_io_frame_fg: Color,
-    temperature_fg: Color,
-    net_frame_fg: Color,
-    selected_row: Color,
-    exceed_threshold_cell: Color,
+enum View {
+    Main,
+    Interfaces,
+    Connections,
+    Services,
+    Detail,
+    OpenFiles,
+    KernelMemory,
+    Numa,
+    Storage,
+    Alerts,
+    Hosts,
+    Columns,
+    Help,
+    #[cfg(feature = "history")]
+    History,
+}
+
+/// Which processes are listed depending on whether they run in a container.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ContainerFilter {
+    All,
+    Containers,
+    Host,
 }
 
 pub struct App {
     exit: bool,
     general_infos: Vec<String>,
+    pressures: Vec<Pressure>,
+    /// `None` until the first probe is done.
+    reachability: Option<Reachability>,
+    ping: Vec<PingTarget>,
+    ping_history: HashMap<String, VecDeque<Option<u64>>>,
     processes: Vec<process::Process>,
+    process_traffic: HashMap<u32, ProcessTraffic>,
     selected_pid: usize, 
     network: Network,
+    network_peaks: VecDeque<f64>,
+    interface_overrides: HashMap<String, bool>,
+    interface_state: TableState,
+    storage_state: TableState,
+    alert_state: TableState,
+    connections: Vec<Connection>,
+    connection_state: TableState,
+    /// Busiest connections first instead of the kernel's order.
+    connections_by_rate: bool,
+    services: Vec<Service>,
+    service_state: TableState,
+    detail: Option<detail::ProcessDetail>,
+    open_files: Option<open_files::OpenFiles>,
+    affinity: Option<affinity::AffinityEditor>,
+    finder: Option<finder::Finder>,
+    input_mode: modal::InputMode,
+    /// PIDs that signals and renicing apply to instead of the selected row.
+    tagged: HashSet<u32>,
+    /// Every PID of the last process list, before filtering.
+    known_pids: HashSet<u32>,
+    /// When processes first appeared, for as long as they are highlighted.
+    born: HashMap<u32, Instant>,
+    /// Exited processes still listed and when they were first missing.
+    dying: HashMap<u32, Instant>,
+    /// Outcome of finished service actions, shown in the status line.
+    service_results: (mpsc::UnboundedSender<String>, mpsc::UnboundedReceiver<String>),
     cores_usage: Vec<f32>,
-    mem_usage: f32,
+    cores_frequency: Vec<u64>,
+    show_frequency: bool,
+    mem_usage: Memory,
+    kernel_memory: KernelMemory,
+    numa_nodes: Vec<NumaNode>,
+    interrupts: Vec<Interrupt>,
+    show_interrupts: bool,
     disks_usage: Vec<Disk>,
-    disk_io: DiskUsage,
+    disk_io: TotalIo,
+    device_io: Vec<DeviceIo>,
     temperatures: Vec<Temperature>,
+    fans: Vec<Fan>,
+    gpus: Vec<Gpu>,
+    batteries: Vec<Battery>,
+    /// None on boards without firmware throttling flags.
+    throttling: Option<Throttling>,
     state: TableState,
+    page_size: usize,
     style: AppStyle,
+    theme: Theme,
     blink_threshold: bool,
+    /// Process CPU and memory cells blinking over their thresholds, by PID.
+    cpu_highlight: highlight::Highlight<u32>,
+    mem_highlight: highlight::Highlight<u32>,
+    /// Cores over `single_cpu_threshold`, by index.
+    core_highlight: highlight::Highlight<usize>,
+    /// Stalled resources over `pressure_threshold`.
+    pressure_highlight: highlight::Highlight<String>,
+    /// Panels flashing since an alert about them was raised.
+    flashing: HashMap<Meter, Instant>,
+    needs_redraw: bool,
+    /// Saves the next frame drawn to files.
+    export_screen: bool,
+    paused: bool,
+    show_kernel_threads: bool,
+    show_threads: bool,
+    cpu_mode: CpuMode,
+    container_filter: ContainerFilter,
+    group_by_cgroup: bool,
+    expanded_cgroups: HashSet<String>,
+    cgroup_state: TableState,
+    columns: Vec<ColumnConfig>,
+    column_state: TableState,
+    sort_column: ProcessColumn,
+    sort_descending: bool,
+    /// Draws columns at their full width and scrolls through them.
+    wide: bool,
+    column_offset: usize,
+    tree_view: bool,
+    header_meters: bool,
+    /// Branch drawn before the name of each child in the tree view.
+    tree_prefixes: HashMap<u32, String>,
+    /// Tree nodes whose children are hidden.
+    collapsed: HashSet<u32>,
+    /// CPU and memory of each collapsed node and everything below it.
+    subtree_totals: HashMap<u32, (f32, f32)>,
+    process_filter: filter::TextFilter,
     config: AppConfig,
+    /// File the column picker saves to.
+    config_path: Option<String>,
+    status: Option<String>,
+    metrics_log: Option<MetricsLog>,
+    alerts: Alerts,
+    remote: Option<Remote>,
+    /// Set when running under WSL, which has no sensors to read.
+    wsl: Option<Wsl>,
+    /// Machines of the hosts overview, this one first, and the one on screen.
+    hosts: Vec<hosts::Host>,
+    active_host: usize,
+    selected_host: usize,
+    /// Cards per row of the hosts overview when it was last drawn.
+    host_columns: u16,
+    view: View,
+    #[cfg(feature = "history")]
+    history: Option<history::History>,
     last_tick: Instant,
-    tx: Sender<Message>,
-    rx: Receiver<Message>,
+    publisher: Publisher,
+    subscriber: Subscriber,
+    shutdown: Shutdown,
 }
 
 impl App {
-    const CONFIG_PATH: &str = "./config_example.yaml";
-    pub fn new() -> Self {
-        let (tx, rx) = mpsc::channel();
-        let app_style = AppStyle {
-            info_fg: tailwind::TEAL.c300,
-            table_fg: tailwind::LIME.c200,
-            cpu_frame_fg: tailwind::YELLOW.c300,
-            mem_frame_fg: tailwind::PURPLE.c300,
-            disk_frame_fg: tailwind::INDIGO.c300,
-            disk_io_frame_fg: tailwind::CYAN.c300,
-            temperature_fg: tailwind::ROSE.c300,
-            net_frame_fg: tailwind::GREEN.c300,
-            selected_row: tailwind::ZINC.c100,
-            exceed_threshold_cell: tailwind::PINK.c400,
-        };
-        let config = AppConfig::new(Self::CONFIG_PATH);
+    /// Smallest terminal the panels can be drawn in without overlapping.
+    const MIN_WIDTH: u16 = 80;
+    const MIN_HEIGHT: u16 = 24;
+    /// Header meters and the process table fit in less.
+    const COMPACT_MIN_WIDTH: u16 = 40;
+    /// Network samples the bar scale is taken from.
+    const NETWORK_PEAK_SAMPLES: usize = 60;
+
+    pub fn new(config: AppConfig) -> Self {
+        let (publisher, subscriber) = hub::channel();
+        let alerts = Alerts::new(&config.alerts);
+        let (hysteresis, after) = (config.highlight_hysteresis.unwrap(), config.highlight_after.unwrap());
+        let cpu_highlight = highlight::Highlight::new(config.cpu_threshold.unwrap(), hysteresis, after);
+        let mem_highlight = highlight::Highlight::new(config.mem_threshold.unwrap(), hysteresis, after);
+        let core_highlight = highlight::Highlight::new(config.single_cpu_threshold.unwrap(), hysteresis, after);
+        let pressure_highlight = highlight::Highlight::new(config.pressure_threshold.unwrap(), hysteresis, after);
         Self { 
             exit: false,
             general_infos: Vec::new(),
+            pressures: Vec::new(),
+            reachability: None,
+            ping: Vec::new(),
+            ping_history: HashMap::new(),
             processes: Vec::new(),
+            process_traffic: HashMap::new(),
             selected_pid: 0,
             network: Network::new(),
+            network_peaks: VecDeque::with_capacity(Self::NETWORK_PEAK_SAMPLES),
+            interface_overrides: HashMap::new(),
+            interface_state: TableState::default().with_selected(0),
+            storage_state: TableState::default().with_selected(0),
+            alert_state: TableState::default().with_selected(0),
+            connections: Vec::new(),
+            connection_state: TableState::default().with_selected(0),
+            connections_by_rate: false,
+            services: Vec::new(),
+            service_state: TableState::default().with_selected(0),
+            input_mode: modal::InputMode::Normal,
+            tagged: HashSet::new(),
+            known_pids: HashSet::new(),
+            born: HashMap::new(),
+            dying: HashMap::new(),
+            detail: None,
+            open_files: None,
+            affinity: None,
+            finder: None,
+            service_results: mpsc::unbounded_channel(),
             cores_usage: Vec::new(),
-            mem_usage: 0.0,
+            cores_frequency: Vec::new(),
+            show_frequency: false,
+            mem_usage: Memory::default(),
+            kernel_memory: KernelMemory::default(),
+            numa_nodes: Vec::new(),
+            interrupts: Vec::new(),
+            show_interrupts: false,
             disks_usage: Vec::new(),
-            disk_io: DiskUsage::default(),
+            disk_io: TotalIo::default(),
+            device_io: Vec::new(),
             temperatures: Vec::new(),
+            fans: Vec::new(),
+            gpus: Vec::new(),
+            batteries: Vec::new(),
+            throttling: None,
             state: TableState::default().with_selected(0),
-            style: app_style,
+            page_size: 1,
+            style: AppStyle::new(config.theme.unwrap()),
+            theme: config.theme.unwrap(),
             last_tick: Instant::now(),
             blink_threshold: false,
-            config: config,
-            tx: tx,
-            rx: rx,
+            cpu_highlight,
+            mem_highlight,
+            core_highlight,
+            pressure_highlight,
+            flashing: HashMap::new(),
+            needs_redraw: true,
+            export_screen: false,
+            paused: false,
+            show_kernel_threads: config.show_kernel_threads.unwrap(),
+            show_threads: config.show_threads.unwrap(),
+            cpu_mode: config.cpu_mode.unwrap(),
+            container_filter: ContainerFilter::All,
+            group_by_cgroup: false,
+            expanded_cgroups: HashSet::new(),
+            cgroup_state: TableState::default().with_selected(0),
+            columns: config.columns.clone().unwrap(),
+            column_state: TableState::default().with_selected(0),
+            sort_column: ProcessColumn::Cpu,
+            sort_descending: true,
+            wide: false,
+            column_offset: 0,
+            tree_view: false,
+            header_meters: config.layout.header_meters.unwrap(),
+            tree_prefixes: HashMap::new(),
+            collapsed: HashSet::new(),
+            subtree_totals: HashMap::new(),
+            process_filter: filter::TextFilter::default(),
+            config,
+            config_path: None,
+            status: None,
+            metrics_log: None,
+            alerts,
+            remote: None,
+            wsl: Wsl::detect(),
+            hosts: Vec::new(),
+            active_host: 0,
+            selected_host: 0,
+            host_columns: 1,
+            view: View::Main,
+            #[cfg(feature = "history")]
+            history: None,
+            publisher,
+            subscriber,
+            shutdown: Shutdown::new(),
         }
     }
 
+    pub fn set_metrics_log(mut self, metrics_log: MetricsLog) -> Self {
+        self.metrics_log = Some(metrics_log);
+        self
+    }
+
+    /// Subscribes another consumer to the data shown by the app.
+    pub fn subscribe(&self) -> Subscriber {
+        self.subscriber.clone()
+    }
+
+    pub fn set_config_path(mut self, config_path: String) -> Self {
+        self.config_path = Some(config_path);
+        self
+    }
+
+    /// Reads data from a remote agent instead of the local collectors.
+    pub fn set_remote(mut self, remote: Remote) -> Self {
+        self.remote = Some(remote);
+        self
+    }
+
+    #[cfg(feature = "history")]
+    pub fn set_history(mut self, history: history::History) -> Self {
+        self.history = Some(history);
+        self
+    }
+
     pub async fn run(&mut self, mut terminal: DefaultTerminal) -> Result<(), std::io::Error> {
-        let sys = Arc::new(Mutex::new(System::new_all()));
-        list_all_processes(self.tx.clone(), Arc::clone(&sys));
-        get_network_info(self.tx.clone());
-        get_disk_usage(self.tx.clone());
-        get_disk_io(self.tx.clone(), Arc::clone(&sys));
-        get_temperature(self.tx.clone());
-        get_general_info(self.tx.clone(), Arc::clone(&sys));
+        match &self.remote {
+            Some(remote) => remote::connect(self.publisher.clone(), remote.clone(), &self.shutdown),
+            None => spawn_collectors(self.publisher.clone(), &self.config, &self.shutdown),
+        }
+        self.connect_hosts();
+        self.restore_session();
+        let mut events = EventStream::new();
+        let tick_rate = self.config.tick_rate.unwrap();
+        let adaptive = &self.config.adaptive;
+        let backoff = Backoff::new(
+            adaptive.enabled.unwrap(),
+            adaptive.min_interval.unwrap(),
+            adaptive.max_interval.unwrap(),
+        );
+        let mut render_interval = tokio::time::interval(tick_rate);
         while ! self.exit {
-            if let Ok(msg) = self.rx.try_recv(){
-                match msg {
-                    Message::Processes(proc) => {
-                        let mut processes = proc;
-                        process::Process::sort_most_consume_cpu(&mut processes);
-                        self.update_processes(processes);
-                    }
-                    Message::CpuUsage(cpu_usage) => {
-                        self.cores_usage = cpu_usage;
-                    }
-                    Message::MemUsage(mem_usage) => {
-                        self.mem_usage = mem_usage;
-                    }
-                    Message::Network(net_data) => {
-                        self.network.update(net_data.upload, net_data.download);
-                    }
-                    Message::DiskUsage(disk_data) => {
-                        self.disks_usage = disk_data;
-                    }
-                    Message::DiskIO(disk_io) => {
-                        self.disk_io = disk_io;
-                    }
-                    Message::Temperature(temp) => {
-                        self.temperatures = temp;
+            tokio::select! {
+                Some(msg) = self.subscriber.next() => self.handle_message(msg),
+                Some(status) = self.service_results.1.recv() => {
+                    self.status = Some(status);
+                    self.needs_redraw = true;
+                }
+                Some(Ok(event)) = events.next() => {
+                    // Input is drawn right away, data waits for the next tick
+                    self.handle_event(event);
+                    if self.needs_redraw {
+                        self.draw(&mut terminal)?;
                     }
-                    Message::GeneralInfo(info_data) => {
-                        self.general_infos = info_data;
+                }
+                _ = render_interval.tick() => {
+                    backoff::adjust(&mut render_interval, backoff.interval(tick_rate, self.cores_usage.len()));
+                    self.handle_tick_threshold();
+                    if self.needs_redraw {
+                        self.draw(&mut terminal)?;
                     }
                 }
             }
-            terminal.draw(|frame| self.ui(frame))?;
-            self.handle_tick_threshold();
-            self.handle_keyboard_events()?;
-            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        self.save_session();
+        self.shutdown.stop().await;
+        Ok(())
+    }
+    
+    fn draw(&mut self, terminal: &mut DefaultTerminal) -> Result<(), std::io::Error> {
+        let frame = terminal.draw(|frame| self.ui(frame))?;
+        self.needs_redraw = false;
+        if self.export_screen {
+            self.export_screen = false;
+            self.status = match export::export_screen(frame.buffer) {
+                Ok((ansi, html)) => Some(format!("Exported the screen to {} and {}", ansi, html)),
+                Err(err) => Some(format!("Error exporting the screen: {}", err)),
+            };
+            self.needs_redraw = true;
         }
         Ok(())
     }
     
+    fn handle_message(&mut self, msg: Message) {
+        let events = self.alerts.evaluate(&msg, self.remote.is_none());
+        self.notify(&events);
+        // Logging reads the latest collector values, so it keeps going while paused
+        if let Message::CpuUsage(_) = msg {
+            self.log_metrics();
+            #[cfg(feature = "history")]
+            self.record_history();
+        }
+        if self.paused {
+            return;
+        }
+        self.apply_message(msg);
+    }
+
+    /// Shows the data of the message, without the alerts, logging and
+    /// history that come with a new reading.
+    fn apply_message(&mut self, msg: Message) {
+        self.needs_redraw = true;
+        match msg {
+            Message::Processes(processes) => {
+                self.update_processes(processes);
+            }
+            Message::CpuUsage(cpu_usage) => {
+                self.core_highlight.update(cpu_usage.iter().copied().enumerate());
+                self.cores_usage = cpu_usage;
+            }
+            Message::CpuFrequency(cores_frequency) => {
+                self.cores_frequency = cores_frequency;
+            }
+            Message::MemUsage(mem_usage) => {
+                self.mem_usage = mem_usage;
+            }
+            Message::KernelMemory(kernel_memory) => {
+                self.kernel_memory = kernel_memory;
+            }
+            Message::Numa(numa_nodes) => {
+                self.numa_nodes = numa_nodes;
+            }
+            Message::Interrupts(interrupts) => {
+                self.interrupts = interrupts;
+            }
+            Message::Network(net_data) => {
+                let (upload, download) = self.network_totals(&net_data);
+                self.network = net_data;
+                self.network.update(upload, download);
+                if self.network_peaks.len() == Self::NETWORK_PEAK_SAMPLES {
+                    self.network_peaks.pop_front();
+                }
+                self.network_peaks.push_back(upload.max(download));
+            }
+            Message::Connections(connections) => {
+                self.connections = connections;
+                self.sort_connections();
+            }
+            Message::Services(services) => {
+                self.services = services;
+            }
+            Message::ProcessTraffic(process_traffic) => {
+                self.process_traffic = process_traffic.into_iter().map(|traffic| (traffic.pid, traffic)).collect();
+            }
+            Message::DiskUsage(disk_data) => {
+                self.disks_usage = disk_data;
+            }
+            Message::DiskIO(disk_io) => {
+                self.disk_io = disk_io;
+            }
+            Message::DeviceIo(device_io) => {
+                self.device_io = device_io;
+            }
+            Message::Temperature(temp) => {
+                self.temperatures = temp;
+            }
+            Message::Fan(fans) => {
+                self.fans = fans;
+            }
+            Message::Gpu(gpus) => {
+                self.gpus = gpus;
+            }
+            Message::Battery(batteries) => {
+                self.batteries = batteries;
+            }
+            Message::Throttling(throttling) => {
+                self.throttling = throttling;
+            }
+            Message::Pressure(pressures) => {
+                self.pressure_highlight.update(pressures.iter().map(|pressure| (pressure.resource.clone(), pressure.avg10)));
+                self.pressures = pressures;
+            }
+            Message::Reachability(reachability) => {
+                self.reachability = Some(reachability);
+            }
+            Message::Ping(targets) => {
+                self.record_ping(targets);
+            }
+            Message::GeneralInfo(info_data) => {
+                self.general_infos = info_data;
+            }
+        }
+    }
+    
     fn handle_tick_threshold(&mut self) {
         if self.last_tick.elapsed() >= self.config.blink_threshold_rate.unwrap()  {
             self.blink_threshold = ! self.blink_threshold;
             self.last_tick = Instant::now();
+            self.needs_redraw = true;
         }
     }
     
-    fn handle_keyboard_events(&mut self) -> Result<(), std::io::Error> {
-        let timeout = self.config.tick_rate.unwrap()
-                                            .saturating_sub(self.last_tick.elapsed());
-        while event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    match key.code {
-                        KeyCode::Char('q') | KeyCode::Esc => self.exit = true,
-                        KeyCode::Char('j') | KeyCode::Down => self.next_row(),
-                        KeyCode::Char('k') | KeyCode::Up => self.previous_row(),
-                        _ => {}
-                    }
+    fn handle_event(&mut self, event: Event) {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                if let modal::InputMode::Confirm(_) = self.input_mode {
+                    self.handle_confirm_key(key.code);
+                    self.needs_redraw = true;
+                    return;
+                }
+                match self.view {
+                    View::Main => self.handle_main_key(key),
+                    View::Interfaces => self.handle_interfaces_key(key.code),
+                    View::Connections => self.handle_connections_key(key.code),
+                    View::Services => self.handle_services_key(key.code),
+                    View::Detail => self.handle_detail_key(key.code),
+                    View::OpenFiles => self.handle_open_files_key(key.code),
+                    View::KernelMemory => self.handle_kernel_memory_key(key.code),
+                    View::Numa => self.handle_numa_key(key.code),
+                    View::Storage => self.handle_storage_key(key.code),
+                    View::Alerts => self.handle_alerts_key(key.code),
+                    View::Hosts => self.handle_hosts_key(key.code),
+                    View::Columns => self.handle_columns_key(key.code),
+                    View::Help => self.handle_help_key(key.code),
+                    #[cfg(feature = "history")]
+                    View::History => self.handle_history_key(key.code),
                 }
+                self.needs_redraw = true;
             }
+            Event::Resize(_, _) => self.needs_redraw = true,
+            _ => {}
         }
-        Ok(())
     }
     
+    fn handle_main_key(&mut self, key: KeyEvent) {
+        if self.affinity.is_some() {
+            return self.handle_affinity_key(key.code);
+        }
+        if self.finder.is_some() {
+            return self.handle_finder_key(key.code);
+        }
+        if self.process_filter.is_editing() {
+            self.process_filter.handle_key(key.code);
+            return self.update_processes(self.subscriber.snapshot().processes);
+        }
+        if self.group_by_cgroup && self.handle_cgroup_key(key.code) {
+            return;
+        }
+        if self.wide && self.handle_wide_key(key.code) {
+            return;
+        }
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('d') if ctrl => self.next_half_page(),
+            KeyCode::Char('u') if ctrl => self.previous_half_page(),
+            KeyCode::Char('p') if ctrl => self.open_finder(),
+            KeyCode::Char('t') if ctrl => self.cycle_theme(),
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::F(10) => self.exit = true,
+            KeyCode::F(1) | KeyCode::Char('?') => self.view = View::Help,
+            KeyCode::F(3) => self.open_finder(),
+            KeyCode::F(4) | KeyCode::Char('\\') => self.process_filter.start(),
+            KeyCode::F(5) => self.toggle_tree_view(),
+            KeyCode::F(6) => {
+                self.shift_sort_column(true);
+                self.status = Some(format!("Sorted by {}", self.column_header(self.sort_column)));
+            }
+            KeyCode::F(9) => self.request_signal(Signal::Term),
+            KeyCode::Char('j') | KeyCode::Down => self.next_row(),
+            KeyCode::Char('k') | KeyCode::Up => self.previous_row(),
+            KeyCode::PageDown => self.next_page(),
+            KeyCode::PageUp => self.previous_page(),
+            KeyCode::Home => self.select_row(0),
+            KeyCode::End => self.select_row(usize::MAX),
+            KeyCode::Enter => self.open_detail(),
+            KeyCode::Char('l') => self.open_files(),
+            KeyCode::Char('a') => self.open_affinity(),
+            KeyCode::Char('t') => self.toggle_tag(),
+            KeyCode::Char('-') => self.collapse_subtree(true),
+            KeyCode::Char('+') | KeyCode::Char('=') => self.collapse_subtree(false),
+            KeyCode::Char('x') => self.request_signal(Signal::Term),
+            KeyCode::Char('X') => self.request_signal(Signal::Kill),
+            KeyCode::Char(']') => self.request_renice(-1),
+            KeyCode::Char('[') => self.request_renice(1),
+            KeyCode::Char('M') => self.view = View::KernelMemory,
+            KeyCode::Char('N') => self.view = View::Numa,
+            KeyCode::Char('D') => self.view = View::Storage,
+            KeyCode::Char('A') => self.view = View::Alerts,
+            KeyCode::Char('O') => {
+                self.selected_host = self.active_host;
+                self.view = View::Hosts;
+            }
+            KeyCode::Char('S') | KeyCode::F(2) => self.open_column_picker(),
+            KeyCode::Char('P') => self.sort_by(ProcessColumn::Cpu),
+            KeyCode::Char('m') => self.sort_by(ProcessColumn::Mem),
+            KeyCode::Char('T') => self.sort_by(ProcessColumn::Start),
+            KeyCode::Char('w') => self.wide = true,
+            KeyCode::Char('>') | KeyCode::Char('.') => self.shift_sort_column(true),
+            KeyCode::Char('<') | KeyCode::Char(',') => self.shift_sort_column(false),
+            KeyCode::Char('i') => self.show_interrupts = !self.show_interrupts,
+            KeyCode::Char('e') => self.export_snapshot(),
+            KeyCode::Char('y') => self.copy_processes(),
+            // Taken from the next frame drawn, without the status line of this key
+            KeyCode::Char('E') => self.export_screen = true,
+            KeyCode::Char('p') | KeyCode::Char(' ') => self.paused = !self.paused,
+            KeyCode::Char('K') => self.toggle_kernel_threads(),
+            KeyCode::Char('u') => self.toggle_threads(),
+            KeyCode::Char('I') => self.toggle_cpu_mode(),
+            KeyCode::Char('C') => self.cycle_container_filter(),
+            KeyCode::Char('G') => self.group_by_cgroup = !self.group_by_cgroup,
+            KeyCode::Char('F') => self.show_frequency = !self.show_frequency,
+            KeyCode::Char('v') => self.toggle_header_meters(),
+            KeyCode::Char('n') => self.view = View::Interfaces,
+            KeyCode::Char('c') => self.view = View::Connections,
+            KeyCode::Char('s') => self.view = View::Services,
+            #[cfg(feature = "history")]
+            KeyCode::Char('H') if self.history.is_some() => self.view = View::History,
+            _ => {}
+        }
+    }
+    
+    fn toggle_kernel_threads(&mut self) {
+        self.show_kernel_threads = !self.show_kernel_threads;
+        self.status = Some(if self.show_kernel_threads {
+            "Showing kernel threads".to_string()
+        } else {
+            "Hiding kernel threads".to_string()
+        });
+        self.update_processes(self.subscriber.snapshot().processes);
+    }
+    
+    fn toggle_threads(&mut self) {
+        self.show_threads = !self.show_threads;
+        self.status = Some(if self.show_threads {
+            "Showing userland threads".to_string()
+        } else {
+            "Hiding userland threads".to_string()
+        });
+        self.update_processes(self.subscriber.snapshot().processes);
+    }
+    
+    fn cycle_theme(&mut self) {
+        self.theme = self.theme.next();
+        self.style = AppStyle::new(self.theme);
+        self.status = Some(format!("Theme: {}", self.theme.name()));
+    }
+    
+    fn toggle_cpu_mode(&mut self) {
+        self.cpu_mode = match self.cpu_mode {
+            CpuMode::Irix => CpuMode::Solaris,
+            CpuMode::Solaris => CpuMode::Irix,
+        };
+        self.update_processes(self.subscriber.snapshot().processes);
+    }
+    
+    fn cycle_container_filter(&mut self) {
+        self.container_filter = match self.container_filter {
+            ContainerFilter::All => ContainerFilter::Containers,
+            ContainerFilter::Containers => ContainerFilter::Host,
+            ContainerFilter::Host => ContainerFilter::All,
+        };
+        self.update_processes(self.subscriber.snapshot().processes);
+    }
+    
+    fn log_metrics(&mut self) {
+        if let Some(metrics_log) = self.metrics_log.as_mut()
+            && let Err(err) = metrics_log.write_row(&self.subscriber.snapshot()) {
+            self.status = Some(format!("Error logging metrics: {}", err));
+        }
+    }
+    
+    fn export_snapshot(&mut self) {
+        self.status = match export::export_snapshot(self) {
+            Ok(file_name) => Some(format!("Exported snapshot to {}", file_name)),
+            Err(err) => Some(format!("Error exporting snapshot: {}", err)),
+        };
+    }
+    
+    fn copy_processes(&mut self) {
+        self.status = match export::copy_to_clipboard(&export::process_table(self)) {
+            // OSC 52 gets no answer, terminals without it drop the table silently
+            Ok(()) => Some(format!(
+                "Sent {} processes to the terminal over OSC 52, unconfirmed: not every terminal supports it",
+                self.processes.len()
+            )),
+            Err(err) => Some(format!("Error sending to the terminal clipboard: {}", err)),
+        };
+    }
+
     fn ui(&mut self, frame: &mut Frame) {
-        let (
-            info_area,
-            process_area, 
-            cpu_area, 
-            network_area, 
-            disk_io_area,
-            mem_area,
-            disk_area, 
-            temperature_area,
-        ) = Self::create_layout(frame);
-        self.render_widgets(frame, cpu_area, mem_area, network_area, disk_area, disk_io_area);
-        self.render_general_info(frame, info_area);
-        self.render_processes_table(frame, process_area);
-        self.render_cpu_usage(frame, cpu_area);
-        self.render_disk_io(frame, disk_io_area);
-        self.render_mem_usage(frame, mem_area);
-        self.render_network(frame, network_area);
-        self.render_disks_usage(frame, disk_area);
-        self.render_temperature(frame, temperature_area);
+        let area = frame.area();
+        let min_width = if self.compact_layout(area) { Self::COMPACT_MIN_WIDTH } else { Self::MIN_WIDTH };
+        if area.width < min_width || area.height < Self::MIN_HEIGHT {
+            self.render_too_small(frame, area, min_width);
+            return;
+        }
+        let [view_area, footer_area] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+        self.render_view(frame, view_area);
+        self.render_footer(frame, footer_area);
+        self.render_confirmation(frame);
+    }
+
+    fn render_view(&mut self, frame: &mut Frame, area: Rect) {
+        match self.view {
+            View::Main => {}
+            View::Interfaces => return self.render_interfaces(frame, area),
+            View::Connections => return self.render_connections(frame, area),
+            View::Services => return self.render_services(frame, area),
+            View::Detail => return self.render_detail(frame, area),
+            View::OpenFiles => return self.render_open_files(frame, area),
+            View::KernelMemory => return self.render_kernel_memory(frame, area),
+            View::Numa => return self.render_numa(frame, area),
+            View::Storage => return self.render_storage(frame, area),
+            View::Alerts => return self.render_alerts(frame, area),
+            View::Hosts => return self.render_hosts(frame, area),
+            View::Columns => return self.render_column_picker(frame, area),
+            View::Help => return self.render_help(frame, area),
+            #[cfg(feature = "history")]
+            View::History => return self.render_history(frame, area),
+        }
+        if self.compact_layout(area) {
+            self.render_compact(frame, area);
+        } else {
+            self.render_panels(frame, area);
+        }
+        self.render_affinity(frame);
+        self.render_finder(frame);
+    }
+
+    /// The header meters, if any, above the two columns of panels.
+    fn render_panels(&mut self, frame: &mut Frame, area: Rect) {
+        let header = self.config.layout.header.clone().unwrap();
+        let header_height = self.header_height(&header, area.width).min(area.height / 2);
+        let [header_area, columns_area] = Layout::vertical([
+            Constraint::Length(header_height),
+            Constraint::Min(0),
+        ]).areas(area);
+        self.render_header(frame, header_area, &header);
+        for (meter, area) in self.create_layout(columns_area) {
+            self.render_panel(frame, meter, area);
+        }
+    }
+
+    fn render_panel(&mut self, frame: &mut Frame, meter: Meter, area: Rect) {
+        // Panels whose own block leaves the borders plain get them colored here
+        let border_fg = match meter {
+            Meter::Cpu => Some(self.style.cpu_frame_fg),
+            Meter::Network | Meter::Reachability | Meter::Ping => Some(self.style.net_frame_fg),
+            Meter::Memory => Some(self.style.mem_frame_fg),
+            Meter::Disks => Some(self.style.disk_frame_fg),
+            Meter::DiskIo => Some(self.style.disk_io_frame_fg),
+            _ => None,
+        };
+        if let Some(color) = border_fg {
+            frame.render_widget(Block::new().borders(Borders::ALL).fg(color), area);
+        }
+        match meter {
+            Meter::Info => self.render_general_info(frame, area),
+            Meter::Processes if self.group_by_cgroup => self.render_cgroups(frame, area),
+            Meter::Processes => self.render_processes_table(frame, area),
+            Meter::Cpu => self.render_cpu_usage(frame, area),
+            Meter::Memory => self.render_mem_usage(frame, area),
+            Meter::Network => self.render_network(frame, area),
+            Meter::DiskIo => self.render_disk_io(frame, area),
+            Meter::Disks => self.render_disks_usage(frame, area),
+            Meter::Sensors => self.render_sensors(frame, area),
+            Meter::Reachability => self.render_reachability(frame, area),
+            Meter::Ping => self.render_ping(frame, area),
+            Meter::Swap => {}
+        }
+        self.flash_border(frame, meter, area);
+    }
+
+    /// Temperatures only say they are not available under WSL, they are
+    /// left out there unless a remote host is shown.
+    fn shows_temperature(&self) -> bool {
+        self.wsl.is_none() || self.remote.is_some()
+    }
+
+    /// Whether the sensors area has any of its panels to show.
+    fn shows_sensors(&self) -> bool {
+        !self.gpus.is_empty() || self.shows_temperature() || self.show_interrupts
+    }
+
+    /// GPUs and interrupts share the temperature area when there is something to show.
+    fn render_sensors(&mut self, frame: &mut Frame, area: Rect) {
+        let show_gpus = !self.gpus.is_empty();
+        let show_temperature = self.shows_temperature();
+        let panels = show_gpus as usize + show_temperature as usize + self.show_interrupts as usize;
+        let areas = Layout::vertical(vec![Constraint::Ratio(1, panels as u32); panels])
+            .split(area);
+        let mut areas = areas.iter().copied();
+        if show_gpus && let Some(area) = areas.next() {
+            self.render_gpus(frame, area);
+        }
+        if show_temperature && let Some(area) = areas.next() {
+            self.render_temperature(frame, area);
+        }
+        if self.show_interrupts && let Some(area) = areas.next() {
+            self.render_interrupts(frame, area);
+        }
+    }
+    
+    fn render_too_small(&self, frame: &mut Frame, area: Rect, min_width: u16) {
+        let text = vec![
+            Line::from("Terminal too small"),
+            Line::from(format!(
+                "need {}x{}, have {}x{}",
+                min_width, Self::MIN_HEIGHT, area.width, area.height
+            )),
+        ];
+        let [text_area] = Layout::vertical([Constraint::Length(2)])
+            .flex(layout::Flex::Center)
+            .areas(area);
+        frame.render_widget(
+            Paragraph::new(text).centered().fg(self.style.info_fg),
+            text_area
+        );
     }
     
+    /// Merges the incoming list into `self.processes` by PID: rows that are
+    /// still shown are updated in place, filtered ones are dropped and new
+    /// ones appended, so the table is sorted only once per update. Exited
+    /// processes stay until `process_highlight` has passed.
     fn update_processes(&mut self, processes: Vec<process::Process>) {
-        self.processes.clear();
-        for process in processes {
-            if process.cpu_usage < 0.2 {
-                continue;
+        let now = Instant::now();
+        let highlight = self.config.process_highlight.unwrap();
+        let alive: HashSet<u32> = processes.iter().map(|process| process.pid).collect();
+        // The first list has nothing to compare with
+        if !self.known_pids.is_empty() {
+            for pid in alive.difference(&self.known_pids) {
+                self.born.insert(*pid, now);
+            }
+        }
+        self.born.retain(|_, seen| now.duration_since(*seen) < highlight);
+        self.dying.retain(|pid, _| !alive.contains(pid));
+        let min_cpu = self.config.min_cpu_display.unwrap();
+        let min_mem = self.config.min_mem_display.unwrap();
+        let cores = match self.cpu_mode {
+            CpuMode::Irix => 1.0,
+            CpuMode::Solaris => self.cores_usage.len().max(1) as f32,
+        };
+        let mut incoming: HashMap<u32, process::Process> = processes
+            .into_iter()
+            .map(|mut process| {
+                process.cpu_usage /= cores;
+                process
+            })
+            // Either usage is enough to be shown, a cutoff of 0 is off
+            .filter(|process| {
+                (min_cpu == 0.0 && min_mem == 0.0)
+                    || (min_cpu > 0.0 && process.cpu_usage >= min_cpu)
+                    || (min_mem > 0.0 && process.mem_usage >= min_mem)
+            })
+            .filter(|process| self.show_kernel_threads || !process.kernel_thread)
+            .filter(|process| self.show_threads || process.thread_of.is_none())
+            .filter(|process| self.process_filter.matches(&process.process_name) || self.process_filter.matches(&process.command))
+            .filter(|process| match self.container_filter {
+                ContainerFilter::All => true,
+                ContainerFilter::Containers => process.container.is_some(),
+                ContainerFilter::Host => process.container.is_none(),
+            })
+            .map(|process| (process.pid, process))
+            .collect();
+        self.processes.retain_mut(|current| match incoming.remove(&current.pid) {
+            Some(process) => {
+                current.update(process);
+                true
+            }
+            None if !alive.contains(&current.pid) => {
+                let since = *self.dying.entry(current.pid).or_insert(now);
+                now.duration_since(since) < highlight
+            }
+            None => false,
+        });
+        self.processes.extend(incoming.into_values());
+        self.dying.retain(|pid, _| self.processes.iter().any(|process| process.pid == *pid));
+        self.tagged.retain(|pid| !self.dying.contains_key(pid) && self.processes.iter().any(|process| process.pid == *pid));
+        self.known_pids = alive;
+        self.sort_processes();
+        // Tree totals are only known once sorted
+        let totals: Vec<(u32, (f32, f32))> = self.processes.iter()
+            .map(|process| (process.pid, self.subtree_total(process)))
+            .collect();
+        self.cpu_highlight.update(totals.iter().map(|(pid, (cpu, _))| (*pid, *cpu)));
+        self.mem_highlight.update(totals.iter().map(|(pid, (_, mem))| (*pid, *mem)));
+    }
+    
+    /// Green while a process is new, dimmed once it has exited.
+    fn churn_style(&self, pid: u32) -> Option<Style> {
+        if self.dying.contains_key(&pid) {
+            return Some(Style::default().fg(self.style.dying_fg).add_modifier(Modifier::DIM));
+        }
+        match self.born.get(&pid) {
+            Some(seen) if seen.elapsed() < self.config.process_highlight.unwrap() => {
+                Some(Style::default().fg(self.style.bar_text_fg).bg(self.style.new_row_bg))
             }
-            self.processes.push(process);
+            _ => None,
         }
-        self.processes.sort_by(|a, b| b.cpu_usage.partial_cmp(&a.cpu_usage).unwrap());
     }
     
-    fn blink_cell(value: f32, threshold: f32, blink: bool, style: Color) -> Cell<'static> {
-        let exceed_threshold_cell = Style::default()
-            .add_modifier(Modifier::UNDERLINED)
-            .fg(style);
-        if value >= threshold && blink {
-            return Cell::from(format!("{:.1}%", value)).style(exceed_threshold_cell)
-        } else {
-            return Cell::from(format!("{:.1}%", value))
+    /// Running, zombie, stopped and uninterruptible processes stand out,
+    /// sleeping ones keep the table color.
+    fn state_color(&self, state: &str) -> Option<Color> {
+        match state {
+            "R" => Some(self.style.state_running_fg),
+            "Z" => Some(self.style.state_zombie_fg),
+            "T" | "t" => Some(self.style.state_stopped_fg),
+            "D" => Some(self.style.state_disk_sleep_fg),
+            _ => None,
+        }
+    }
+    
+    /// A value that may be over its threshold, drawn as `alert_style` says.
+    fn alert_cell(&self, text: String, highlighted: bool) -> Cell<'static> {
+        if !highlighted {
+            return Cell::from(text);
+        }
+        let color = Style::default().fg(self.style.exceed_threshold_cell);
+        match self.config.alert_style.unwrap() {
+            AlertStyle::Blink if self.blink_threshold => Cell::from(text).style(color.add_modifier(Modifier::UNDERLINED)),
+            AlertStyle::Blink => Cell::from(text),
+            AlertStyle::Bold => Cell::from(text).style(color.add_modifier(Modifier::BOLD)),
+            AlertStyle::Reverse => Cell::from(text).style(color.add_modifier(Modifier::REVERSED)),
+            AlertStyle::Color => Cell::from(text).style(color),
+            AlertStyle::Icon => Cell::from(format!("▲ {}", text)).style(color),
         }
     }
     
     fn render_cpu_usage(&mut self, frame: &mut Frame, area: Rect) {
         let mut bars = Vec::new();
         let mut bar_color = self.style.cpu_frame_fg;
-        let title = Line::from("CPU usage").centered();
+        let title = if self.show_frequency { "CPU usage (GHz)" } else { "CPU usage" };
         let block = Block::new()
             .borders(Borders::ALL)
             .padding(Padding::horizontal(3))
-            .title(title);
+            .title(Line::from(title).centered());
         for (idx, cores_usage) in self.cores_usage.iter().enumerate() {
-            if *cores_usage > self.config.single_cpu_threshold.unwrap() {
+            if self.core_highlight.contains(&idx) {
                 bar_color = self.style.exceed_threshold_cell;
             } 
+            // The bars keep showing usage, only the text switches to the clock
+            let text_value = match self.cores_frequency.get(idx) {
+                Some(mhz) if self.show_frequency => format!("{:.1}", *mhz as f64 / 1000.0),
+                _ => format!("{}%", *cores_usage as u64),
+            };
             bars.push(
                 Bar::default()
                     .value(*cores_usage as u64)
                     .label(Line::from(format!("#{idx}")))
-                    .text_value(format!("{}%", *cores_usage as u64))
+                    .text_value(text_value)
                     .style(bar_color)
             );
         }
-        let bar_chart = BarChart::default()
-            .block(block)
-            .data(BarGroup::default().bars(&bars))
+        let mut bar_chart = BarChart::default().block(block);
+        // Grouping only helps on machines that have several nodes
+        if self.config.group_cpus_by_node.unwrap() && self.numa_nodes.len() > 1 {
+            for node in &self.numa_nodes {
+                let node_bars: Vec<Bar> = node.cpus.iter().filter_map(|cpu| bars.get(*cpu).cloned()).collect();
+                bar_chart = bar_chart.data(
+                    BarGroup::default()
+                        .label(Line::from(format!("node{}", node.id)).centered())
+                        .bars(&node_bars)
+                );
+            }
+        } else {
+            bar_chart = bar_chart.data(BarGroup::default().bars(&bars));
+        }
+        let bar_chart = bar_chart
+            .group_gap(3)
             .direction(Direction::Vertical)
             .bar_width(5)
             .bar_gap(6)
-            .bar_style(Style::default().bg(Color::DarkGray))
+            .bar_style(Style::default().bg(self.style.bar_bg))
             .max(100);
         frame.render_widget(bar_chart, area);
     }
     
+    /// A read and a write bar per block device, or the total of the
+    /// processes where `/proc/diskstats` is not available.
     fn render_disk_io(&self, frame: &mut Frame, area: Rect) {
         let title = Line::from("Read / Write").centered();
         let block = Block::new()
             .borders(Borders::ALL)
             .padding(Padding::horizontal(3))
             .title(title);
-        let bar_style = Style::default()
-            .fg(self.style.disk_io_frame_fg)
-            .bg(Color::DarkGray);
-        let text_style = Style::default()
-            .fg(tailwind::BLACK)
-            .bg(self.style.disk_io_frame_fg);
-        let read_mbs = self.disk_io.read_bytes / 1024;
-        let write_mbs = self.disk_io.written_bytes / 1024;
-        let bars = vec![ 
-            Bar::default()
-                .value(read_mbs)
-                .value_style(Style::default().bg(self.style.disk_io_frame_fg))
-                .text_value(format!("{} Kb/s", read_mbs))
-                .value_style(text_style)
-                .label(Line::from("Read"))
-                .style(bar_style),
-            Bar::default()
-                .value(write_mbs)
-                .value_style(Style::default().bg(self.style.disk_io_frame_fg))
-                .text_value(format!("{} Kb/s", write_mbs))
-                .value_style(text_style)
-                .label(Line::from("Write"))
-                .style(bar_style),
-        ];
-        
-        let bar_chart = BarChart::default()
+        let rates: Vec<(String, f64, f64)> = match self.device_io.is_empty() {
+            true => vec![(String::new(), self.disk_io.read, self.disk_io.write)],
+            false => self.device_io.iter().map(|device| (device.name.clone(), device.read, device.write)).collect(),
+        };
+        let mut bar_chart = BarChart::default()
             .block(block)
-            .data(BarGroup::default().bars(&bars))
             .direction(Direction::Horizontal)
             .bar_width(1)
+            .bar_gap(0)
+            .group_gap(1)
             .max(1_000_000);
+        for (name, read_rate, write_rate) in rates {
+            let bars = [
+                self.disk_io_bar(format!("{} Read", name), read_rate),
+                self.disk_io_bar(format!("{} Write", name), write_rate),
+            ];
+            bar_chart = bar_chart.data(BarGroup::default().bars(&bars));
+        }
         frame.render_widget(bar_chart, area);
     }
-    
+
+    fn disk_io_bar(&self, label: String, rate: f64) -> Bar<'static> {
+        Bar::default()
+            .value(rate as u64 / 1000)
+            .text_value(units::bytes_per_second(rate))
+            .value_style(Style::default().fg(self.style.bar_text_fg).bg(self.style.disk_io_frame_fg))
+            .label(Line::from(label.trim_start().to_string()))
+            .style(Style::default().fg(self.style.disk_io_frame_fg).bg(self.style.bar_bg))
+    }
+
     fn render_mem_usage(&self, frame: &mut Frame, area: Rect) {
-        let title = Line::from("Memory usage").centered();
+        let title = Line::from(format!(
+            "Memory usage {:.1}% {} of {}",
+            self.mem_usage.percent_used(),
+            units::bytes(self.mem_usage.used),
+            units::bytes(self.mem_usage.total)
+        )).centered();
         let block = Block::new()
             .borders(Borders::ALL)
             .padding(Padding::horizontal(3))
-            .title(title);
-        let bar_style = Style::default()
             .fg(self.style.mem_frame_fg)
-            .bg(Color::DarkGray);   
-        let bar = vec![
-            Bar::default()
-                .value(self.mem_usage as u64)
-                .value_style(Style::default().bg(self.style.mem_frame_fg))
-                .label(Line::from(format!("{:.1}%", self.mem_usage)))
-                .style(bar_style)
-        ];
-        let bar_chart = BarChart::default()
-            .block(block)
-            .data(BarGroup::default().bars(&bar))
-            .direction(Direction::Horizontal)
-            .bar_width(1)
-            .max(100);
-        frame.render_widget(bar_chart, area);
+            .title(title);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let segments: Vec<(&str, u64, Color)> = self.mem_usage.segments().into_iter().map(|(name, bytes)| {
+            let color = match name {
+                "used" => self.style.mem_frame_fg,
+                "buffers" => self.style.mem_buffers_fg,
+                "shared" => self.style.mem_shared_fg,
+                "cached" => self.style.mem_cached_fg,
+                _ => self.style.bar_bg,
+            };
+            (name, bytes, color)
+        }).collect();
+        // Each segment is rounded down, free memory takes what is left
+        let width = inner.width as u64;
+        let total = self.mem_usage.total.max(1);
+        let mut drawn = 0;
+        let mut bar: Vec<Span> = segments.iter().map(|(name, bytes, color)| {
+            let cells = match *name {
+                "free" => 0,
+                _ => (bytes * width / total).min(width - drawn),
+            };
+            drawn += cells;
+            Span::styled(" ".repeat(cells as usize), Style::default().bg(*color))
+        }).collect();
+        bar.push(Span::styled(" ".repeat((width - drawn) as usize), Style::default().bg(self.style.bar_bg)));
+
+        let mut legend: Vec<Span> = segments.iter()
+            .filter(|(name, _, _)| *name != "free")
+            .flat_map(|(name, bytes, color)| [
+                Span::styled("■ ", Style::default().fg(*color)),
+                Span::raw(format!("{} {}  ", name, units::bytes(*bytes))),
+            ])
+            .collect();
+        if self.mem_usage.available > 0 {
+            legend.push(Span::raw(format!("avail {}", units::bytes(self.mem_usage.available))));
+        }
+        let [bar_area, legend_area] = Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(inner);
+        frame.render_widget(Line::from(bar), bar_area);
+        frame.render_widget(Paragraph::new(Line::from(legend)).wrap(Wrap { trim: true }), legend_area);
     }
     
     fn render_disks_usage(&self, frame: &mut Frame, area: Rect) {
-        let title = Line::from("Disk usage").centered();
+        // Every bar takes two rows inside the borders, the storage view
+        // lists the ones cut off
+        let fitting = area.height.saturating_sub(2) as usize / 2;
+        let title = match self.disks_usage.len() > fitting {
+            true => Line::from(format!("Disk usage (D for all {} mounts)", self.disks_usage.len())).centered(),
+            false => Line::from("Disk usage").centered(),
+        };
         let block = Block::new()
             .borders(Borders::ALL)
             .padding(Padding::horizontal(3))
             .title(title);
         let bar_style = Style::default()
             .fg(self.style.disk_frame_fg)
-            .bg(Color::DarkGray);
+            .bg(self.style.bar_bg);
         let text_style = Style::default()
-            .fg(tailwind::BLACK)
+            .fg(self.style.bar_text_fg)
             .bg(self.style.disk_frame_fg);
+        // Mounts that raise a disk alert
+        let low_bar_style = bar_style.fg(self.style.exceed_threshold_cell);
+        let low_text_style = text_style.bg(self.style.exceed_threshold_cell);
         let mut bars: Vec<Bar> = Vec::new();
         for disk in self.disks_usage.iter() {
-            let total_space_gb = disk.total_space / 1_000_000_000;
+            let (bar_style, text_style) = match self.alerts.low_on_space(disk) {
+                true => (low_bar_style, low_text_style),
+                false => (bar_style, text_style),
+            };
             bars.push(
                 Bar::default()
-                    .value(disk.percent_used_space() as u64)
+                    .value(disk.percent_used_space())
                     .value_style(Style::default().bg(self.style.mem_frame_fg))
-                    .text_value(format!("{}% of {}GB", disk.percent_used_space(), total_space_gb))
+                    .text_value(format!("{}% of {}", disk.percent_used_space(), units::bytes(disk.total_space)))
                     .value_style(text_style)
                     .label(Line::from(format!("{:?}", disk.name)))
                     .style(bar_style)
@@ -331,49 +1117,196 @@ impl App {
     }
     
     fn render_network(&mut self, frame: &mut Frame, area: Rect) {
-        let title = Line::from("Network").centered();
-        let block = Block::new()
+        // Scaled to the recent peak so both slow and fast links stay readable
+        let peak = self.network_peaks.iter().copied().fold(1.0, f64::max);
+        let title = Line::from(format!("Network (max {})", units::kilobits_per_second(peak))).centered();
+        let mut block = Block::new()
             .borders(Borders::ALL)
             .padding(Padding::horizontal(3))
             .title(title);
+        // Agents that don't report interfaces have no volumes either
+        if !self.network.interfaces.is_empty() {
+            block = block.title_bottom(Line::from(format!(
+                "Session {} up {} down, since boot {} up {} down",
+                units::bytes(self.counted_volume(|interface| interface.session_transmitted)),
+                units::bytes(self.counted_volume(|interface| interface.session_received)),
+                units::bytes(self.counted_volume(|interface| interface.total_transmitted)),
+                units::bytes(self.counted_volume(|interface| interface.total_received)),
+            )).centered());
+        }
         let bar_style = Style::default()
             .fg(self.style.net_frame_fg)
-            .bg(Color::DarkGray);   
+            .bg(self.style.bar_bg);
         let bar = vec![
             Bar::default()
                 .value(self.network.upload as u64)
                 .value_style(Style::default().bg(self.style.net_frame_fg))
-                .label(Line::from(format!("Upload {:.1} Kbps", self.network.upload)))
+                .label(Line::from(format!("Upload {}", units::kilobits_per_second(self.network.upload))))
                 .style(bar_style),
             Bar::default()
                 .value(self.network.download as u64)
                 .value_style(Style::default().bg(self.style.net_frame_fg))
-                .label(Line::from(format!("Download {:.1} Kbps", self.network.download)))
+                .label(Line::from(format!("Download {}", units::kilobits_per_second(self.network.download))))
                 .style(bar_style)
         ];
         let bar_chart = BarChart::default()
-            .block(block)
             .data(BarGroup::default().bars(&bar))
             .direction(Direction::Horizontal)
             .bar_width(1)
-            .max(200);
-        frame.render_widget(bar_chart, area);
+            .bar_gap(0)
+            .max(peak.ceil() as u64);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        // The addresses of the counted interfaces go below the bars when
+        // the panel is tall enough
+        let [bars_area, addresses_area] = Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(inner);
+        frame.render_widget(bar_chart, bars_area);
+        let addresses: Vec<Line> = self.network.interfaces.iter()
+            .filter(|interface| self.interface_counted(&interface.name))
+            .map(|interface| Line::from(vec![
+                Span::raw(format!("{} ", interface.name)).bold(),
+                Span::raw(interface.ip_addresses.join(" ")),
+                Span::raw(format!(" {}", interface.mac_address)).dim(),
+            ]))
+            .collect();
+        frame.render_widget(Paragraph::new(addresses).fg(self.style.net_frame_fg), addresses_area);
+    }
+    
+    /// The gateway and the DNS servers, each with a dot telling whether it
+    /// answered the last probe.
+    fn render_reachability(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .padding(Padding::horizontal(3))
+            .title(Line::from("Reachability").centered());
+        let endpoint = |role: &str, endpoint: &Endpoint| {
+            let (dot, state) = match endpoint.up {
+                true => (Span::raw("● ").fg(self.style.state_running_fg), "up"),
+                false => (Span::raw("● ").fg(self.style.state_zombie_fg), "down"),
+            };
+            Line::from(vec![dot, Span::raw(format!("{:<8} {} {}", role, endpoint.address, state))])
+        };
+        let lines: Vec<Line> = match &self.reachability {
+            None => vec![Line::from("Probing the gateway and DNS servers")],
+            Some(reachability) => {
+                let gateway = match &reachability.gateway {
+                    Some(gateway) => endpoint("Gateway", gateway),
+                    None => Line::from("No default route"),
+                };
+                let mut lines = vec![gateway];
+                lines.extend(reachability.dns.iter().map(|dns| endpoint("DNS", dns)));
+                if reachability.dns.is_empty() {
+                    lines.push(Line::from("No DNS servers in /etc/resolv.conf"));
+                }
+                lines
+            }
+        };
+        frame.render_widget(Paragraph::new(lines).fg(self.style.net_frame_fg).block(block), area);
+    }
+
+    fn render_interrupts(&self, frame: &mut Frame, area: Rect) {
+        let header = ["Source", "Per second", "Description"]
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .height(1);
+        let rows = self.interrupts.iter().map(|interrupt| {
+            Row::new(vec![
+                Cell::from(interrupt.source.clone()),
+                Cell::from(format!("{:.0}", interrupt.rate)),
+                Cell::from(interrupt.description.clone()),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [Constraint::Length(10), Constraint::Length(11), Constraint::Min(10)],
+        )
+        .header(header)
+        .fg(self.style.info_fg)
+        .block(Block::default().borders(Borders::ALL).title(match self.interrupts.is_empty() {
+            true => "Interrupts (not available on this system, i to hide)",
+            false => "Interrupts (i to hide)",
+        }));
+        frame.render_widget(table, area);
+    }
+
+    fn render_gpus(&self, frame: &mut Frame, area: Rect) {
+        let header = ["Name", "Usage", "Memory", "°C"]
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .height(1);
+        let rows = self.gpus.iter().map(|gpu| {
+            Row::new(vec![
+                Cell::from(gpu.name.clone()),
+                Cell::from(format!("{:.0}%", gpu.utilization)),
+                Cell::from(format!(
+                    "{:.0}% of {}",
+                    gpu.percent_memory_used(),
+                    units::bytes(gpu.memory_total)
+                )),
+                Cell::from(format!("{:.0}", gpu.temperature)),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(20),
+                Constraint::Length(8),
+                Constraint::Length(18),
+                Constraint::Length(6),
+            ],
+        )
+        .header(header)
+        .fg(self.style.gpu_fg)
+        .block(Block::default().borders(Borders::ALL).title("GPU"));
+        frame.render_widget(table, area);
     }
     
     fn render_temperature(&mut self, frame: &mut Frame, area: Rect) {
-        let header = ["Name", "°C", "Max value", "Critical"]
+        let header = ["Name", "Value", "Max value", "Critical"]
             .into_iter()
             .map(Cell::from)
             .collect::<Row>()
             .height(1);
+        let threshold = self.config.alerts.temperature_threshold.unwrap();
         let rows = self.temperatures.iter().map(|temperature| {
+            let value = Cell::from(format!("{} °C", temperature.value));
+            let value = match temperature.level(threshold) {
+                Level::Normal => value,
+                Level::High => value.fg(self.style.sensor_high_fg),
+                Level::Critical => value.fg(self.style.sensor_critical_fg).add_modifier(Modifier::BOLD),
+            };
             Row::new(vec![
                 Cell::from(temperature.label.clone()),
-                Cell::from(temperature.value.to_string()),
+                value,
                 Cell::from(temperature.max.to_string()),
                 Cell::from(temperature.critical.to_string()),
             ])
         });
+        // A stopped fan only matters while something is running hot
+        let hot = self.temperatures
+            .iter()
+            .any(|temperature| temperature.level(threshold) != Level::Normal);
+        let fan_rows = self.fans.iter().map(|fan| {
+            let rpm = self.alert_cell(format!("{} RPM", fan.rpm), hot && fan.rpm == 0);
+            Row::new(vec![Cell::from(fan.label.clone()), rpm])
+        });
+        let rows = rows.chain(fan_rows);
+        let block = Block::default().borders(Borders::ALL).title("Sensors");
+        if self.temperatures.is_empty() && self.fans.is_empty() {
+            let text = if cfg!(windows) {
+                // The thermal zones of WMI are only readable by administrators
+                "Not available on this system, run htop-ui as administrator"
+            } else if cfg!(target_os = "freebsd") {
+                // dev.cpu.N.temperature only exists with one of them loaded
+                "Not available on this system, load coretemp or amdtemp for CPU temperatures"
+            } else {
+                "Not available on this system"
+            };
+            frame.render_widget(Paragraph::new(text).fg(self.style.temperature_fg).block(block), area);
+            return;
+        }
         let t = Table::new(
             rows,
             [
@@ -385,9 +1318,9 @@ impl App {
         )
         .header(header)
         .fg(self.style.temperature_fg)
-        .block(Block::default().borders(Borders::ALL).title("Temperature"));
+        .block(block);
 
-        frame.render_stateful_widget(t, area, &mut self.state);
+        frame.render_widget(t, area);
     }
     
     fn render_general_info(&mut self, frame: &mut Frame, area: Rect) {
@@ -397,199 +1330,282 @@ impl App {
                 Line::from(info)
             );
         }
+        if !self.pressures.is_empty() {
+            text.push(self.pressure_line());
+        }
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .title("Info")
+            .title(Line::from(self.battery_summary()).right_aligned());
+        if let Some(badge) = self.throttling_badge() {
+            block = block.title(badge);
+        }
         let paragraph = Paragraph::new(text)
             .fg(self.style.info_fg)
-            .block(Block::default().borders(Borders::ALL).title("Info"));
+            .block(block);
 
         frame.render_widget(paragraph, area);
     }
+
+    /// Empty on machines without a battery.
+    fn battery_summary(&self) -> String {
+        self.batteries.iter().map(|battery| {
+            let mut status = format!("{} {:.0}% {}", battery.name, battery.capacity, battery.status);
+            if let Some(secs) = battery.time_remaining {
+                status.push_str(&format!(" {}:{:02} left", secs / 3600, secs % 3600 / 60));
+            }
+            status
+        }).collect::<Vec<String>>().join(" | ")
+    }
+    
+    /// Conditions the firmware slows the CPU down for, in the threshold
+    /// color while they last and as a reminder once they are over. None
+    /// when it never did since boot.
+    fn throttling_badge(&self) -> Option<Line<'static>> {
+        let throttling = self.throttling?;
+        let (active, occurred) = (throttling.active(), throttling.occurred());
+        let mut spans = Vec::new();
+        if !active.is_empty() {
+            spans.push(Span::from(format!("⚠ {}", active.join(", ")))
+                .fg(self.style.exceed_threshold_cell)
+                .bold());
+        }
+        if !occurred.is_empty() {
+            let separator = if spans.is_empty() { "" } else { ", " };
+            spans.push(Span::from(format!("{}{} since boot", separator, occurred.join(", "))).fg(self.style.sensor_high_fg));
+        }
+        match spans.is_empty() {
+            true => None,
+            false => Some(Line::from(spans)),
+        }
+    }
+
+    /// PSI averages over 10s/60s/300s, resources stalled above the threshold
+    /// are highlighted.
+    fn pressure_line(&self) -> Line<'static> {
+        let mut spans = vec![Span::from("PSI 10s/60s/300s:")];
+        for pressure in self.pressures.iter() {
+            let span = Span::from(format!(
+                " {} {:.1}/{:.1}/{:.1}",
+                pressure.resource, pressure.avg10, pressure.avg60, pressure.avg300
+            ));
+            spans.push(if self.pressure_highlight.contains(&pressure.resource) {
+                span.fg(self.style.exceed_threshold_cell)
+            } else {
+                span
+            });
+        }
+        Line::from(spans)
+    }
     
     fn render_processes_table(&mut self, frame: &mut Frame, area: Rect) {
         let selected_row_style = Style::default()
             .add_modifier(Modifier::REVERSED)
             .fg(self.style.selected_row);
-        let header = ["PID", "Name", "User", "CPU %", "Memory %"]
+        // The container column only shows up on hosts running containers
+        let show_container = self.processes.iter().any(|process| process.container.is_some());
+        let show_traffic = self.config.process_network.unwrap();
+        let mut header: Vec<String> = self.columns.iter().map(|shown| self.sorted_column_header(shown.column)).collect();
+        if show_traffic {
+            header.extend(["NET RX".to_string(), "NET TX".to_string()]);
+        }
+        if show_container {
+            header.push("Container".to_string());
+        }
+        let mut widths: Vec<Constraint> = self.columns.iter().map(ColumnConfig::constraint).collect();
+        if show_traffic {
+            widths.extend([Constraint::Length(11), Constraint::Length(11)]);
+        }
+        if show_container {
+            widths.push(Constraint::Length(20));
+        }
+        // Columns that do not fit are scrolled to in wide mode
+        let visible = self.visible_columns(&widths, area.width.saturating_sub(2));
+        let hidden = widths.len() - visible.len();
+        let widths: Vec<Constraint> = visible.iter().map(|index| widths[*index]).collect();
+        let header = visible.iter().map(|index| header[*index].clone()).collect::<Vec<_>>();
+        let header = header
             .into_iter()
             .map(Cell::from)
             .collect::<Row>()
             .height(1);
         
-        let rows = self.processes.iter().enumerate().map(|(idx, process)| {
-            if process.pid as usize == self.selected_pid {
-                self.state.select(Some(idx));
+        if let Some(idx) = self.processes
+            .iter()
+            .position(|process| process.pid as usize == self.selected_pid) {
+            self.state.select(Some(idx));
+        }
+        // Only the rows inside the borders and below the header are built
+        let visible_rows = area.height.saturating_sub(3) as usize;
+        self.page_size = visible_rows.max(1);
+        let selected = self.state.selected().unwrap_or(0);
+        let mut offset = self.state.offset()
+            .min(self.processes.len().saturating_sub(visible_rows));
+        if selected < offset {
+            offset = selected;
+        } else if selected >= offset + visible_rows {
+            offset = selected + 1 - visible_rows;
+        }
+        *self.state.offset_mut() = offset;
+        let mut visible_state = TableState::default().with_selected(selected.checked_sub(offset));
+
+        let rows = self.processes.iter().skip(offset).take(visible_rows).map(|process| {
+            let mut cells: Vec<Cell> = self.columns.iter().map(|shown| self.column_cell(shown.column, process)).collect();
+            if show_traffic {
+                let traffic = self.process_traffic.get(&process.pid).copied().unwrap_or_default();
+                cells.push(Cell::from(units::bytes_per_second(traffic.rx)));
+                cells.push(Cell::from(units::bytes_per_second(traffic.tx)));
+            }
+            if show_container {
+                cells.push(Cell::from(process.container.clone().unwrap_or_default()));
+            }
+            let cells = cells.into_iter().enumerate()
+                .filter(|(index, _)| visible.contains(index))
+                .map(|(_, cell)| cell);
+            let tagged = self.tagged.contains(&process.pid);
+            match (tagged, self.churn_style(process.pid), self.state_color(&process.state)) {
+                (true, _, _) => Row::new(cells).fg(self.style.tagged_row).add_modifier(Modifier::BOLD),
+                // Processes breaching an alert rule
+                _ if self.alerts.breaching(process.pid) => Row::new(cells)
+                    .fg(self.style.bar_text_fg)
+                    .bg(self.style.exceed_threshold_cell),
+                (false, Some(style), _) => Row::new(cells).style(style),
+                (false, None, Some(color)) => Row::new(cells).fg(color),
+                (false, None, None) if process.thread_of.is_some() => Row::new(cells).fg(self.style.thread_fg),
+                (false, None, None) => Row::new(cells),
             }
-            Row::new(vec![
-                Cell::from(process.pid.to_string()),
-                Cell::from(process.process_name.to_string()),
-                Cell::from(process.user.to_string()),
-                Self::blink_cell(
-                    process.cpu_usage, 
-                    self.config.cpu_threshold.unwrap(), 
-                    self.blink_threshold, 
-                    self.style.exceed_threshold_cell
-                ),
-                Self::blink_cell(
-                    process.mem_usage, 
-                    self.config.mem_threshold.unwrap(),
-                    self.blink_threshold, 
-                    self.style.exceed_threshold_cell
-                )
-            ])
         });
-        
-        let t = Table::new(
-            rows,
-            [
-                Constraint::Length(10),
-                Constraint::Min(20),
-                Constraint::Min(15),
-                Constraint::Length(10),
-                Constraint::Length(10),
-            ],
-        )
+        let title = match (self.paused, self.container_filter) {
+            (true, _) => "Processes (paused)",
+            (false, ContainerFilter::All) => "Processes",
+            (false, ContainerFilter::Containers) => "Processes (containers)",
+            (false, ContainerFilter::Host) => "Processes (host)",
+        };
+        let title = match self.process_filter.is_empty() {
+            true => title.to_string(),
+            false => self.process_filter.title(title, ""),
+        };
+        let title = match (self.wide, self.columns_squeezed(&widths, area.width.saturating_sub(2))) {
+            (true, _) => format!("{} (wide, {} columns hidden, h/l to scroll, w to leave)", title, hidden),
+            (false, true) => format!("{} (w to scroll columns)", title),
+            (false, false) => title,
+        };
+        let bottom = match self.process_filter.is_editing() {
+            true => self.process_filter.prompt(),
+            false => self.status.clone().unwrap_or_default(),
+        };
+
+        let t = Table::new(rows, widths)
         .header(header)
         .fg(self.style.table_fg)
         .row_highlight_style(selected_row_style)
         .highlight_spacing(HighlightSpacing::Always)
-        .block(Block::default().borders(Borders::ALL).title("Processes"));
+        .block(Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .title_bottom(Line::from(bottom).right_aligned()));
 
-        frame.render_stateful_widget(t, area, &mut self.state);
-    }
+        frame.render_stateful_widget(t, area, &mut visible_state);
         
-    fn render_widgets(
-        &mut self,
-        frame: &mut Frame, 
-        cpu_area: Rect,
-        ram_area: Rect,
-        net_area: Rect,
-        disk_area: Rect,
-        disk_io_area: Rect,
-    ) {
-        frame.render_widget(
-            Paragraph::new("")
-                .block(Block::new()
-                        .title_alignment(Alignment::Center)
-                        .fg(self.style.cpu_frame_fg)
-                        .borders(Borders::all())), 
-            cpu_area
-        );
-        frame.render_widget(
-            Paragraph::new("")
-                .block(Block::new()
-                        .title_alignment(Alignment::Center)
-                        .fg(self.style.net_frame_fg)
-                        .borders(Borders::all())), 
-            net_area
-        );
-        frame.render_widget(
-            Paragraph::new("")
-                .block(Block::new()
-                        .title_alignment(Alignment::Center)
-                        .fg(self.style.mem_frame_fg)
-                        .borders(Borders::all())), 
-            ram_area
-        );
-        frame.render_widget(
-            Paragraph::new("")
-                .block(Block::new()
-                        .title_alignment(Alignment::Center)
-                        .fg(self.style.disk_frame_fg)
-                        .borders(Borders::all())), 
-            disk_area
-        );
-        frame.render_widget(
-            Paragraph::new("")
-                .block(Block::new()
-                        .title_alignment(Alignment::Center)
-                        .fg(self.style.disk_io_frame_fg)
-                        .borders(Borders::all())), 
-            disk_io_area
-        );
+        if self.processes.len() > visible_rows {
+            let mut scrollbar_state = ScrollbarState::new(self.processes.len().saturating_sub(visible_rows))
+                .position(offset);
+            frame.render_stateful_widget(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                    .begin_symbol(None)
+                    .end_symbol(None)
+                    .style(Style::default().fg(self.style.table_fg)),
+                // Along the right border, next to the rows only
+                Rect { y: area.y + 2, height: visible_rows as u16, ..area },
+                &mut scrollbar_state,
+            );
+        }
     }
-    
-    fn create_layout(frame: &mut Frame) -> (Rect, Rect, Rect, Rect, Rect, Rect, Rect, Rect) {
-        let main_layout = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints(vec![
-                Constraint::Percentage(50),
-                Constraint::Percentage(50),
-            ])
-            .split(frame.area());
-        let left_side = main_layout[0];
-        let right_side = main_layout[1];
-        
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(vec![
-                Constraint::Percentage(20),
-                Constraint::Percentage(60),
-                Constraint::Percentage(20),
-            ])
-            .split(left_side);
-        let info_area = chunks[0];
-        let process_area = chunks[1];
-        let cpu_area = chunks[2];
-        
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(vec![
-                Constraint::Percentage(15),
-                Constraint::Percentage(15),
-                Constraint::Percentage(10),
-                Constraint::Percentage(15),
-                Constraint::Percentage(45),
-            ])
-            .split(right_side);
-        let network_area = chunks[0];
-        let disk_io = chunks[1];
-        let mem_area = chunks[2];
-        let disk_area = chunks[3];
-        let temperature_area = chunks[4];
         
-        return (
-            info_area,
-            process_area,
-            cpu_area, 
-            network_area, 
-            disk_io,
-            mem_area,
-            disk_area, 
-            temperature_area,
-        );
+    /// Areas of the panels in `layout.left` and `layout.right`, top to
+    /// bottom. A column without panels leaves its width to the other one.
+    fn create_layout(&self, area: Rect) -> Vec<(Meter, Rect)> {
+        let layout = &self.config.layout;
+        let left = layout.left.as_ref().unwrap();
+        let right = layout.right.as_ref().unwrap();
+        let left_width = match (left.is_empty(), right.is_empty()) {
+            (false, true) => 100,
+            (true, false) => 0,
+            _ => layout.left_width.unwrap(),
+        };
+        let [left_side, right_side] = Layout::horizontal([
+            Constraint::Percentage(left_width),
+            Constraint::Percentage(100 - left_width),
+        ]).areas(area);
+
+        let mut panels = Vec::new();
+        for (meters, side) in [(left, left_side), (right, right_side)] {
+            let meters: Vec<Meter> = meters.iter()
+                .copied()
+                .filter(Meter::fits_column)
+                .filter(|meter| *meter != Meter::Sensors || self.shows_sensors())
+                .collect();
+            let areas = Layout::vertical(meters.iter().map(|meter| Constraint::Percentage(layout.height(*meter))))
+                .split(side);
+            panels.extend(meters.into_iter().zip(areas.iter().copied()));
+        }
+        panels
     }
     
     fn next_row(&mut self) {
-        let row = match self.state.selected() {
-            Some(row) => {
-                if row >= self.processes.len() - 1 {
-                    self.processes.len() - 1
-                } else {
-                    row + 1
-                }
-            }
-            None => 0,
-        };
-        self.state.select(Some(row));
-        self.update_seleted_process_id(row);
+        self.select_row(self.state.selected().map_or(0, |row| row + 1));
     }
     
     fn previous_row(&mut self) {
-        let row = match self.state.selected() {
-            Some(row) => {
-                if row == 0 {
-                    0
-                } else {
-                    row - 1
-                }
-            }
-            None => 0,
-        };
+        self.select_row(self.state.selected().map_or(0, |row| row.saturating_sub(1)));
+    }
+    
+    fn next_page(&mut self) {
+        self.select_row(self.state.selected().unwrap_or(0) + self.page_size);
+    }
+    
+    fn previous_page(&mut self) {
+        self.select_row(self.state.selected().unwrap_or(0).saturating_sub(self.page_size));
+    }
+    
+    fn next_half_page(&mut self) {
+        self.select_row(self.state.selected().unwrap_or(0) + (self.page_size / 2).max(1));
+    }
+    
+    fn previous_half_page(&mut self) {
+        self.select_row(self.state.selected().unwrap_or(0).saturating_sub((self.page_size / 2).max(1)));
+    }
+    
+    fn select_row(&mut self, row: usize) {
+        if self.processes.is_empty() {
+            return;
+        }
+        let row = row.min(self.processes.len() - 1);
         self.state.select(Some(row));
         self.update_seleted_process_id(row);
     }
+    
     fn update_seleted_process_id(&mut self, row: usize) {
         if let Some(process) = self.processes.get(row) {
             self.selected_pid = process.pid as usize;
         }
     }
-}
\ No newline at end of file
+}
+
+/// CPU numbers as ranges, e.g. `0-3,8-11`.
+fn cpu_ranges(cpus: &[usize]) -> String {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for cpu in cpus {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == *cpu => *end = *cpu,
+            _ => ranges.push((*cpu, *cpu)),
+        }
+    }
+    ranges.iter()
+        .map(|(start, end)| match start == end {
+            true => start.to_string(),
+            false => format!("{}-{}", start, end),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
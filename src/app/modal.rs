@@ -0,0 +1,68 @@
+use crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{app::App, cmd::{control::Signal, service::ServiceAction}};
+
+/// Where key presses go: the current view, or a modal drawn over it.
+#[derive(Default)]
+pub(super) enum InputMode {
+    #[default]
+    Normal,
+    Confirm(Confirmation),
+}
+
+/// Action run once the user answers yes.
+pub(super) enum PendingAction {
+    Signal { pids: Vec<u32>, signal: Signal },
+    Renice { pids: Vec<u32>, delta: i32 },
+    Service { action: ServiceAction, unit: String },
+}
+
+/// A yes/no question, answering anything but y cancels the action.
+pub(super) struct Confirmation {
+    question: String,
+    action: PendingAction,
+}
+
+impl App {
+    pub(super) fn confirm(&mut self, question: String, action: PendingAction) {
+        self.input_mode = InputMode::Confirm(Confirmation { question, action });
+    }
+
+    pub(super) fn handle_confirm_key(&mut self, code: KeyCode) {
+        let InputMode::Confirm(confirmation) = std::mem::take(&mut self.input_mode) else {
+            return;
+        };
+        if !matches!(code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+            self.status = Some("Cancelled".to_string());
+            return;
+        }
+        match confirmation.action {
+            PendingAction::Signal { pids, signal } => self.send_signal(&pids, signal),
+            PendingAction::Renice { pids, delta } => self.renice(&pids, delta),
+            PendingAction::Service { action, unit } => self.run_service_action(action, unit),
+        }
+    }
+
+    pub(super) fn render_confirmation(&self, frame: &mut Frame) {
+        let InputMode::Confirm(confirmation) = &self.input_mode else {
+            return;
+        };
+        let text = Line::from(format!("{} [y/N]", confirmation.question));
+        let width = text.width() as u16 + 4;
+        let [area] = Layout::vertical([Constraint::Length(3)])
+            .flex(layout::Flex::Center)
+            .areas(frame.area());
+        let [area] = Layout::horizontal([Constraint::Length(width.max(24))])
+            .flex(layout::Flex::Center)
+            .areas(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(text)
+                .centered()
+                .fg(self.style.exceed_threshold_cell)
+                .block(Block::default().borders(Borders::ALL).title("Confirm")),
+            area
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use std::{io::Write, time::{Duration, Instant}};
+use ratatui::prelude::*;
+
+use crate::{alert::{AlertEvent, AlertState, Metric}, app::{config::Meter, App}};
+
+impl App {
+    /// How long the border of a panel flashes after an alert about it.
+    const FLASH: Duration = Duration::from_secs(5);
+
+    /// Rings the bell and starts flashing the panels of the alerts just
+    /// raised, as enabled under `alerts`.
+    pub(super) fn notify(&mut self, events: &[AlertEvent]) {
+        let breaches: Vec<&AlertEvent> = events.iter().filter(|event| event.state == AlertState::Breach).collect();
+        if breaches.is_empty() {
+            return;
+        }
+        if self.config.alerts.bell.unwrap() {
+            let mut stdout = std::io::stdout();
+            let _ = stdout.write_all(b"\x07").and_then(|_| stdout.flush());
+        }
+        if self.config.alerts.flash.unwrap() {
+            let now = Instant::now();
+            for event in breaches {
+                self.flashing.insert(panel(event.metric), now);
+            }
+        }
+    }
+
+    /// Switches the border of a flashing panel to the threshold color in
+    /// step with the other blinking cells, keeping its titles.
+    pub(super) fn flash_border(&mut self, frame: &mut Frame, meter: Meter, area: Rect) {
+        self.flashing.retain(|_, since| since.elapsed() < Self::FLASH);
+        if !self.flashing.contains_key(&meter) || !self.blink_threshold || area.width < 2 || area.height < 2 {
+            return;
+        }
+        let style = Style::default().fg(self.style.exceed_threshold_cell).add_modifier(Modifier::BOLD);
+        let buffer = frame.buffer_mut();
+        for x in area.left()..area.right() {
+            buffer[(x, area.top())].set_style(style);
+            buffer[(x, area.bottom() - 1)].set_style(style);
+        }
+        for y in area.top()..area.bottom() {
+            buffer[(area.left(), y)].set_style(style);
+            buffer[(area.right() - 1, y)].set_style(style);
+        }
+    }
+}
+
+/// The panel showing what an alert is about.
+fn panel(metric: Metric) -> Meter {
+    match metric {
+        Metric::Cpu => Meter::Cpu,
+        Metric::Memory => Meter::Memory,
+        Metric::Disk | Metric::DiskFree => Meter::Disks,
+        Metric::Temperature | Metric::TemperatureCritical => Meter::Sensors,
+        Metric::ProcessCpu | Metric::ProcessMemory => Meter::Processes,
+    }
+}
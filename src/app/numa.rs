@@ -0,0 +1,60 @@
+use crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{app::{cpu_ranges, App, View}, cmd::units};
+
+impl App {
+    pub(super) fn handle_numa_key(&mut self, code: KeyCode) {
+        if let KeyCode::Char('q') | KeyCode::Esc | KeyCode::Char('N') = code {
+            self.view = View::Main;
+        }
+    }
+
+    pub(super) fn render_numa(&self, frame: &mut Frame, area: Rect) {
+        let header = ["Node", "CPUs", "Memory used", "Hit", "Miss", "Foreign", "Local", "Other", "Miss %"]
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .height(1);
+        let rows = self.numa_nodes.iter().map(|node| {
+            Row::new(vec![
+                Cell::from(format!("node{}", node.id)),
+                Cell::from(cpu_ranges(&node.cpus)),
+                Cell::from(format!(
+                    "{:.1}% {} of {}",
+                    node.percent_memory_used(),
+                    units::bytes(node.memory_used),
+                    units::bytes(node.memory_total)
+                )),
+                Cell::from(node.numa_hit.to_string()),
+                Cell::from(node.numa_miss.to_string()),
+                Cell::from(node.numa_foreign.to_string()),
+                Cell::from(node.local_node.to_string()),
+                Cell::from(node.other_node.to_string()),
+                Cell::from(format!("{:.2}%", node.percent_miss())),
+            ])
+        });
+        let title = match self.numa_nodes.len() {
+            0 => "NUMA nodes (not available on this system, N to close)",
+            _ => "NUMA nodes (allocation counters since boot, N to close)",
+        };
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(7),
+                Constraint::Min(10),
+                Constraint::Length(28),
+                Constraint::Length(12),
+                Constraint::Length(12),
+                Constraint::Length(12),
+                Constraint::Length(12),
+                Constraint::Length(12),
+                Constraint::Length(8),
+            ],
+        )
+        .header(header)
+        .fg(self.style.mem_frame_fg)
+        .block(Block::default().borders(Borders::ALL).title(title));
+        frame.render_widget(table, area);
+    }
+}
@@ -0,0 +1,145 @@
+use crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{
+    app::{filter::TextFilter, App, View},
+    cmd::open_file::OpenFile,
+};
+
+/// State of the open files view of one process.
+pub(super) struct OpenFiles {
+    pid: u32,
+    process_name: String,
+    files: Result<Vec<OpenFile>, String>,
+    state: TableState,
+    page_size: usize,
+    filter: TextFilter,
+}
+
+impl OpenFiles {
+    fn read(pid: u32) -> Result<Vec<OpenFile>, String> {
+        OpenFile::read_all(pid).map_err(|err| err.to_string())
+    }
+
+    fn filtered(&self) -> Vec<&OpenFile> {
+        let Ok(files) = &self.files else {
+            return Vec::new();
+        };
+        files.iter()
+            .filter(|file| self.filter.matches(file.kind.label()) || self.filter.matches(&file.target))
+            .collect()
+    }
+}
+
+impl OpenFiles {
+    pub(super) fn is_searching(&self) -> bool {
+        self.filter.is_editing()
+    }
+}
+
+impl App {
+    /// Lists the file descriptors of the selected process.
+    pub(super) fn open_files(&mut self) {
+        let Some(process) = self.state.selected().and_then(|row| self.processes.get(row)) else {
+            return;
+        };
+        let files = match self.remote {
+            Some(_) => Err("Not available for a remote agent".to_string()),
+            None => OpenFiles::read(process.pid),
+        };
+        self.open_files = Some(OpenFiles {
+            pid: process.pid,
+            process_name: process.process_name.clone(),
+            files,
+            state: TableState::default().with_selected(0),
+            page_size: 1,
+            filter: TextFilter::default(),
+        });
+        self.view = View::OpenFiles;
+    }
+
+    pub(super) fn handle_open_files_key(&mut self, code: KeyCode) {
+        let Some(open_files) = self.open_files.as_mut() else {
+            self.view = View::Main;
+            return;
+        };
+        if open_files.filter.is_editing() {
+            open_files.filter.handle_key(code);
+            open_files.state.select(Some(0));
+            return;
+        }
+        let last = open_files.filtered().len().saturating_sub(1);
+        let selected = open_files.state.selected().unwrap_or(0);
+        let row = match code {
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Char('l') => {
+                self.open_files = None;
+                self.view = View::Main;
+                return;
+            }
+            KeyCode::Char('/') => {
+                open_files.filter.start();
+                return;
+            }
+            KeyCode::Char('r') if self.remote.is_none() => {
+                open_files.files = OpenFiles::read(open_files.pid);
+                selected.min(open_files.filtered().len().saturating_sub(1))
+            }
+            KeyCode::Char('j') | KeyCode::Down => (selected + 1).min(last),
+            KeyCode::Char('k') | KeyCode::Up => selected.saturating_sub(1),
+            KeyCode::PageDown => (selected + open_files.page_size).min(last),
+            KeyCode::PageUp => selected.saturating_sub(open_files.page_size),
+            KeyCode::Home => 0,
+            KeyCode::End => last,
+            _ => return,
+        };
+        open_files.state.select(Some(row));
+    }
+
+    pub(super) fn render_open_files(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(open_files) = self.open_files.as_mut() else {
+            return;
+        };
+        open_files.page_size = (area.height.saturating_sub(3) as usize).max(1);
+        let files = open_files.filtered();
+        let title = match &open_files.files {
+            Err(err) => format!("Open files of {} ({})", open_files.pid, err),
+            Ok(_) => open_files.filter.title(
+                &format!("Open files of {} {}", open_files.pid, open_files.process_name),
+                "/ to filter, r to reload, l to close",
+            ),
+        };
+        // Position within the list, as the table only shows one page of it
+        let page = match files.len() {
+            0 => String::new(),
+            count => format!(
+                "page {}/{}",
+                open_files.state.selected().unwrap_or(0) / open_files.page_size + 1,
+                count.div_ceil(open_files.page_size),
+            ),
+        };
+        let header = ["FD", "Type", "Target"]
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .height(1);
+        let rows: Vec<Row> = files.iter().map(|file| {
+            Row::new(vec![
+                Cell::from(file.fd.to_string()),
+                Cell::from(file.kind.label()),
+                Cell::from(file.target.clone()),
+            ])
+        }).collect();
+        let table = Table::new(rows, [Constraint::Length(6), Constraint::Length(8), Constraint::Min(0)])
+            .header(header)
+            .fg(self.style.table_fg)
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_spacing(HighlightSpacing::Always)
+            .block(Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .title_bottom(Line::from(open_files.filter.prompt()))
+                    .title_bottom(Line::from(page).right_aligned()));
+
+        frame.render_stateful_widget(table, area, &mut open_files.state);
+    }
+}
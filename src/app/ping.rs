@@ -0,0 +1,70 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{app::App, cmd::ping::PingTarget};
+
+impl App {
+    /// Round trips kept per host, more than the sparklines usually show.
+    const PING_SAMPLES: usize = 200;
+
+    /// Adds the latest round trips, in microseconds, to the history of
+    /// their host and forgets hosts that are no longer probed.
+    pub(super) fn record_ping(&mut self, targets: Vec<PingTarget>) {
+        self.ping_history.retain(|host, _| targets.iter().any(|target| target.host == *host));
+        for target in targets.iter() {
+            let history = self.ping_history.entry(target.host.clone()).or_default();
+            if history.len() == Self::PING_SAMPLES {
+                history.pop_front();
+            }
+            history.push_back(target.rtt.map(|rtt| rtt.as_micros() as u64));
+        }
+        self.ping = targets;
+    }
+
+    /// A row per host with its last round trip and a sparkline of the
+    /// previous ones, lost probes marked in red.
+    pub(super) fn render_ping(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .padding(Padding::horizontal(3))
+            .title(Line::from("Ping").centered());
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        if self.ping.is_empty() {
+            let text = match self.config.ping.hosts.as_ref().unwrap().is_empty() {
+                true => "No hosts, list them under ping.hosts",
+                false => "Probing",
+            };
+            frame.render_widget(Paragraph::new(text).fg(self.style.net_frame_fg), inner);
+            return;
+        }
+
+        let label_width = self.ping.iter().map(|target| target.host.len()).max().unwrap_or_default() + 12;
+        let rows = Layout::vertical(vec![Constraint::Length(1); self.ping.len()]).split(inner);
+        for (target, row) in self.ping.iter().zip(rows.iter()) {
+            let [label_area, sparkline_area] = Layout::horizontal([
+                Constraint::Length(label_width as u16),
+                Constraint::Min(0),
+            ]).areas(*row);
+            let rtt = match target.rtt {
+                Some(rtt) => format!("{:.1} ms", rtt.as_secs_f64() * 1000.0),
+                None => "lost".to_string(),
+            };
+            let label = format!("{} {:>width$}", target.host, rtt, width = label_width - target.host.len() - 2);
+            frame.render_widget(Paragraph::new(label).fg(self.style.net_frame_fg), label_area);
+            // The most recent samples that fit, oldest on the left
+            let data: Vec<Option<u64>> = match self.ping_history.get(&target.host) {
+                Some(history) => history.iter()
+                    .skip(history.len().saturating_sub(sparkline_area.width as usize))
+                    .copied()
+                    .collect(),
+                None => Vec::new(),
+            };
+            let sparkline = Sparkline::default()
+                .data(data)
+                .style(Style::default().fg(self.style.net_frame_fg))
+                .absent_value_symbol("x")
+                .absent_value_style(Style::default().fg(self.style.state_zombie_fg));
+            frame.render_widget(sparkline, sparkline_area);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{
+    app::{modal::PendingAction, App, View},
+    cmd::{service::ServiceAction, units},
+};
+
+impl App {
+    pub(super) fn handle_services_key(&mut self, code: KeyCode) {
+        let count = self.services.len();
+        let selected = self.service_state.selected().unwrap_or(0);
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Char('s') => self.view = View::Main,
+            KeyCode::Char('j') | KeyCode::Down if count > 0 => {
+                self.service_state.select(Some((selected + 1).min(count - 1)));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.service_state.select(Some(selected.saturating_sub(1)));
+            }
+            KeyCode::Char('S') => self.request_service_action(ServiceAction::Start, selected),
+            KeyCode::Char('T') => self.request_service_action(ServiceAction::Stop, selected),
+            KeyCode::Char('R') => self.request_service_action(ServiceAction::Restart, selected),
+            _ => {}
+        }
+    }
+
+    fn request_service_action(&mut self, action: ServiceAction, row: usize) {
+        if self.remote.is_some() {
+            self.status = Some("Services of a remote agent cannot be controlled".to_string());
+            return;
+        }
+        if let Some(service) = self.services.get(row) {
+            let question = format!("systemctl {} {}?", action.verb(), service.name);
+            self.confirm(question, PendingAction::Service { action, unit: service.name.clone() });
+        }
+    }
+
+    /// Runs the confirmed action in the background, its outcome comes back
+    /// through `service_results`.
+    pub(super) fn run_service_action(&mut self, action: ServiceAction, unit: String) {
+        self.status = Some(format!("Running systemctl {} {}", action.verb(), unit));
+        let results = self.service_results.0.clone();
+        self.shutdown.spawn(async move {
+            let status = match action.run(unit.clone()).await {
+                Ok(()) => format!("systemctl {} {} succeeded", action.verb(), unit),
+                Err(err) => format!("systemctl {} {} failed: {}", action.verb(), unit, err),
+            };
+            let _ = results.send(status);
+        });
+    }
+
+    pub(super) fn render_services(&mut self, frame: &mut Frame, area: Rect) {
+        let header = ["Unit", "Active", "Sub", "Main PID", "CPU %", "Memory", "Description"]
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .height(1);
+        let rows = self.services.iter().map(|service| {
+            // CPU usage is only known for the main process, memory covers the whole unit
+            let main_process = service.main_pid
+                .and_then(|pid| self.processes.iter().find(|process| process.pid == pid));
+            let memory = service.memory.or(main_process.map(|process| process.memory));
+            let active = Cell::from(service.active_state.clone()).fg(match service.active_state.as_str() {
+                "active" => self.style.state_running_fg,
+                "failed" => self.style.exceed_threshold_cell,
+                _ => self.style.info_fg,
+            });
+            Row::new(vec![
+                Cell::from(service.name.clone()),
+                active,
+                Cell::from(service.sub_state.clone()),
+                Cell::from(service.main_pid.map(|pid| pid.to_string()).unwrap_or_default()),
+                Cell::from(main_process.map(|process| format!("{:.1}%", process.cpu_usage)).unwrap_or_default()),
+                Cell::from(memory.map(units::bytes).unwrap_or_default()),
+                Cell::from(service.description.clone()),
+            ])
+        });
+        let title = if self.services.is_empty() {
+            "Services (not available on this system, s to close)"
+        } else {
+            "Services (S start, T stop, R restart, s to close)"
+        };
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(24),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(9),
+                Constraint::Length(7),
+                Constraint::Length(10),
+                Constraint::Min(20),
+            ],
+        )
+        .header(header)
+        .fg(self.style.table_fg)
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_spacing(HighlightSpacing::Always)
+        .block(Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .title_bottom(Line::from(self.status.clone().unwrap_or_default()).right_aligned()));
+
+        frame.render_stateful_widget(table, area, &mut self.service_state);
+    }
+}
@@ -0,0 +1,115 @@
+use std::{fs, io, path::PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::app::{config::{ColumnConfig, ProcessColumn}, App, ContainerFilter};
+
+/// How the app looked when it was closed, read back on the next launch.
+/// Every field is optional so that files written by other versions still
+/// restore what they can.
+#[derive(Serialize, Deserialize)]
+pub(super) struct Session {
+    /// What the main view shows besides the process table.
+    #[serde(default)]
+    header_meters: Option<bool>,
+    #[serde(default)]
+    show_interrupts: Option<bool>,
+    #[serde(default)]
+    show_frequency: Option<bool>,
+    #[serde(default)]
+    group_by_cgroup: Option<bool>,
+    #[serde(default)]
+    sort_column: Option<ProcessColumn>,
+    #[serde(default)]
+    sort_descending: Option<bool>,
+    #[serde(default)]
+    tree_view: Option<bool>,
+    #[serde(default)]
+    filter: Option<String>,
+    #[serde(default)]
+    container_filter: Option<ContainerFilter>,
+    #[serde(default)]
+    show_kernel_threads: Option<bool>,
+    #[serde(default)]
+    show_threads: Option<bool>,
+    #[serde(default)]
+    columns: Option<Vec<ColumnConfig>>,
+    #[serde(default)]
+    selected_pid: Option<u32>,
+}
+
+/// `$XDG_STATE_HOME/htop-ui/session.json`, under `~/.local/state` when the
+/// variable is unset, or `%LOCALAPPDATA%\htop-ui\session.json` on Windows.
+fn path() -> Option<PathBuf> {
+    let state_home = match std::env::var_os("XDG_STATE_HOME").filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None if cfg!(windows) => PathBuf::from(std::env::var_os("LOCALAPPDATA")?),
+        None => PathBuf::from(std::env::var_os("HOME")?).join(".local/state"),
+    };
+    Some(state_home.join("htop-ui").join("session.json"))
+}
+
+impl App {
+    pub(super) fn restore_session(&mut self) {
+        if !self.config.restore_session.unwrap() {
+            return;
+        }
+        let Some(path) = path() else { return };
+        let session: Session = match fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str(&content) {
+                Ok(session) => session,
+                Err(err) => {
+                    self.status = Some(format!("Error restoring session from {}: {}", path.display(), err));
+                    return;
+                }
+            },
+            // Nothing was saved yet
+            Err(_) => return,
+        };
+        self.header_meters = session.header_meters.unwrap_or(self.header_meters);
+        self.show_interrupts = session.show_interrupts.unwrap_or(self.show_interrupts);
+        self.show_frequency = session.show_frequency.unwrap_or(self.show_frequency);
+        self.group_by_cgroup = session.group_by_cgroup.unwrap_or(self.group_by_cgroup);
+        if let Some(columns) = session.columns.filter(|columns| !columns.is_empty()) {
+            self.columns = columns;
+        }
+        self.sort_column = session.sort_column.unwrap_or(self.sort_column);
+        self.sort_descending = session.sort_descending.unwrap_or(self.sort_descending);
+        self.tree_view = session.tree_view.unwrap_or(self.tree_view);
+        self.process_filter.set_text(session.filter.unwrap_or_default());
+        self.container_filter = session.container_filter.unwrap_or(self.container_filter);
+        self.show_kernel_threads = session.show_kernel_threads.unwrap_or(self.show_kernel_threads);
+        self.show_threads = session.show_threads.unwrap_or(self.show_threads);
+        if let Some(pid) = session.selected_pid {
+            self.selected_pid = pid as usize;
+        }
+    }
+
+    pub(super) fn save_session(&self) {
+        if !self.config.restore_session.unwrap() {
+            return;
+        }
+        let Some(path) = path() else { return };
+        let session = Session {
+            header_meters: Some(self.header_meters),
+            show_interrupts: Some(self.show_interrupts),
+            show_frequency: Some(self.show_frequency),
+            group_by_cgroup: Some(self.group_by_cgroup),
+            sort_column: Some(self.sort_column),
+            sort_descending: Some(self.sort_descending),
+            tree_view: Some(self.tree_view),
+            filter: Some(self.process_filter.text().to_string()),
+            container_filter: Some(self.container_filter),
+            show_kernel_threads: Some(self.show_kernel_threads),
+            show_threads: Some(self.show_threads),
+            columns: Some(self.columns.clone()),
+            selected_pid: Some(self.selected_pid as u32),
+        };
+        let result = path.parent().map_or(Ok(()), fs::create_dir_all).and_then(|_| {
+            let json = serde_json::to_string_pretty(&session).map_err(io::Error::other)?;
+            fs::write(&path, json)
+        });
+        if let Err(err) = result {
+            log::warn!("Error saving session to {}: {}", path.display(), err);
+        }
+    }
+}
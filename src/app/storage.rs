@@ -0,0 +1,75 @@
+use crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{app::{App, View}, cmd::{disk::Disk, units}};
+
+impl App {
+    pub(super) fn handle_storage_key(&mut self, code: KeyCode) {
+        let count = self.disks_usage.len();
+        let selected = self.storage_state.selected().unwrap_or(0);
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Char('D') => self.view = View::Main,
+            KeyCode::Char('j') | KeyCode::Down if count > 0 => {
+                self.storage_state.select(Some((selected + 1).min(count - 1)));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.storage_state.select(Some(selected.saturating_sub(1)));
+            }
+            _ => {}
+        }
+    }
+
+    /// Every mount with its space and inode usage, one row each so that
+    /// machines with many mounts fit where the disk panel bars don't.
+    pub(super) fn render_storage(&mut self, frame: &mut Frame, area: Rect) {
+        let header = ["Mount point", "Device", "Type", "Size", "Used", "Used %", "Inodes %"]
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .height(1);
+        let mut disks: Vec<&Disk> = self.disks_usage.iter().collect();
+        disks.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+        // Full mounts stand out from the level that raises a disk alert
+        let threshold = self.config.alerts.disk_threshold.unwrap();
+        let rows = disks.into_iter().map(|disk| {
+            let used_space = disk.total_space.saturating_sub(disk.available_space);
+            let percent_used = disk.percent_used_space();
+            let percent_inodes = disk.percent_used_inodes();
+            let style = |percent: u64| match percent as f32 >= threshold {
+                true => Style::default().fg(self.style.exceed_threshold_cell),
+                false => Style::default(),
+            };
+            Row::new(vec![
+                Cell::from(disk.mount_point.clone()),
+                Cell::from(disk.name.clone()),
+                Cell::from(disk.file_system.clone()),
+                Cell::from(units::bytes(disk.total_space)),
+                Cell::from(units::bytes(used_space)),
+                Cell::from(format!("{}%", percent_used)).style(style(percent_used)),
+                match percent_inodes {
+                    Some(percent) => Cell::from(format!("{}%", percent)).style(style(percent)),
+                    None => Cell::from("-"),
+                },
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Fill(2),
+                Constraint::Fill(1),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(8),
+                Constraint::Length(9),
+            ],
+        )
+        .header(header)
+        .fg(self.style.disk_frame_fg)
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_spacing(HighlightSpacing::Always)
+        .block(Block::default().borders(Borders::ALL).title("Storage (D to close)"));
+
+        frame.render_stateful_widget(table, area, &mut self.storage_state);
+    }
+}
@@ -0,0 +1,201 @@
+use ratatui::{style::palette::tailwind, style::Color};
+
+use crate::app::config::Theme;
+
+pub(super) struct AppStyle {
+    pub(super) info_fg: Color,
+    pub(super) table_fg: Color,
+    pub(super) cpu_frame_fg: Color,
+    pub(super) mem_frame_fg: Color,
+    pub(super) disk_frame_fg: Color,
+    pub(super) disk_io_frame_fg: Color,
+    pub(super) temperature_fg: Color,
+    pub(super) gpu_fg: Color,
+    pub(super) mem_buffers_fg: Color,
+    pub(super) mem_shared_fg: Color,
+    pub(super) mem_cached_fg: Color,
+    pub(super) net_frame_fg: Color,
+    pub(super) selected_row: Color,
+    pub(super) tagged_row: Color,
+    /// Userland threads listed under their process.
+    pub(super) thread_fg: Color,
+    /// Processes that just started, drawn with `bar_text_fg` on top.
+    pub(super) new_row_bg: Color,
+    /// Processes that exited, kept for a moment.
+    pub(super) dying_fg: Color,
+    pub(super) state_running_fg: Color,
+    pub(super) state_zombie_fg: Color,
+    pub(super) state_stopped_fg: Color,
+    pub(super) state_disk_sleep_fg: Color,
+    pub(super) exceed_threshold_cell: Color,
+    /// Sensors above `alerts.temperature_threshold`.
+    pub(super) sensor_high_fg: Color,
+    /// Sensors at their critical value.
+    pub(super) sensor_critical_fg: Color,
+    /// Unfilled part of the bars.
+    pub(super) bar_bg: Color,
+    /// Values printed on top of a filled bar.
+    pub(super) bar_text_fg: Color,
+}
+
+impl Theme {
+    pub(super) fn next(&self) -> Theme {
+        match self {
+            Theme::Dark => Theme::Light,
+            Theme::Light => Theme::Solarized,
+            Theme::Solarized => Theme::Monochrome,
+            Theme::Monochrome => Theme::Dark,
+        }
+    }
+}
+
+impl AppStyle {
+    pub(super) fn new(theme: Theme) -> Self {
+        match theme {
+            Theme::Dark => Self::dark(),
+            Theme::Light => Self::light(),
+            Theme::Solarized => Self::solarized(),
+            Theme::Monochrome => Self::monochrome(),
+        }
+    }
+
+    fn dark() -> Self {
+        AppStyle {
+            info_fg: tailwind::TEAL.c300,
+            table_fg: tailwind::LIME.c200,
+            cpu_frame_fg: tailwind::YELLOW.c300,
+            mem_frame_fg: tailwind::PURPLE.c300,
+            disk_frame_fg: tailwind::INDIGO.c300,
+            disk_io_frame_fg: tailwind::CYAN.c300,
+            temperature_fg: tailwind::ROSE.c300,
+            gpu_fg: tailwind::ORANGE.c300,
+            mem_buffers_fg: tailwind::BLUE.c400,
+            mem_shared_fg: tailwind::FUCHSIA.c400,
+            mem_cached_fg: tailwind::AMBER.c400,
+            net_frame_fg: tailwind::GREEN.c300,
+            selected_row: tailwind::ZINC.c100,
+            tagged_row: tailwind::AMBER.c300,
+            thread_fg: tailwind::SKY.c300,
+            new_row_bg: tailwind::GREEN.c400,
+            dying_fg: tailwind::ZINC.c500,
+            state_running_fg: tailwind::GREEN.c500,
+            state_zombie_fg: tailwind::RED.c500,
+            state_stopped_fg: tailwind::YELLOW.c400,
+            state_disk_sleep_fg: tailwind::FUCHSIA.c500,
+            exceed_threshold_cell: tailwind::PINK.c400,
+            sensor_high_fg: tailwind::YELLOW.c300,
+            sensor_critical_fg: tailwind::RED.c500,
+            bar_bg: Color::DarkGray,
+            bar_text_fg: tailwind::BLACK,
+        }
+    }
+
+    /// Darker shades that stay readable on a white background.
+    fn light() -> Self {
+        AppStyle {
+            info_fg: tailwind::TEAL.c800,
+            table_fg: tailwind::ZINC.c800,
+            cpu_frame_fg: tailwind::AMBER.c700,
+            mem_frame_fg: tailwind::PURPLE.c700,
+            disk_frame_fg: tailwind::INDIGO.c700,
+            disk_io_frame_fg: tailwind::CYAN.c800,
+            temperature_fg: tailwind::ROSE.c700,
+            gpu_fg: tailwind::ORANGE.c700,
+            mem_buffers_fg: tailwind::BLUE.c600,
+            mem_shared_fg: tailwind::FUCHSIA.c600,
+            mem_cached_fg: tailwind::AMBER.c500,
+            net_frame_fg: tailwind::GREEN.c700,
+            selected_row: tailwind::ZINC.c900,
+            tagged_row: tailwind::ORANGE.c600,
+            thread_fg: tailwind::SKY.c700,
+            new_row_bg: tailwind::GREEN.c600,
+            dying_fg: tailwind::ZINC.c400,
+            state_running_fg: tailwind::GREEN.c700,
+            state_zombie_fg: tailwind::RED.c700,
+            state_stopped_fg: tailwind::YELLOW.c700,
+            state_disk_sleep_fg: tailwind::FUCHSIA.c700,
+            exceed_threshold_cell: tailwind::PINK.c600,
+            sensor_high_fg: tailwind::YELLOW.c700,
+            sensor_critical_fg: tailwind::RED.c700,
+            bar_bg: tailwind::ZINC.c300,
+            bar_text_fg: tailwind::WHITE,
+        }
+    }
+
+    /// The accent colors of the Solarized palette over its dark base.
+    fn solarized() -> Self {
+        const BASE03: Color = Color::Rgb(0x00, 0x2b, 0x36);
+        const BASE02: Color = Color::Rgb(0x07, 0x36, 0x42);
+        const BASE01: Color = Color::Rgb(0x58, 0x6e, 0x75);
+        const BASE0: Color = Color::Rgb(0x83, 0x94, 0x96);
+        const BASE2: Color = Color::Rgb(0xee, 0xe8, 0xd5);
+        const YELLOW: Color = Color::Rgb(0xb5, 0x89, 0x00);
+        const ORANGE: Color = Color::Rgb(0xcb, 0x4b, 0x16);
+        const RED: Color = Color::Rgb(0xdc, 0x32, 0x2f);
+        const MAGENTA: Color = Color::Rgb(0xd3, 0x36, 0x82);
+        const VIOLET: Color = Color::Rgb(0x6c, 0x71, 0xc4);
+        const BLUE: Color = Color::Rgb(0x26, 0x8b, 0xd2);
+        const CYAN: Color = Color::Rgb(0x2a, 0xa1, 0x98);
+        const GREEN: Color = Color::Rgb(0x85, 0x99, 0x00);
+        AppStyle {
+            info_fg: CYAN,
+            table_fg: BASE0,
+            cpu_frame_fg: YELLOW,
+            mem_frame_fg: VIOLET,
+            disk_frame_fg: BLUE,
+            disk_io_frame_fg: CYAN,
+            temperature_fg: RED,
+            gpu_fg: ORANGE,
+            mem_buffers_fg: BLUE,
+            mem_shared_fg: MAGENTA,
+            mem_cached_fg: YELLOW,
+            net_frame_fg: GREEN,
+            selected_row: BASE2,
+            tagged_row: ORANGE,
+            thread_fg: BLUE,
+            new_row_bg: GREEN,
+            dying_fg: BASE01,
+            state_running_fg: GREEN,
+            state_zombie_fg: RED,
+            state_stopped_fg: YELLOW,
+            state_disk_sleep_fg: MAGENTA,
+            exceed_threshold_cell: MAGENTA,
+            sensor_high_fg: YELLOW,
+            sensor_critical_fg: RED,
+            bar_bg: BASE02,
+            bar_text_fg: BASE03,
+        }
+    }
+
+    /// The terminal's own colors, bars told apart by shades of gray.
+    fn monochrome() -> Self {
+        AppStyle {
+            info_fg: Color::Reset,
+            table_fg: Color::Reset,
+            cpu_frame_fg: Color::Reset,
+            mem_frame_fg: Color::White,
+            disk_frame_fg: Color::Reset,
+            disk_io_frame_fg: Color::Reset,
+            temperature_fg: Color::Reset,
+            gpu_fg: Color::Reset,
+            mem_buffers_fg: Color::Gray,
+            mem_shared_fg: Color::Gray,
+            mem_cached_fg: Color::Gray,
+            net_frame_fg: Color::Reset,
+            selected_row: Color::Reset,
+            tagged_row: Color::White,
+            thread_fg: Color::Gray,
+            new_row_bg: Color::White,
+            dying_fg: Color::DarkGray,
+            state_running_fg: Color::Reset,
+            state_zombie_fg: Color::Reset,
+            state_stopped_fg: Color::Reset,
+            state_disk_sleep_fg: Color::Reset,
+            exceed_threshold_cell: Color::Reset,
+            sensor_high_fg: Color::Reset,
+            sensor_critical_fg: Color::Reset,
+            bar_bg: Color::DarkGray,
+            bar_text_fg: Color::Black,
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{app::App, cmd::process::Process};
+
+impl App {
+    pub(super) fn toggle_tree_view(&mut self) {
+        self.tree_view = !self.tree_view;
+        self.tree_prefixes.clear();
+        self.update_processes(self.subscriber.snapshot().processes);
+    }
+
+    /// Hides or shows the children of the selected process in the tree view.
+    pub(super) fn collapse_subtree(&mut self, collapse: bool) {
+        if !self.tree_view {
+            self.status = Some("Subtrees collapse in the tree view, F5 to switch".to_string());
+            return;
+        }
+        let Some(pid) = self.state.selected().and_then(|row| self.processes.get(row)).map(|process| process.pid) else {
+            return;
+        };
+        let changed = match collapse {
+            true => self.collapsed.insert(pid),
+            false => self.collapsed.remove(&pid),
+        };
+        // Hidden children are back in the list only after a fresh update
+        if changed {
+            self.update_processes(self.subscriber.snapshot().processes);
+        }
+    }
+
+    /// CPU and memory shown for a process, which cover its hidden subtree
+    /// when it is collapsed.
+    pub(super) fn subtree_total(&self, process: &Process) -> (f32, f32) {
+        match self.subtree_totals.get(&process.pid) {
+            Some(total) if self.tree_view => *total,
+            _ => (process.cpu_usage, process.mem_usage),
+        }
+    }
+
+    /// Moves every process below its parent, siblings keeping the current
+    /// sort order. Processes whose parent is not listed become roots and
+    /// the descendants of collapsed processes are left out.
+    pub(super) fn arrange_tree(&mut self) {
+        let pids: HashSet<u32> = self.processes.iter().map(|process| process.pid).collect();
+        self.collapsed.retain(|pid| pids.contains(pid));
+        let mut children: HashMap<u32, Vec<usize>> = HashMap::new();
+        let mut roots = Vec::new();
+        for (index, process) in self.processes.iter().enumerate() {
+            match process.parent.filter(|parent| *parent != process.pid && pids.contains(parent)) {
+                Some(parent) => children.entry(parent).or_default().push(index),
+                None => roots.push(index),
+            }
+        }
+
+        let mut order = Vec::with_capacity(self.processes.len());
+        self.tree_prefixes.clear();
+        self.subtree_totals.clear();
+        // Depth first, each entry carries the indentation of its children
+        let mut stack: Vec<(usize, String)> = roots.into_iter().rev().map(|root| (root, String::new())).collect();
+        while let Some((index, indent)) = stack.pop() {
+            order.push(index);
+            let pid = self.processes[index].pid;
+            let Some(kids) = children.get(&pid) else {
+                continue;
+            };
+            if self.collapsed.contains(&pid) {
+                self.tree_prefixes.entry(pid).or_default().push_str("+ ");
+                self.subtree_totals.insert(pid, self.sum_subtree(index, &children));
+                continue;
+            }
+            for (position, kid) in kids.iter().enumerate().rev() {
+                let last = position == kids.len() - 1;
+                let branch = if last { "└─ " } else { "├─ " };
+                self.tree_prefixes.insert(self.processes[*kid].pid, format!("{}{}", indent, branch));
+                let child_indent = format!("{}{}", indent, if last { "   " } else { "│  " });
+                stack.push((*kid, child_indent));
+            }
+        }
+
+        let mut slots: Vec<_> = std::mem::take(&mut self.processes).into_iter().map(Some).collect();
+        self.processes = order.into_iter().filter_map(|index| slots[index].take()).collect();
+    }
+
+    fn sum_subtree(&self, index: usize, children: &HashMap<u32, Vec<usize>>) -> (f32, f32) {
+        let (mut cpu, mut mem) = (0.0, 0.0);
+        let mut pending = vec![index];
+        while let Some(index) = pending.pop() {
+            let process = &self.processes[index];
+            cpu += process.cpu_usage;
+            mem += process.mem_usage;
+            pending.extend(children.get(&process.pid).into_iter().flatten());
+        }
+        (cpu, mem)
+    }
+}
@@ -0,0 +1,48 @@
+use std::net::IpAddr;
+use clap::Parser;
+
+#[derive(Debug, Parser)]
+#[command(version, about = "A terminal system monitor")]
+pub struct Args {
+    /// Path to the YAML config file
+    #[arg(long, default_value = "./config_example.yaml")]
+    pub config: String,
+    /// Validate the config file, report problems and exit
+    #[arg(long)]
+    pub check_config: bool,
+    /// Append a CSV row of metrics to this file on every refresh
+    #[arg(long, value_name = "PATH")]
+    pub log_metrics: Option<String>,
+    /// Serve Prometheus metrics on this port under /metrics
+    #[arg(long, value_name = "PORT")]
+    pub prometheus_port: Option<u16>,
+    /// Address the Prometheus exporter listens on, e.g. 0.0.0.0 for every
+    /// interface
+    #[arg(long, value_name = "IP", default_value = "127.0.0.1", requires = "prometheus_port")]
+    pub prometheus_addr: IpAddr,
+    /// Serve the latest readings as JSON on this address, e.g. under /processes,
+    /// and stream updates over a WebSocket under /stream
+    #[arg(long, value_name = "ADDR")]
+    pub serve: Option<String>,
+    /// Append warnings and errors to this file. Without it they go to stderr
+    /// with --agent and are dropped in the UI, which draws on the terminal
+    #[arg(long, value_name = "PATH")]
+    pub log_file: Option<String>,
+    /// Run headless and serve collector data to remote clients on this address,
+    /// or write it to stdout with -
+    #[arg(long, value_name = "ADDR", conflicts_with_all = ["remote", "ssh"])]
+    pub agent: Option<String>,
+    /// Show data from a remote agent instead of this machine
+    #[arg(long, value_name = "HOST:PORT", conflicts_with = "ssh")]
+    pub remote: Option<String>,
+    /// Show data from a host reached over SSH, which runs --ssh-command
+    #[arg(long, value_name = "USER@HOST")]
+    pub ssh: Option<String>,
+    /// Command run on the SSH host, an agent writing to stdout
+    #[arg(long, value_name = "COMMAND", default_value = "htop-ui --agent -", requires = "ssh")]
+    pub ssh_command: String,
+    /// Record metrics to this SQLite database and enable the history view
+    #[cfg(feature = "history")]
+    #[arg(long, value_name = "PATH")]
+    pub history_db: Option<String>,
+}
@@ -0,0 +1,66 @@
+//! CPU affinity of a process through `sched_getaffinity(2)` and
+//! `sched_setaffinity(2)`, on Linux and FreeBSD.
+use std::io;
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+use std::mem;
+
+#[cfg(target_os = "linux")]
+type CpuSet = libc::cpu_set_t;
+#[cfg(target_os = "freebsd")]
+type CpuSet = libc::cpuset_t;
+
+/// Cores the kernel knows about, including offline ones.
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+pub fn core_count() -> usize {
+    // SAFETY: sysconf has no memory safety requirements
+    let count = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_CONF) };
+    (count.max(1) as usize).min(libc::CPU_SETSIZE as usize)
+}
+
+/// Whether the process may run on each core.
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+pub fn get(pid: u32) -> io::Result<Vec<bool>> {
+    // SAFETY: the set is a plain bit set and the kernel writes at most
+    // size_of::<CpuSet>() bytes into it
+    unsafe {
+        let mut set: CpuSet = mem::zeroed();
+        if libc::sched_getaffinity(pid as libc::pid_t, mem::size_of::<CpuSet>(), &mut set) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok((0..core_count()).map(|core| libc::CPU_ISSET(core, &set)).collect())
+    }
+}
+
+/// Pins the process to the enabled cores. Changing a process of another
+/// user requires `CAP_SYS_NICE` on Linux and root on FreeBSD.
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+pub fn set(pid: u32, cores: &[bool]) -> io::Result<()> {
+    // SAFETY: the set is initialized before it is passed to the kernel and
+    // core indexes stay below CPU_SETSIZE
+    unsafe {
+        let mut set: CpuSet = mem::zeroed();
+        libc::CPU_ZERO(&mut set);
+        for (core, _) in cores.iter().enumerate().filter(|(_, enabled)| **enabled) {
+            libc::CPU_SET(core, &mut set);
+        }
+        if libc::sched_setaffinity(pid as libc::pid_t, mem::size_of::<CpuSet>(), &set) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "freebsd")))]
+pub fn core_count() -> usize {
+    std::thread::available_parallelism().map(usize::from).unwrap_or(1)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "freebsd")))]
+pub fn get(_pid: u32) -> io::Result<Vec<bool>> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "CPU affinity is only supported on Linux and FreeBSD"))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "freebsd")))]
+pub fn set(_pid: u32, _cores: &[bool]) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "CPU affinity is only supported on Linux and FreeBSD"))
+}
@@ -0,0 +1,82 @@
+use std::time::Duration;
+use sysinfo::System;
+use tokio::time::{Instant, Interval, MissedTickBehavior};
+
+/// Stretches refresh intervals while the machine is overloaded, that is
+/// while the 1-minute load average is above the number of cores, so the
+/// monitor doesn't add to the problem.
+#[derive(Debug, Clone, Copy)]
+pub struct Backoff {
+    enabled: bool,
+    min: Duration,
+    max: Duration,
+}
+
+impl Backoff {
+    pub fn new(enabled: bool, min: Duration, max: Duration) -> Self {
+        Self {
+            enabled,
+            min,
+            max: max.max(min),
+        }
+    }
+
+    pub fn interval(&self, base: Duration, cores: usize) -> Duration {
+        if !self.enabled || cores == 0 {
+            return base;
+        }
+        self.stretch(base, System::load_average().one / cores as f64)
+    }
+
+    /// `base` scaled by the load per core. The bounds only apply to a
+    /// stretched interval, and never make it shorter than `base`.
+    fn stretch(&self, base: Duration, load: f64) -> Duration {
+        if load <= 1.0 {
+            return base;
+        }
+        base.mul_f64(load).clamp(base.max(self.min), base.max(self.max))
+    }
+}
+
+/// Restarts `tick` with a new period, keeping it untouched when unchanged.
+pub fn adjust(tick: &mut Interval, period: Duration) {
+    if tick.period() != period {
+        *tick = tokio::time::interval_at(Instant::now() + period, period);
+        tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIN: Duration = Duration::from_millis(100);
+    const MAX: Duration = Duration::from_secs(10);
+
+    #[test]
+    fn unloaded_intervals_are_kept() {
+        let backoff = Backoff::new(true, MIN, MAX);
+        assert_eq!(backoff.stretch(Duration::from_secs(30), 0.5), Duration::from_secs(30));
+        assert_eq!(backoff.stretch(Duration::from_millis(50), 1.0), Duration::from_millis(50));
+    }
+
+    #[test]
+    fn stretched_by_the_load() {
+        let backoff = Backoff::new(true, MIN, MAX);
+        assert_eq!(backoff.stretch(Duration::from_secs(1), 2.0), Duration::from_secs(2));
+        assert_eq!(backoff.stretch(Duration::from_secs(1), 50.0), MAX);
+        assert_eq!(backoff.stretch(Duration::from_millis(20), 2.0), MIN);
+    }
+
+    #[test]
+    fn longer_than_the_bounds_is_never_shortened() {
+        let backoff = Backoff::new(true, MIN, MAX);
+        assert_eq!(backoff.stretch(Duration::from_secs(30), 4.0), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn disabled_keeps_the_base() {
+        let backoff = Backoff::new(false, MIN, MAX);
+        assert_eq!(backoff.interval(Duration::from_secs(30), 1), Duration::from_secs(30));
+    }
+}
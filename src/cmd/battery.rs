@@ -0,0 +1,69 @@
+use std::{fs, path::Path};
+use serde::{Deserialize, Serialize};
+
+const POWER_SUPPLY: &str = "/sys/class/power_supply";
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Battery {
+    pub name: String,
+    pub capacity: f32,
+    pub status: String,
+    /// Seconds until empty while discharging, or until full while charging.
+    pub time_remaining: Option<u64>,
+}
+
+impl Battery {
+    /// Reads every battery under `/sys/class/power_supply`. Returns an
+    /// empty list on machines without one.
+    pub fn read_all() -> Vec<Battery> {
+        let Ok(supplies) = fs::read_dir(POWER_SUPPLY) else {
+            return Vec::new();
+        };
+        let mut batteries: Vec<Battery> = supplies
+            .flatten()
+            .filter(|supply| read_string(&supply.path().join("type")).as_deref() == Some("Battery"))
+            .filter_map(|supply| Self::read(&supply.path()))
+            .collect();
+        batteries.sort_by(|a, b| a.name.cmp(&b.name));
+        batteries
+    }
+
+    fn read(path: &Path) -> Option<Battery> {
+        let name = path.file_name()?.to_string_lossy().into_owned();
+        let capacity = read_number(&path.join("capacity"))? as f32;
+        let status = read_string(&path.join("status")).unwrap_or_else(|| "Unknown".to_string());
+        // Batteries report either energy (µWh, µW) or charge (µAh, µA)
+        let (now, full, rate) = match read_number(&path.join("energy_now")) {
+            Some(energy_now) => (
+                energy_now,
+                read_number(&path.join("energy_full")),
+                read_number(&path.join("power_now")),
+            ),
+            None => (
+                read_number(&path.join("charge_now"))?,
+                read_number(&path.join("charge_full")),
+                read_number(&path.join("current_now")),
+            ),
+        };
+        let time_remaining = match (status.as_str(), full, rate) {
+            (_, _, None | Some(0)) => None,
+            ("Discharging", _, Some(rate)) => Some(now * 3600 / rate),
+            ("Charging", Some(full), Some(rate)) => Some(full.saturating_sub(now) * 3600 / rate),
+            _ => None,
+        };
+        Some(Battery {
+            name,
+            capacity,
+            status,
+            time_remaining,
+        })
+    }
+}
+
+fn read_string(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|value| value.trim().to_string())
+}
+
+fn read_number(path: &Path) -> Option<u64> {
+    read_string(path)?.parse().ok()
+}
@@ -0,0 +1,208 @@
+use std::{
+    collections::HashMap,
+    fs,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr},
+};
+use serde::{Deserialize, Serialize};
+
+/// Socket tables under `/proc/net` and the protocol they list.
+const TABLES: [(&str, &str); 4] = [
+    ("tcp", "/proc/net/tcp"),
+    ("tcp6", "/proc/net/tcp6"),
+    ("udp", "/proc/net/udp"),
+    ("udp6", "/proc/net/udp6"),
+];
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Connection {
+    pub protocol: String,
+    pub local: String,
+    pub remote: String,
+    pub state: String,
+    pub pid: Option<u32>,
+    pub process_name: String,
+    #[serde(default)]
+    pub inode: u64,
+    /// Bytes per second received and sent, only known for TCP.
+    #[serde(default)]
+    pub rates: Option<(f64, f64)>,
+}
+
+impl Connection {
+    /// Reads every TCP and UDP socket and matches it to the process holding
+    /// it. Sockets of processes we may not inspect have no PID.
+    pub fn read_all() -> Vec<Connection> {
+        let owners = socket_owners();
+        let mut connections = Vec::new();
+        for (protocol, path) in TABLES {
+            let Ok(table) = fs::read_to_string(path) else {
+                continue;
+            };
+            connections.extend(Self::parse_table(protocol, &table, &owners));
+        }
+        connections
+    }
+
+    /// Sockets of one table, skipping its header line.
+    fn parse_table(protocol: &str, table: &str, owners: &HashMap<u64, (u32, String)>) -> Vec<Connection> {
+        let mut connections = Vec::new();
+        for line in table.lines().skip(1) {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 {
+                continue;
+            }
+            let (Some(local), Some(remote)) = (parse_address(fields[1]), parse_address(fields[2])) else {
+                continue;
+            };
+            let owner = fields[9].parse::<u64>().ok().and_then(|inode| owners.get(&inode));
+            connections.push(Connection {
+                protocol: protocol.to_string(),
+                local: local.to_string(),
+                remote: remote.to_string(),
+                state: tcp_state(protocol, fields[3]).to_string(),
+                pid: owner.map(|(pid, _)| *pid),
+                process_name: owner.map(|(_, name)| name.clone()).unwrap_or_default(),
+                inode: fields[9].parse().unwrap_or_default(),
+                rates: None,
+            });
+        }
+        connections
+    }
+
+    /// Bytes per second received plus sent, zero when unknown.
+    pub fn total_rate(&self) -> f64 {
+        self.rates.map(|(rx, tx)| rx + tx).unwrap_or_default()
+    }
+}
+
+/// Maps every socket inode to the PID and name of the process holding it.
+pub fn socket_owners() -> HashMap<u64, (u32, String)> {
+    let mut owners = HashMap::new();
+    let Ok(processes) = fs::read_dir("/proc") else {
+        return owners;
+    };
+    for process in processes.flatten() {
+        let Ok(pid) = process.file_name().to_string_lossy().parse::<u32>() else {
+            continue;
+        };
+        let Ok(fds) = fs::read_dir(process.path().join("fd")) else {
+            continue;
+        };
+        let name = fs::read_to_string(process.path().join("comm"))
+            .map(|name| name.trim().to_string())
+            .unwrap_or_default();
+        for fd in fds.flatten() {
+            let Ok(target) = fs::read_link(fd.path()) else {
+                continue;
+            };
+            let target = target.to_string_lossy();
+            if let Some(inode) = target
+                .strip_prefix("socket:[")
+                .and_then(|inode| inode.strip_suffix(']'))
+                .and_then(|inode| inode.parse().ok()) {
+                owners.insert(inode, (pid, name.clone()));
+            }
+        }
+    }
+    owners
+}
+
+/// Parses `0100007F:0035` style addresses. The IP is hex in host byte
+/// order, in 32-bit words for IPv6.
+fn parse_address(address: &str) -> Option<SocketAddr> {
+    let (ip, port) = address.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    let ip = match ip.len() {
+        8 => Ipv4Addr::from(u32::from_str_radix(ip, 16).ok()?.swap_bytes()).into(),
+        32 => {
+            let mut octets = [0u8; 16];
+            for (idx, word) in octets.chunks_mut(4).enumerate() {
+                let value = u32::from_str_radix(&ip[idx * 8..idx * 8 + 8], 16).ok()?;
+                word.copy_from_slice(&value.swap_bytes().to_be_bytes());
+            }
+            Ipv6Addr::from(octets).into()
+        }
+        _ => return None,
+    };
+    Some(SocketAddr::new(ip, port))
+}
+
+fn tcp_state(protocol: &str, state: &str) -> &'static str {
+    if protocol.starts_with("udp") {
+        return if state == "01" { "ESTABLISHED" } else { "-" };
+    }
+    match state {
+        "01" => "ESTABLISHED",
+        "02" => "SYN_SENT",
+        "03" => "SYN_RECV",
+        "04" => "FIN_WAIT1",
+        "05" => "FIN_WAIT2",
+        "06" => "TIME_WAIT",
+        "07" => "CLOSE",
+        "08" => "CLOSE_WAIT",
+        "09" => "LAST_ACK",
+        "0A" => "LISTEN",
+        "0B" => "CLOSING",
+        _ => "UNKNOWN",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TCP: &str = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 23456 1 0000000000000000 100 0 0 10 0
+   1: 0F02000A:A2C4 5DB8D822:01BB 01 00000000:00000000 02:000A3F1D 00000000  1000        0 98765 2 0000000000000000 20 4 30 10 -1
+";
+    const TCP6: &str = "\
+  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000000000000000000001000000:0016 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 34567 1 0000000000000000 100 0 0 10 0
+   1: 0000000000000000FFFF00000100007F:0050 0000000000000000FFFF00000F02000A:B00C 06 00000000:00000000 03:00000F9B 00000000     0        0 0 3 0000000000000000
+";
+    const UDP: &str = "\
+   sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+  512: 00000000:14E9 00000000:0000 07 00000000:00000000 00:00000000 00000000   104        0 45678 2 0000000000000000 0
+";
+
+    #[test]
+    fn tcp_sockets_with_their_owner() {
+        let owners = HashMap::from([(23456, (812, "cupsd".to_string()))]);
+        let connections = Connection::parse_table("tcp", TCP, &owners);
+        assert_eq!(connections.len(), 2);
+        let listening = &connections[0];
+        assert_eq!((listening.local.as_str(), listening.remote.as_str()), ("127.0.0.1:631", "0.0.0.0:0"));
+        assert_eq!(listening.state, "LISTEN");
+        assert_eq!((listening.pid, listening.process_name.as_str(), listening.inode), (Some(812), "cupsd", 23456));
+        let established = &connections[1];
+        assert_eq!((established.local.as_str(), established.remote.as_str()), ("10.0.2.15:41668", "34.216.184.93:443"));
+        assert_eq!(established.state, "ESTABLISHED");
+        assert_eq!((established.pid, established.process_name.as_str()), (None, ""));
+    }
+
+    #[test]
+    fn ipv6_addresses_are_in_32_bit_words() {
+        let connections = Connection::parse_table("tcp6", TCP6, &HashMap::new());
+        assert_eq!(connections[0].local, "[::1]:22");
+        assert_eq!(connections[1].local, "[::ffff:127.0.0.1]:80");
+        assert_eq!(connections[1].remote, "[::ffff:10.0.2.15]:45068");
+        assert_eq!(connections[1].state, "TIME_WAIT");
+    }
+
+    #[test]
+    fn udp_has_no_states_but_connected() {
+        let connections = Connection::parse_table("udp", UDP, &HashMap::new());
+        assert_eq!(connections[0].local, "0.0.0.0:5353");
+        assert_eq!(connections[0].state, "-");
+        assert_eq!(tcp_state("udp6", "01"), "ESTABLISHED");
+    }
+
+    #[test]
+    fn malformed_lines_are_skipped() {
+        let table = "header\n   0: 0100007F 00000000:0000 0A 0 0 0 0 0 0\n   1: 0100007F:0277 00000000:0000 0A\n";
+        assert!(Connection::parse_table("tcp", table, &HashMap::new()).is_empty());
+        assert_eq!(parse_address("0100007F:ZZZZ"), None);
+        assert_eq!(parse_address("7F:0050"), None);
+    }
+}
@@ -0,0 +1,45 @@
+use std::fs;
+
+/// Runtimes recognised in cgroup paths, as `(marker, runtime)`. The marker
+/// is followed by the container ID.
+const RUNTIMES: [(&str, &str); 6] = [
+    ("docker-", "docker"),
+    ("/docker/", "docker"),
+    ("libpod-", "podman"),
+    ("/libpod/", "podman"),
+    ("cri-containerd-", "containerd"),
+    ("crio-", "cri-o"),
+];
+
+/// Length container IDs are shortened to, like `docker ps` does.
+const SHORT_ID: usize = 12;
+
+/// Returns the cgroup of a process, preferring the unified hierarchy and
+/// then the systemd one on cgroup v1 hosts.
+pub fn cgroup_of(pid: u32) -> Option<String> {
+    let content = fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?;
+    // hierarchy-ID:controllers:path
+    let paths: Vec<(&str, &str)> = content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, ':');
+            let _ = fields.next()?;
+            Some((fields.next()?, fields.next()?))
+        })
+        .collect();
+    paths.iter()
+        .find(|(controllers, path)| controllers.is_empty() && *path != "/")
+        .or_else(|| paths.iter().find(|(controllers, _)| *controllers == "name=systemd"))
+        .or_else(|| paths.first())
+        .map(|(_, path)| path.to_string())
+}
+
+/// Returns `runtime:short-id` when the cgroup belongs to a container.
+pub fn container_of(path: &str) -> Option<String> {
+    RUNTIMES.iter().find_map(|(marker, runtime)| {
+        let (_, rest) = path.split_once(marker)?;
+        let id: String = rest.chars().take_while(|c| c.is_ascii_hexdigit()).collect();
+        // Full IDs are 64 hex characters, shorter matches are unrelated names
+        (id.len() == 64).then(|| format!("{}:{}", runtime, &id[..SHORT_ID]))
+    })
+}
@@ -0,0 +1,91 @@
+//! Signals and scheduling priority of other processes. Windows has neither,
+//! there both signals terminate the process and priorities are not shown.
+use std::io;
+#[cfg(target_os = "linux")]
+use libc::__errno_location as errno;
+#[cfg(any(target_os = "macos", target_os = "freebsd"))]
+use libc::__error as errno;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Signal {
+    Term,
+    Kill,
+}
+
+impl Signal {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Signal::Term => "SIGTERM",
+            Signal::Kill => "SIGKILL",
+        }
+    }
+
+    #[cfg(unix)]
+    fn number(&self) -> libc::c_int {
+        match self {
+            Signal::Term => libc::SIGTERM,
+            Signal::Kill => libc::SIGKILL,
+        }
+    }
+}
+
+#[cfg(unix)]
+pub fn send_signal(pid: u32, signal: Signal) -> io::Result<()> {
+    // SAFETY: kill has no memory safety requirements
+    if unsafe { libc::kill(pid as libc::pid_t, signal.number()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+pub fn nice(pid: u32) -> io::Result<i32> {
+    // SAFETY: getpriority has no memory safety requirements. -1 is a valid
+    // priority, so errno tells errors apart.
+    unsafe {
+        *errno() = 0;
+        // The PID is an id_t, or a c_int on FreeBSD
+        let niceness = libc::getpriority(libc::PRIO_PROCESS, pid as _);
+        if niceness == -1 && *errno() != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(niceness)
+    }
+}
+
+/// Moves the nice value of a process by `delta` within -20..=19 and returns
+/// the new value. Lowering it requires `CAP_SYS_NICE`.
+#[cfg(unix)]
+pub fn renice(pid: u32, delta: i32) -> io::Result<i32> {
+    let niceness = (nice(pid)? + delta).clamp(-20, 19);
+    // SAFETY: setpriority has no memory safety requirements
+    if unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as _, niceness) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(niceness)
+}
+
+/// `TerminateProcess`, which can't be caught like `SIGTERM`.
+#[cfg(windows)]
+pub fn send_signal(pid: u32, _signal: Signal) -> io::Result<()> {
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+    let pid = Pid::from_u32(pid);
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(ProcessesToUpdate::Some(&[pid]), false, ProcessRefreshKind::nothing());
+    match sys.process(pid) {
+        Some(process) if process.kill() => Ok(()),
+        Some(_) => Err(io::Error::last_os_error()),
+        None => Err(io::Error::new(io::ErrorKind::NotFound, "no such process")),
+    }
+}
+
+#[cfg(windows)]
+pub fn nice(_pid: u32) -> io::Result<i32> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "nice values don't exist on Windows"))
+}
+
+#[cfg(windows)]
+pub fn renice(pid: u32, _delta: i32) -> io::Result<i32> {
+    nice(pid)
+}
@@ -1,21 +1,211 @@
-#[derive (Default)]
+use std::{collections::HashMap, fs, path::Path, time::Duration};
+use serde::{Deserialize, Serialize};
+
+#[derive (Default, Clone, Serialize, Deserialize)]
 pub struct Disk {
     pub name: String,
     pub total_space: u64,
     pub available_space: u64,
+    #[serde(default)]
+    pub file_system: String,
+    #[serde(default)]
+    pub mount_point: String,
+    /// Zero on file systems without a fixed inode table, such as btrfs.
+    #[serde(default)]
+    pub inodes_total: u64,
+    #[serde(default)]
+    pub inodes_free: u64,
 }
 
 impl Disk {
-    pub fn new(name: String, total_space: u64, available_space: u64) -> Self {
+    pub fn new(name: String, total_space: u64, available_space: u64, file_system: String) -> Self {
         Self {
             name,
             total_space,
             available_space,
+            file_system,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_mount_point(mut self, mount_point: &Path) -> Self {
+        self.mount_point = mount_point.to_string_lossy().into_owned();
+        if let Some((total, free)) = inodes(mount_point) {
+            self.inodes_total = total;
+            self.inodes_free = free;
         }
+        self
     }
     
     pub fn percent_used_space(&self) -> u64 {
-        let used_space = self.total_space - self.available_space;
-        return used_space * 100 / self.total_space;
+        if self.total_space == 0 {
+            return 0;
+        }
+        let used_space = self.total_space.saturating_sub(self.available_space);
+        used_space * 100 / self.total_space
+    }
+
+    /// `None` when the file system doesn't count inodes.
+    pub fn percent_used_inodes(&self) -> Option<u64> {
+        if self.inodes_total == 0 {
+            return None;
+        }
+        let used_inodes = self.inodes_total.saturating_sub(self.inodes_free);
+        Some(used_inodes * 100 / self.inodes_total)
+    }
+}
+
+/// Total and free inodes of the file system mounted at `mount_point`,
+/// through `statvfs(3)`.
+#[cfg(unix)]
+fn inodes(mount_point: &Path) -> Option<(u64, u64)> {
+    use std::{ffi::CString, mem, os::unix::ffi::OsStrExt};
+
+    let path = CString::new(mount_point.as_os_str().as_bytes()).ok()?;
+    // SAFETY: the path is NUL terminated and statvfs only writes into the
+    // zeroed struct it is given
+    unsafe {
+        let mut stat: libc::statvfs = mem::zeroed();
+        if libc::statvfs(path.as_ptr(), &mut stat) != 0 {
+            return None;
+        }
+        Some((stat.f_files as u64, stat.f_ffree as u64))
+    }
+}
+
+/// NTFS has no fixed inode table.
+#[cfg(not(unix))]
+fn inodes(_mount_point: &Path) -> Option<(u64, u64)> {
+    None
+}
+
+/// Read and write throughput of all processes together in bytes per second,
+/// for when the block devices themselves can't be measured.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct TotalIo {
+    pub read: f64,
+    pub write: f64,
+}
+
+/// Read and write throughput of a block device in bytes per second.
+#[derive(Default, Clone, Serialize, Deserialize)]
+pub struct DeviceIo {
+    pub name: String,
+    pub read: f64,
+    pub write: f64,
+}
+
+/// Turns the sector counters of `/proc/diskstats` into rates per device.
+#[derive(Default)]
+pub struct DiskStatsMeter {
+    previous: HashMap<String, (u64, u64)>,
+}
+
+impl DiskStatsMeter {
+    /// `/proc/diskstats` counts in 512 byte sectors whatever the device uses.
+    const SECTOR_SIZE: u64 = 512;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether there are counters to read, which some containers hide.
+    pub fn available() -> bool {
+        Path::new("/proc/diskstats").exists()
+    }
+
+    /// Rates of every whole device over the last `elapsed`, leaving out
+    /// partitions and devices that were never used. The first call only
+    /// records the counters and returns nothing.
+    pub fn measure(&mut self, elapsed: Duration) -> Vec<DeviceIo> {
+        self.update(read_diskstats(), elapsed)
+    }
+
+    fn update(&mut self, counters: HashMap<String, (u64, u64)>, elapsed: Duration) -> Vec<DeviceIo> {
+        let seconds = elapsed.as_secs_f64().max(f64::EPSILON);
+        let mut devices: Vec<DeviceIo> = counters.iter()
+            .filter(|(_, (read, written))| read + written > 0)
+            .filter_map(|(name, (read, written))| {
+                let (previous_read, previous_written) = self.previous.get(name)?;
+                Some(DeviceIo {
+                    name: name.clone(),
+                    read: (read.saturating_sub(*previous_read) * Self::SECTOR_SIZE) as f64 / seconds,
+                    write: (written.saturating_sub(*previous_written) * Self::SECTOR_SIZE) as f64 / seconds,
+                })
+            })
+            .collect();
+        devices.sort_by(|a, b| a.name.cmp(&b.name));
+        self.previous = counters;
+        devices
+    }
+}
+
+/// Sectors read and written by every whole device since boot, loop and
+/// RAM disks aside. Only whole devices have an entry in `/sys/block`.
+fn read_diskstats() -> HashMap<String, (u64, u64)> {
+    let Ok(content) = fs::read_to_string("/proc/diskstats") else {
+        return HashMap::new();
+    };
+    parse_diskstats(&content, |name| Path::new("/sys/block").join(name).exists())
+}
+
+fn parse_diskstats(content: &str, whole_device: impl Fn(&str) -> bool) -> HashMap<String, (u64, u64)> {
+    content.lines().filter_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let name = *fields.get(2)?;
+        if name.starts_with("loop") || name.starts_with("ram") || !whole_device(name) {
+            return None;
+        }
+        let read: u64 = fields.get(5)?.parse().ok()?;
+        let written: u64 = fields.get(9)?.parse().ok()?;
+        Some((name.to_string(), (read, written)))
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DISKSTATS: &str = "\
+   7       0 loop0 42 0 1652 9 0 0 0 0 0 20 9 0 0 0 0 0 0
+   1       0 ram0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
+ 259       0 nvme0n1 183422 52811 14805372 31845 402877 289640 26581868 389202 0 227132 436590 0 0 0 0 22153 15541
+ 259       1 nvme0n1p1 342 1024 18042 91 2 0 2 0 0 79 91 0 0 0 0 0 0
+ 259       2 nvme0n1p2 182933 51787 14782722 31735 402875 289640 26581866 389202 0 227039 420937 0 0 0 0 0 0
+   8       0 sda 1022 0 41280 512 88 4 9120 307 0 830 819 0 0 0 0 0 0
+   8      16 sdb 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
+ 253       0 dm-0 180012 0 14781210 30110 692515 0 26581866 1020913 0 227511 1051023 0 0 0 0 0 0
+";
+
+    fn whole_device(name: &str) -> bool {
+        ["nvme0n1", "sda", "sdb", "dm-0", "loop0", "ram0"].contains(&name)
+    }
+
+    #[test]
+    fn whole_devices_without_loop_and_ram_disks() {
+        let counters = parse_diskstats(DISKSTATS, whole_device);
+        let mut names: Vec<&str> = counters.keys().map(String::as_str).collect();
+        names.sort();
+        assert_eq!(names, ["dm-0", "nvme0n1", "sda", "sdb"]);
+        assert_eq!(counters["nvme0n1"], (14805372, 26581868));
+        assert_eq!(counters["sda"], (41280, 9120));
+        assert!(parse_diskstats("   8 0 sda 1 2\n 8 16\n", whole_device).is_empty());
+    }
+
+    #[test]
+    fn rates_are_in_bytes_per_second() {
+        let mut meter = DiskStatsMeter::new();
+        let counters = parse_diskstats(DISKSTATS, whole_device);
+        assert!(meter.update(counters.clone(), Duration::from_secs(2)).is_empty());
+        let mut later = counters;
+        later.insert("nvme0n1".to_string(), (14805372 + 2048, 26581868 + 8192));
+        // A device that appeared in between has no rate yet
+        later.insert("sdc".to_string(), (100, 0));
+        let devices = meter.update(later, Duration::from_secs(2));
+        // sdb was never used
+        let names: Vec<&str> = devices.iter().map(|device| device.name.as_str()).collect();
+        assert_eq!(names, ["dm-0", "nvme0n1", "sda"]);
+        assert_eq!((devices[1].read, devices[1].write), (2048.0 * 512.0 / 2.0, 8192.0 * 512.0 / 2.0));
+        assert_eq!((devices[0].read, devices[0].write), (0.0, 0.0));
     }
 }
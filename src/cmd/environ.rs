@@ -0,0 +1,37 @@
+use std::{fs, io};
+
+use crate::cmd::{no_procfs, PROCFS};
+
+#[derive(Debug, Clone)]
+pub struct EnvVar {
+    pub name: String,
+    pub value: String,
+}
+
+impl EnvVar {
+    /// Reads the environment a process was started with from
+    /// `/proc/<pid>/environ`, which only its owner and root may read.
+    pub fn read_all(pid: u32) -> io::Result<Vec<EnvVar>> {
+        if !PROCFS {
+            return Err(no_procfs());
+        }
+        let content = fs::read(format!("/proc/{}/environ", pid))?;
+        let mut vars: Vec<EnvVar> = content
+            .split(|byte| *byte == 0)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let entry = String::from_utf8_lossy(entry);
+                let (name, value) = entry.split_once('=').unwrap_or((&entry, ""));
+                EnvVar { name: name.to_string(), value: value.to_string() }
+            })
+            .collect();
+        vars.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(vars)
+    }
+
+    /// Whether the name contains one of `patterns`, ignoring case.
+    pub fn is_secret(&self, patterns: &[String]) -> bool {
+        let name = self.name.to_uppercase();
+        patterns.iter().any(|pattern| name.contains(&pattern.to_uppercase()))
+    }
+}
@@ -0,0 +1,46 @@
+use std::{fs, path::Path};
+use serde::{Deserialize, Serialize};
+
+const HWMON: &str = "/sys/class/hwmon";
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Fan {
+    pub label: String,
+    pub rpm: u64,
+}
+
+impl Fan {
+    /// Reads every `fan*_input` under `/sys/class/hwmon`, labelled with the
+    /// `fan*_label` file when the driver provides one.
+    pub fn read_all() -> Vec<Fan> {
+        let mut fans = Vec::new();
+        let Ok(monitors) = fs::read_dir(HWMON) else {
+            return fans;
+        };
+        for monitor in monitors.flatten() {
+            let path = monitor.path();
+            let chip = read_string(&path.join("name")).unwrap_or_default();
+            let Ok(files) = fs::read_dir(&path) else {
+                continue;
+            };
+            for file in files.flatten() {
+                let file_name = file.file_name().to_string_lossy().into_owned();
+                let Some(fan) = file_name.strip_suffix("_input").filter(|fan| fan.starts_with("fan")) else {
+                    continue;
+                };
+                let Some(rpm) = read_string(&file.path()).and_then(|rpm| rpm.parse().ok()) else {
+                    continue;
+                };
+                let label = read_string(&path.join(format!("{}_label", fan)))
+                    .unwrap_or_else(|| format!("{} {}", chip, fan));
+                fans.push(Fan { label, rpm });
+            }
+        }
+        fans.sort_by(|a, b| a.label.cmp(&b.label));
+        fans
+    }
+}
+
+fn read_string(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|value| value.trim().to_string())
+}
@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Gpu {
+    pub name: String,
+    pub utilization: f32,
+    pub memory_used: u64,
+    pub memory_total: u64,
+    pub temperature: f32,
+}
+
+impl Gpu {
+    pub fn percent_memory_used(&self) -> f32 {
+        if self.memory_total == 0 {
+            return 0.0;
+        }
+        self.memory_used as f32 * 100.0 / self.memory_total as f32
+    }
+}
+
+#[cfg(feature = "gpu")]
+pub use collector::get_gpu_info;
+
+#[cfg(feature = "gpu")]
+mod collector {
+    use std::{fs, path::Path, time::Duration};
+    use nvml_wrapper::{enum_wrappers::device::TemperatureSensor, Nvml};
+
+    use crate::cmd::{gpu::Gpu, hub::Publisher, shutdown::Shutdown, Message};
+
+    /// Publishes NVIDIA GPUs through NVML and AMD GPUs through the amdgpu
+    /// sysfs files. Machines without either publish an empty list.
+    pub fn get_gpu_info(publisher: Publisher, refresh: Duration, shutdown: &Shutdown) {
+        shutdown.spawn(async move {
+            // NVML is loaded at runtime, missing drivers only disable NVIDIA GPUs
+            let nvml = Nvml::init().ok();
+            loop {
+                let mut gpus = nvml.as_ref().map(nvidia_gpus).unwrap_or_default();
+                gpus.extend(amd_gpus());
+                publisher.publish(Message::Gpu(gpus));
+                tokio::time::sleep(refresh).await;
+            }
+        });
+    }
+
+    fn nvidia_gpus(nvml: &Nvml) -> Vec<Gpu> {
+        let mut gpus = Vec::new();
+        for index in 0..nvml.device_count().unwrap_or(0) {
+            let Ok(device) = nvml.device_by_index(index) else {
+                continue;
+            };
+            let memory = device.memory_info().ok();
+            gpus.push(Gpu {
+                name: device.name().unwrap_or_else(|_| format!("nvidia{}", index)),
+                utilization: device.utilization_rates().map_or(0.0, |rates| rates.gpu as f32),
+                memory_used: memory.as_ref().map_or(0, |memory| memory.used),
+                memory_total: memory.as_ref().map_or(0, |memory| memory.total),
+                temperature: device.temperature(TemperatureSensor::Gpu).map_or(0.0, |temp| temp as f32),
+            });
+        }
+        gpus
+    }
+
+    fn amd_gpus() -> Vec<Gpu> {
+        let mut gpus = Vec::new();
+        let Ok(cards) = fs::read_dir("/sys/class/drm") else {
+            return gpus;
+        };
+        for card in cards.flatten() {
+            let card_name = card.file_name().to_string_lossy().into_owned();
+            // Skip the connectors, e.g. card0-DP-1
+            if !card_name.strip_prefix("card").is_some_and(|id| id.chars().all(|c| c.is_ascii_digit())) {
+                continue;
+            }
+            let device = card.path().join("device");
+            let Some(utilization) = read_number(&device.join("gpu_busy_percent")) else {
+                continue;
+            };
+            let name = fs::read_to_string(device.join("product_name"))
+                .map(|name| name.trim().to_string())
+                .unwrap_or(card_name);
+            gpus.push(Gpu {
+                name,
+                utilization: utilization as f32,
+                memory_used: read_number(&device.join("mem_info_vram_used")).unwrap_or(0),
+                memory_total: read_number(&device.join("mem_info_vram_total")).unwrap_or(0),
+                temperature: hwmon_temperature(&device).unwrap_or(0.0),
+            });
+        }
+        gpus
+    }
+
+    fn hwmon_temperature(device: &Path) -> Option<f32> {
+        let hwmon = fs::read_dir(device.join("hwmon")).ok()?.flatten().next()?;
+        // Millidegrees Celsius
+        read_number(&hwmon.path().join("temp1_input")).map(|temp| temp as f32 / 1000.0)
+    }
+
+    fn read_number(path: &Path) -> Option<u64> {
+        fs::read_to_string(path).ok()?.trim().parse().ok()
+    }
+}
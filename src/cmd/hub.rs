@@ -0,0 +1,279 @@
+use tokio::sync::watch;
+
+use crate::cmd::{
+    battery::Battery, connection::Connection, disk::{DeviceIo, Disk, TotalIo}, fan::Fan, gpu::Gpu, interrupts::Interrupt, memory::{KernelMemory, Memory}, network::Network, numa::NumaNode, ping::PingTarget, pressure::Pressure,
+    process::Process, reachability::Reachability, service::Service, traffic::ProcessTraffic, snapshot::Snapshot, temperature::Temperature, throttle::Throttling, Message,
+};
+
+/// Sending half of the collector channels, collectors publish their latest
+/// values through it.
+#[derive(Clone)]
+pub struct Publisher {
+    processes: watch::Sender<Vec<Process>>,
+    cores_usage: watch::Sender<Vec<f32>>,
+    cores_frequency: watch::Sender<Vec<u64>>,
+    mem_usage: watch::Sender<Memory>,
+    kernel_memory: watch::Sender<KernelMemory>,
+    numa_nodes: watch::Sender<Vec<NumaNode>>,
+    interrupts: watch::Sender<Vec<Interrupt>>,
+    network: watch::Sender<Network>,
+    connections: watch::Sender<Vec<Connection>>,
+    services: watch::Sender<Vec<Service>>,
+    process_traffic: watch::Sender<Vec<ProcessTraffic>>,
+    disks: watch::Sender<Vec<Disk>>,
+    disk_io: watch::Sender<TotalIo>,
+    device_io: watch::Sender<Vec<DeviceIo>>,
+    temperatures: watch::Sender<Vec<Temperature>>,
+    fans: watch::Sender<Vec<Fan>>,
+    gpus: watch::Sender<Vec<Gpu>>,
+    batteries: watch::Sender<Vec<Battery>>,
+    throttling: watch::Sender<Option<Throttling>>,
+    general_infos: watch::Sender<Vec<String>>,
+    pressures: watch::Sender<Vec<Pressure>>,
+    reachability: watch::Sender<Reachability>,
+    ping: watch::Sender<Vec<PingTarget>>,
+}
+
+/// Receiving half of the collector channels. Every clone is an independent
+/// subscription that sees each update.
+#[derive(Clone)]
+pub struct Subscriber {
+    processes: watch::Receiver<Vec<Process>>,
+    cores_usage: watch::Receiver<Vec<f32>>,
+    cores_frequency: watch::Receiver<Vec<u64>>,
+    mem_usage: watch::Receiver<Memory>,
+    kernel_memory: watch::Receiver<KernelMemory>,
+    numa_nodes: watch::Receiver<Vec<NumaNode>>,
+    interrupts: watch::Receiver<Vec<Interrupt>>,
+    network: watch::Receiver<Network>,
+    connections: watch::Receiver<Vec<Connection>>,
+    services: watch::Receiver<Vec<Service>>,
+    process_traffic: watch::Receiver<Vec<ProcessTraffic>>,
+    disks: watch::Receiver<Vec<Disk>>,
+    disk_io: watch::Receiver<TotalIo>,
+    device_io: watch::Receiver<Vec<DeviceIo>>,
+    temperatures: watch::Receiver<Vec<Temperature>>,
+    fans: watch::Receiver<Vec<Fan>>,
+    gpus: watch::Receiver<Vec<Gpu>>,
+    batteries: watch::Receiver<Vec<Battery>>,
+    throttling: watch::Receiver<Option<Throttling>>,
+    general_infos: watch::Receiver<Vec<String>>,
+    pressures: watch::Receiver<Vec<Pressure>>,
+    reachability: watch::Receiver<Reachability>,
+    ping: watch::Receiver<Vec<PingTarget>>,
+}
+
+pub fn channel() -> (Publisher, Subscriber) {
+    let (processes_tx, processes_rx) = watch::channel(Vec::new());
+    let (cores_usage_tx, cores_usage_rx) = watch::channel(Vec::new());
+    let (cores_frequency_tx, cores_frequency_rx) = watch::channel(Vec::new());
+    let (mem_usage_tx, mem_usage_rx) = watch::channel(Memory::default());
+    let (kernel_memory_tx, kernel_memory_rx) = watch::channel(KernelMemory::default());
+    let (numa_nodes_tx, numa_nodes_rx) = watch::channel(Vec::new());
+    let (interrupts_tx, interrupts_rx) = watch::channel(Vec::new());
+    let (network_tx, network_rx) = watch::channel(Network::new());
+    let (connections_tx, connections_rx) = watch::channel(Vec::new());
+    let (services_tx, services_rx) = watch::channel(Vec::new());
+    let (process_traffic_tx, process_traffic_rx) = watch::channel(Vec::new());
+    let (disks_tx, disks_rx) = watch::channel(Vec::new());
+    let (disk_io_tx, disk_io_rx) = watch::channel(TotalIo::default());
+    let (device_io_tx, device_io_rx) = watch::channel(Vec::new());
+    let (temperatures_tx, temperatures_rx) = watch::channel(Vec::new());
+    let (fans_tx, fans_rx) = watch::channel(Vec::new());
+    let (gpus_tx, gpus_rx) = watch::channel(Vec::new());
+    let (batteries_tx, batteries_rx) = watch::channel(Vec::new());
+    let (throttling_tx, throttling_rx) = watch::channel(None);
+    let (general_infos_tx, general_infos_rx) = watch::channel(Vec::new());
+    let (pressures_tx, pressures_rx) = watch::channel(Vec::new());
+    let (reachability_tx, reachability_rx) = watch::channel(Reachability::default());
+    let (ping_tx, ping_rx) = watch::channel(Vec::new());
+    let publisher = Publisher {
+        processes: processes_tx,
+        cores_usage: cores_usage_tx,
+        cores_frequency: cores_frequency_tx,
+        mem_usage: mem_usage_tx,
+        kernel_memory: kernel_memory_tx,
+        numa_nodes: numa_nodes_tx,
+        interrupts: interrupts_tx,
+        network: network_tx,
+        connections: connections_tx,
+        services: services_tx,
+        process_traffic: process_traffic_tx,
+        disks: disks_tx,
+        disk_io: disk_io_tx,
+        device_io: device_io_tx,
+        temperatures: temperatures_tx,
+        fans: fans_tx,
+        gpus: gpus_tx,
+        batteries: batteries_tx,
+        throttling: throttling_tx,
+        general_infos: general_infos_tx,
+        pressures: pressures_tx,
+        reachability: reachability_tx,
+        ping: ping_tx,
+    };
+    let subscriber = Subscriber {
+        processes: processes_rx,
+        cores_usage: cores_usage_rx,
+        cores_frequency: cores_frequency_rx,
+        mem_usage: mem_usage_rx,
+        kernel_memory: kernel_memory_rx,
+        numa_nodes: numa_nodes_rx,
+        interrupts: interrupts_rx,
+        network: network_rx,
+        connections: connections_rx,
+        services: services_rx,
+        process_traffic: process_traffic_rx,
+        disks: disks_rx,
+        disk_io: disk_io_rx,
+        device_io: device_io_rx,
+        temperatures: temperatures_rx,
+        fans: fans_rx,
+        gpus: gpus_rx,
+        batteries: batteries_rx,
+        throttling: throttling_rx,
+        general_infos: general_infos_rx,
+        pressures: pressures_rx,
+        reachability: reachability_rx,
+        ping: ping_rx,
+    };
+    (publisher, subscriber)
+}
+
+impl Publisher {
+    pub fn publish(&self, msg: Message) {
+        match msg {
+            Message::Processes(processes) => { self.processes.send_replace(processes); }
+            Message::CpuUsage(cores_usage) => { self.cores_usage.send_replace(cores_usage); }
+            Message::CpuFrequency(cores_frequency) => { self.cores_frequency.send_replace(cores_frequency); }
+            Message::MemUsage(mem_usage) => { self.mem_usage.send_replace(mem_usage); }
+            Message::KernelMemory(kernel_memory) => { self.kernel_memory.send_replace(kernel_memory); }
+            Message::Numa(numa_nodes) => { self.numa_nodes.send_replace(numa_nodes); }
+            Message::Interrupts(interrupts) => { self.interrupts.send_replace(interrupts); }
+            Message::Network(network) => { self.network.send_replace(network); }
+            Message::Connections(connections) => { self.connections.send_replace(connections); }
+            Message::Services(services) => { self.services.send_replace(services); }
+            Message::ProcessTraffic(process_traffic) => { self.process_traffic.send_replace(process_traffic); }
+            Message::DiskUsage(disks) => { self.disks.send_replace(disks); }
+            Message::DiskIO(disk_io) => { self.disk_io.send_replace(disk_io); }
+            Message::DeviceIo(device_io) => { self.device_io.send_replace(device_io); }
+            Message::Temperature(temperatures) => { self.temperatures.send_replace(temperatures); }
+            Message::Fan(fans) => { self.fans.send_replace(fans); }
+            Message::Gpu(gpus) => { self.gpus.send_replace(gpus); }
+            Message::Battery(batteries) => { self.batteries.send_replace(batteries); }
+            Message::Throttling(throttling) => { self.throttling.send_replace(throttling); }
+            Message::GeneralInfo(general_infos) => { self.general_infos.send_replace(general_infos); }
+            Message::Pressure(pressures) => { self.pressures.send_replace(pressures); }
+            Message::Reachability(reachability) => { self.reachability.send_replace(reachability); }
+            Message::Ping(ping) => { self.ping.send_replace(ping); }
+        }
+    }
+}
+
+impl Subscriber {
+    /// Waits for any channel to change and returns its new value. Returns
+    /// `None` once every collector has stopped.
+    pub async fn next(&mut self) -> Option<Message> {
+        tokio::select! {
+            Ok(()) = self.processes.changed() => {
+                Some(Message::Processes(self.processes.borrow_and_update().clone()))
+            }
+            Ok(()) = self.cores_usage.changed() => {
+                Some(Message::CpuUsage(self.cores_usage.borrow_and_update().clone()))
+            }
+            Ok(()) = self.cores_frequency.changed() => {
+                Some(Message::CpuFrequency(self.cores_frequency.borrow_and_update().clone()))
+            }
+            Ok(()) = self.mem_usage.changed() => {
+                Some(Message::MemUsage(*self.mem_usage.borrow_and_update()))
+            }
+            Ok(()) = self.kernel_memory.changed() => {
+                Some(Message::KernelMemory(*self.kernel_memory.borrow_and_update()))
+            }
+            Ok(()) = self.numa_nodes.changed() => {
+                Some(Message::Numa(self.numa_nodes.borrow_and_update().clone()))
+            }
+            Ok(()) = self.interrupts.changed() => {
+                Some(Message::Interrupts(self.interrupts.borrow_and_update().clone()))
+            }
+            Ok(()) = self.network.changed() => {
+                Some(Message::Network(self.network.borrow_and_update().clone()))
+            }
+            Ok(()) = self.connections.changed() => {
+                Some(Message::Connections(self.connections.borrow_and_update().clone()))
+            }
+            Ok(()) = self.services.changed() => {
+                Some(Message::Services(self.services.borrow_and_update().clone()))
+            }
+            Ok(()) = self.process_traffic.changed() => {
+                Some(Message::ProcessTraffic(self.process_traffic.borrow_and_update().clone()))
+            }
+            Ok(()) = self.disks.changed() => {
+                Some(Message::DiskUsage(self.disks.borrow_and_update().clone()))
+            }
+            Ok(()) = self.disk_io.changed() => {
+                Some(Message::DiskIO(*self.disk_io.borrow_and_update()))
+            }
+            Ok(()) = self.device_io.changed() => {
+                Some(Message::DeviceIo(self.device_io.borrow_and_update().clone()))
+            }
+            Ok(()) = self.temperatures.changed() => {
+                Some(Message::Temperature(self.temperatures.borrow_and_update().clone()))
+            }
+            Ok(()) = self.fans.changed() => {
+                Some(Message::Fan(self.fans.borrow_and_update().clone()))
+            }
+            Ok(()) = self.gpus.changed() => {
+                Some(Message::Gpu(self.gpus.borrow_and_update().clone()))
+            }
+            Ok(()) = self.batteries.changed() => {
+                Some(Message::Battery(self.batteries.borrow_and_update().clone()))
+            }
+            Ok(()) = self.throttling.changed() => {
+                Some(Message::Throttling(*self.throttling.borrow_and_update()))
+            }
+            Ok(()) = self.general_infos.changed() => {
+                Some(Message::GeneralInfo(self.general_infos.borrow_and_update().clone()))
+            }
+            Ok(()) = self.pressures.changed() => {
+                Some(Message::Pressure(self.pressures.borrow_and_update().clone()))
+            }
+            Ok(()) = self.reachability.changed() => {
+                Some(Message::Reachability(self.reachability.borrow_and_update().clone()))
+            }
+            Ok(()) = self.ping.changed() => {
+                Some(Message::Ping(self.ping.borrow_and_update().clone()))
+            }
+            else => None,
+        }
+    }
+
+    /// Latest value of every channel.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            processes: self.processes.borrow().clone(),
+            cores_usage: self.cores_usage.borrow().clone(),
+            cores_frequency: self.cores_frequency.borrow().clone(),
+            mem_usage: *self.mem_usage.borrow(),
+            kernel_memory: *self.kernel_memory.borrow(),
+            numa_nodes: self.numa_nodes.borrow().clone(),
+            interrupts: self.interrupts.borrow().clone(),
+            network: self.network.borrow().clone(),
+            connections: self.connections.borrow().clone(),
+            services: self.services.borrow().clone(),
+            process_traffic: self.process_traffic.borrow().clone(),
+            disks: self.disks.borrow().clone(),
+            disk_io: *self.disk_io.borrow(),
+            device_io: self.device_io.borrow().clone(),
+            temperatures: self.temperatures.borrow().clone(),
+            fans: self.fans.borrow().clone(),
+            gpus: self.gpus.borrow().clone(),
+            batteries: self.batteries.borrow().clone(),
+            throttling: *self.throttling.borrow(),
+            general_infos: self.general_infos.borrow().clone(),
+            pressures: self.pressures.borrow().clone(),
+            reachability: self.reachability.borrow().clone(),
+            ping: self.ping.borrow().clone(),
+        }
+    }
+}
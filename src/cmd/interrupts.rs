@@ -0,0 +1,157 @@
+use std::{collections::HashMap, fs, path::Path, time::Duration};
+use serde::{Deserialize, Serialize};
+
+/// Interrupt sources kept in a measurement, the busiest first.
+const TOP_SOURCES: usize = 10;
+
+/// A hardware interrupt line or softirq and how often it fired per second,
+/// summed over every CPU.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Interrupt {
+    pub source: String,
+    pub description: String,
+    pub rate: f64,
+}
+
+/// Turns the counters of `/proc/interrupts` and `/proc/softirqs` into rates.
+#[derive(Default)]
+pub struct InterruptMeter {
+    previous: HashMap<String, u64>,
+}
+
+impl InterruptMeter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether there are counters to read, which some containers hide.
+    pub fn available() -> bool {
+        Path::new("/proc/interrupts").exists() || Path::new("/proc/softirqs").exists()
+    }
+
+    /// Returns the sources that grew the most over the last `elapsed`.
+    /// The first call only records the counters and returns nothing.
+    pub fn measure(&mut self, elapsed: Duration) -> Vec<Interrupt> {
+        let mut counters = read_table("/proc/interrupts", false);
+        counters.extend(read_table("/proc/softirqs", true));
+        self.update(counters, elapsed)
+    }
+
+    fn update(&mut self, counters: HashMap<String, (u64, String)>, elapsed: Duration) -> Vec<Interrupt> {
+        let seconds = elapsed.as_secs_f64().max(f64::EPSILON);
+        let mut interrupts: Vec<Interrupt> = counters.iter()
+            .filter_map(|(source, (total, description))| {
+                let previous = self.previous.get(source)?;
+                Some(Interrupt {
+                    source: source.clone(),
+                    description: description.clone(),
+                    rate: total.saturating_sub(*previous) as f64 / seconds,
+                })
+            })
+            .filter(|interrupt| interrupt.rate > 0.0)
+            .collect();
+        interrupts.sort_by(|a, b| b.rate.total_cmp(&a.rate));
+        interrupts.truncate(TOP_SOURCES);
+        self.previous = counters.into_iter().map(|(source, (total, _))| (source, total)).collect();
+        interrupts
+    }
+}
+
+/// Total count and description of every row, by source. Rows hold one
+/// count per CPU followed by the controller and device names.
+fn read_table(path: &str, softirq: bool) -> HashMap<String, (u64, String)> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return HashMap::new();
+    };
+    parse_table(&content, softirq)
+}
+
+fn parse_table(content: &str, softirq: bool) -> HashMap<String, (u64, String)> {
+    let mut lines = content.lines();
+    let cpus = lines.next().map_or(0, |header| header.split_whitespace().count());
+    lines.filter_map(|line| {
+        let (source, rest) = line.split_once(':')?;
+        let mut fields = rest.split_whitespace().peekable();
+        let mut total = 0;
+        // Rows such as ERR and MIS have a single count
+        for _ in 0..cpus {
+            match fields.peek().and_then(|field| field.parse::<u64>().ok()) {
+                Some(count) => {
+                    total += count;
+                    fields.next();
+                }
+                None => break,
+            }
+        }
+        let description = match softirq {
+            true => "softirq".to_string(),
+            false => fields.collect::<Vec<_>>().join(" "),
+        };
+        Some((source.trim().to_string(), (total, description)))
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERRUPTS: &str = "\
+           CPU0       CPU1       CPU2       CPU3
+  0:         35          0          0          0   IO-APIC   2-edge      timer
+  8:          0          0          1          0   IO-APIC   8-edge      rtc0
+129:     104862       2235      88810       5520   PCI-MSI 524288-edge      nvme0q0
+NMI:         12         10         11          9   Non-maskable interrupts
+LOC:    9253621    8410232    8875120    8012455   Local timer interrupts
+ERR:          0
+MIS:          0
+";
+    const SOFTIRQS: &str = "\
+                    CPU0       CPU1       CPU2       CPU3
+          HI:          1          0          0          2
+       TIMER:     491245     402113     450762     388410
+      NET_RX:      20938       1733     155022       9841
+";
+
+    fn counters() -> HashMap<String, (u64, String)> {
+        let mut counters = parse_table(INTERRUPTS, false);
+        counters.extend(parse_table(SOFTIRQS, true));
+        counters
+    }
+
+    #[test]
+    fn counts_are_summed_over_cpus() {
+        let counters = counters();
+        assert_eq!(counters["129"], (104862 + 2235 + 88810 + 5520, "PCI-MSI 524288-edge nvme0q0".to_string()));
+        assert_eq!(counters["LOC"].1, "Local timer interrupts");
+        assert_eq!(counters["NET_RX"], (20938 + 1733 + 155022 + 9841, "softirq".to_string()));
+        // Rows with one count for the whole system
+        assert_eq!(counters["ERR"], (0, String::new()));
+        assert_eq!(counters.len(), 10);
+    }
+
+    #[test]
+    fn rates_of_the_sources_that_grew() {
+        let mut meter = InterruptMeter::new();
+        assert!(meter.update(counters(), Duration::from_secs(2)).is_empty());
+        let mut later = counters();
+        later.get_mut("LOC").unwrap().0 += 8000;
+        later.get_mut("NET_RX").unwrap().0 += 200;
+        later.get_mut("129").unwrap().0 += 50;
+        // A source that appeared in between has no rate yet
+        later.insert("130".to_string(), (10, "PCI-MSI nvme0q1".to_string()));
+        let interrupts = meter.update(later, Duration::from_secs(2));
+        let rates: Vec<(&str, f64)> = interrupts.iter().map(|interrupt| (interrupt.source.as_str(), interrupt.rate)).collect();
+        assert_eq!(rates, [("LOC", 4000.0), ("NET_RX", 100.0), ("129", 25.0)]);
+    }
+
+    #[test]
+    fn only_the_busiest_sources_are_kept() {
+        let mut meter = InterruptMeter::new();
+        let counters = |step: u64| (0..15).map(|line| (line.to_string(), (line * step, String::new()))).collect();
+        meter.update(counters(0), Duration::from_secs(1));
+        let interrupts = meter.update(counters(1), Duration::from_secs(1));
+        assert_eq!(interrupts.len(), TOP_SOURCES);
+        assert_eq!(interrupts[0].source, "14");
+        assert_eq!(interrupts[TOP_SOURCES - 1].source, "5");
+    }
+}
@@ -0,0 +1,80 @@
+use std::{fs, io};
+
+use crate::cmd::{no_procfs, PROCFS};
+
+/// Limits shown for a process: the `/proc/<pid>/limits` label, the name we
+/// display and whether the values are bytes rather than counts.
+const LIMITS: [(&str, &str, bool); 4] = [
+    ("Max open files", "Open files", false),
+    ("Max processes", "Processes", false),
+    ("Max resident set", "Resident memory", true),
+    ("Max address space", "Address space", true),
+];
+
+/// A resource limit of a process and how much of it is in use. `None` means
+/// unlimited for the limits, and unknown for the usage.
+#[derive(Debug, Clone)]
+pub struct ResourceLimit {
+    pub name: &'static str,
+    pub soft: Option<u64>,
+    pub hard: Option<u64>,
+    pub usage: Option<u64>,
+    pub in_bytes: bool,
+}
+
+impl ResourceLimit {
+    pub fn read_all(pid: u32) -> io::Result<Vec<ResourceLimit>> {
+        if !PROCFS {
+            return Err(no_procfs());
+        }
+        let limits = fs::read_to_string(format!("/proc/{}/limits", pid))?;
+        let status = fs::read_to_string(format!("/proc/{}/status", pid)).unwrap_or_default();
+        Ok(LIMITS.iter().filter_map(|(label, name, in_bytes)| {
+            let values = limits.lines().find_map(|line| line.strip_prefix(label))?;
+            let mut values = values.split_whitespace();
+            // "unlimited" does not parse and becomes None
+            let soft = values.next()?.parse().ok();
+            let hard = values.next()?.parse().ok();
+            let usage = match *label {
+                "Max open files" => fs::read_dir(format!("/proc/{}/fd", pid)).ok().map(|fds| fds.count() as u64),
+                "Max processes" => status_field(&status, "Uid:").map(|uid| threads_of_user(uid as u32)),
+                "Max resident set" => status_field(&status, "VmRSS:").map(|kb| kb * 1024),
+                "Max address space" => status_field(&status, "VmSize:").map(|kb| kb * 1024),
+                _ => None,
+            };
+            Some(ResourceLimit { name, soft, hard, usage, in_bytes: *in_bytes })
+        }).collect())
+    }
+
+    /// Share of the soft limit in use, from 0 to 1.
+    pub fn usage_ratio(&self) -> Option<f64> {
+        match (self.usage, self.soft) {
+            (Some(_), Some(0)) => Some(1.0),
+            (Some(usage), Some(soft)) => Some(usage as f64 / soft as f64),
+            _ => None,
+        }
+    }
+}
+
+/// First number after `key` in a `/proc/<pid>/status` file.
+fn status_field(status: &str, key: &str) -> Option<u64> {
+    status.lines()
+        .find_map(|line| line.strip_prefix(key))
+        .and_then(|value| value.split_whitespace().next())
+        .and_then(|value| value.parse().ok())
+}
+
+/// The process limit counts every thread of the real user, not only the
+/// ones of the process itself.
+fn threads_of_user(uid: u32) -> u64 {
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_name().to_str().is_some_and(|name| name.bytes().all(|byte| byte.is_ascii_digit())))
+        .filter_map(|entry| fs::read_to_string(entry.path().join("status")).ok())
+        .filter(|status| status_field(status, "Uid:") == Some(uid as u64))
+        .filter_map(|status| status_field(&status, "Threads:"))
+        .sum()
+}
@@ -0,0 +1,225 @@
+use std::{collections::HashMap, fs};
+use serde::{Deserialize, Serialize};
+
+/// Memory in bytes. `used` excludes what the kernel could reclaim, the other
+/// fields split the total the way `free` does and stay zero without
+/// `/proc/meminfo`.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct Memory {
+    pub total: u64,
+    pub used: u64,
+    #[serde(default)]
+    pub free: u64,
+    #[serde(default)]
+    pub available: u64,
+    #[serde(default)]
+    pub buffers: u64,
+    /// Page cache and reclaimable slab, without shared memory.
+    #[serde(default)]
+    pub cached: u64,
+    /// tmpfs and shared memory segments, counted in the page cache by the kernel.
+    #[serde(default)]
+    pub shared: u64,
+    #[serde(default)]
+    pub swap_total: u64,
+    #[serde(default)]
+    pub swap_used: u64,
+}
+
+impl Memory {
+    pub fn new(total: u64, used: u64) -> Self {
+        Self { total, used, ..Default::default() }
+    }
+
+    /// Fills in the breakdown from `/proc/meminfo`.
+    pub fn with_breakdown(self) -> Self {
+        self.with_meminfo(&meminfo())
+    }
+
+    fn with_meminfo(mut self, meminfo: &HashMap<String, u64>) -> Self {
+        let field = |key: &str| meminfo.get(key).copied().unwrap_or_default();
+        self.free = field("MemFree");
+        self.available = field("MemAvailable");
+        self.buffers = field("Buffers");
+        self.shared = field("Shmem");
+        self.cached = (field("Cached") + field("SReclaimable")).saturating_sub(self.shared);
+        self
+    }
+
+    pub fn with_swap(mut self, total: u64, used: u64) -> Self {
+        self.swap_total = total;
+        self.swap_used = used;
+        self
+    }
+
+    pub fn percent_used(&self) -> f32 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.used as f32 * 100.0 / self.total as f32
+    }
+
+    /// Parts of the total that add up to it, in the order they are drawn.
+    /// Without a breakdown there is only used and free memory.
+    pub fn segments(&self) -> Vec<(&'static str, u64)> {
+        if self.free == 0 && self.cached == 0 && self.buffers == 0 {
+            return vec![("used", self.used), ("free", self.total.saturating_sub(self.used))];
+        }
+        let in_use = self.total
+            .saturating_sub(self.free)
+            .saturating_sub(self.buffers)
+            .saturating_sub(self.cached)
+            .saturating_sub(self.shared);
+        vec![
+            ("used", in_use),
+            ("buffers", self.buffers),
+            ("shared", self.shared),
+            ("cached", self.cached),
+            ("free", self.free),
+        ]
+    }
+}
+
+/// Every field of `/proc/meminfo` in bytes, or the page count for the
+/// fields without a unit such as `HugePages_Total`.
+pub fn meminfo() -> HashMap<String, u64> {
+    let Ok(content) = fs::read_to_string("/proc/meminfo") else {
+        return HashMap::new();
+    };
+    parse_meminfo(&content)
+}
+
+fn parse_meminfo(content: &str) -> HashMap<String, u64> {
+    content.lines().filter_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        let mut value = value.split_whitespace();
+        let amount: u64 = value.next()?.parse().ok()?;
+        let amount = match value.next() {
+            Some("kB") => amount * 1024,
+            _ => amount,
+        };
+        Some((key.to_string(), amount))
+    }).collect()
+}
+
+/// Kernel side memory from `/proc/meminfo` that no process accounts for,
+/// in bytes unless noted.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct KernelMemory {
+    pub slab: u64,
+    pub slab_reclaimable: u64,
+    pub slab_unreclaimable: u64,
+    pub kernel_stack: u64,
+    pub page_tables: u64,
+    pub vmalloc_used: u64,
+    pub dirty: u64,
+    pub writeback: u64,
+    pub committed: u64,
+    pub commit_limit: u64,
+    pub anon_huge_pages: u64,
+    /// Counts of huge pages, each `huge_page_size` bytes.
+    pub huge_pages_total: u64,
+    pub huge_pages_free: u64,
+    pub huge_pages_reserved: u64,
+    pub huge_pages_surplus: u64,
+    pub huge_page_size: u64,
+}
+
+impl KernelMemory {
+    pub fn read() -> Self {
+        Self::from_meminfo(&meminfo())
+    }
+
+    fn from_meminfo(meminfo: &HashMap<String, u64>) -> Self {
+        let field = |key: &str| meminfo.get(key).copied().unwrap_or_default();
+        Self {
+            slab: field("Slab"),
+            slab_reclaimable: field("SReclaimable"),
+            slab_unreclaimable: field("SUnreclaim"),
+            kernel_stack: field("KernelStack"),
+            page_tables: field("PageTables"),
+            vmalloc_used: field("VmallocUsed"),
+            dirty: field("Dirty"),
+            writeback: field("Writeback"),
+            committed: field("Committed_AS"),
+            commit_limit: field("CommitLimit"),
+            anon_huge_pages: field("AnonHugePages"),
+            huge_pages_total: field("HugePages_Total"),
+            huge_pages_free: field("HugePages_Free"),
+            huge_pages_reserved: field("HugePages_Rsvd"),
+            huge_pages_surplus: field("HugePages_Surp"),
+            huge_page_size: field("Hugepagesize"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEMINFO: &str = "\
+MemTotal:       16299928 kB
+MemFree:         2149412 kB
+MemAvailable:    9450724 kB
+Buffers:          412196 kB
+Cached:          6895428 kB
+SwapCached:        10240 kB
+Shmem:            631024 kB
+Slab:             923144 kB
+SReclaimable:     588520 kB
+SUnreclaim:       334624 kB
+KernelStack:       21856 kB
+PageTables:        58412 kB
+CommitLimit:    12344964 kB
+Committed_AS:   17095780 kB
+VmallocUsed:       64248 kB
+AnonHugePages:    206848 kB
+HugePages_Total:       8
+HugePages_Free:        6
+HugePages_Rsvd:        1
+HugePages_Surp:        0
+Hugepagesize:       2048 kB
+";
+    const KIB: u64 = 1024;
+
+    #[test]
+    fn fields_are_in_bytes_or_pages() {
+        let meminfo = parse_meminfo(MEMINFO);
+        assert_eq!(meminfo["MemTotal"], 16299928 * KIB);
+        assert_eq!(meminfo["HugePages_Total"], 8);
+        assert_eq!(meminfo.len(), 21);
+        // Lines without a number are left out
+        assert!(parse_meminfo("Broken:\nDirectMap4k: x kB\n").is_empty());
+    }
+
+    #[test]
+    fn breakdown_adds_up_to_the_total() {
+        let total = 16299928 * KIB;
+        let memory = Memory::new(total, 6849204 * KIB).with_meminfo(&parse_meminfo(MEMINFO));
+        assert_eq!(memory.free, 2149412 * KIB);
+        assert_eq!(memory.available, 9450724 * KIB);
+        assert_eq!(memory.shared, 631024 * KIB);
+        // Cached and reclaimable slab without the shared memory in it
+        assert_eq!(memory.cached, (6895428 + 588520 - 631024) * KIB);
+        let segments = memory.segments();
+        assert_eq!(segments.iter().map(|(name, _)| *name).collect::<Vec<_>>(), ["used", "buffers", "shared", "cached", "free"]);
+        assert_eq!(segments.iter().map(|(_, bytes)| bytes).sum::<u64>(), total);
+    }
+
+    #[test]
+    fn no_breakdown_without_meminfo() {
+        let memory = Memory::new(1000, 400).with_meminfo(&HashMap::new());
+        assert_eq!(memory.segments(), [("used", 400), ("free", 600)]);
+        assert_eq!(memory.percent_used(), 40.0);
+    }
+
+    #[test]
+    fn kernel_memory_fields() {
+        let kernel = KernelMemory::from_meminfo(&parse_meminfo(MEMINFO));
+        assert_eq!(kernel.slab, kernel.slab_reclaimable + kernel.slab_unreclaimable);
+        assert_eq!(kernel.committed, 17095780 * KIB);
+        assert_eq!((kernel.huge_pages_total, kernel.huge_pages_free, kernel.huge_pages_reserved), (8, 6, 1));
+        assert_eq!(kernel.huge_page_size, 2 * 1024 * KIB);
+        assert_eq!(kernel.dirty, 0);
+    }
+}
@@ -1,122 +1,527 @@
 pub mod process;
+pub mod affinity;
+pub mod container;
+pub mod pressure;
+pub mod reachability;
+pub mod battery;
 pub mod network;
+pub mod numa;
+pub mod ping;
+pub mod open_file;
+pub mod connection;
+pub mod control;
+pub mod service;
 pub mod disk;
+pub mod environ;
+pub mod fan;
+pub mod gpu;
+pub mod interrupts;
+pub mod limits;
+pub mod memory;
 pub mod temperature;
-mod utils;
+pub mod throttle;
+pub mod traffic;
+pub mod snapshot;
+pub mod stat;
+pub mod backoff;
+pub mod hub;
+pub mod remote;
+pub mod shutdown;
+pub mod units;
+pub mod utils;
+pub mod wsl;
 
-use tokio::{self, sync::Mutex};
-use std::{
-    collections::HashMap, sync::{mpsc::Sender, Arc}, time::Duration
+use tokio::{self, time::MissedTickBehavior};
+use std::{collections::HashMap, time::{Duration, Instant}};
+use serde::{Deserialize, Serialize};
+use sysinfo::{
+    Components, DiskRefreshKind, Disks, Pid, ProcessRefreshKind, ProcessStatus, ProcessesToUpdate, System, ThreadKind, UpdateKind, Users
 };
-use sysinfo::{Components, DiskUsage, Disks, ProcessStatus, System, Users};
 
-use crate::cmd::{disk::Disk, network::Network, temperature::Temperature, utils::seconds_to_timestamp};
+use crate::{
+    app::config::{AppConfig, Meter},
+    cmd::{backoff::Backoff, disk::Disk, hub::Publisher, memory::{KernelMemory, Memory}, network::{Interface, Network}, shutdown::Shutdown, temperature::Temperature, utils::seconds_to_timestamp},
+};
+
+/// Whether the kernel has the procfs and sysfs files most readers beyond
+/// sysinfo parse. Elsewhere they are not started and their panels show as
+/// not available.
+pub const PROCFS: bool = cfg!(target_os = "linux");
 
+/// Error of the readers of `/proc/<pid>` where there is no procfs.
+pub fn no_procfs() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::Unsupported, "only available on Linux")
+}
+
+#[derive(Serialize, Deserialize)]
 pub enum Message {
     Processes(Vec<process::Process>),
     Network(network::Network),
+    Connections(Vec<connection::Connection>),
+    Services(Vec<service::Service>),
+    ProcessTraffic(Vec<traffic::ProcessTraffic>),
     CpuUsage(Vec<f32>),
-    MemUsage(f32),
+    CpuFrequency(Vec<u64>),
+    MemUsage(memory::Memory),
+    KernelMemory(memory::KernelMemory),
+    Numa(Vec<numa::NumaNode>),
+    Interrupts(Vec<interrupts::Interrupt>),
     DiskUsage(Vec<Disk>),
-    DiskIO(DiskUsage),
+    DiskIO(disk::TotalIo),
+    DeviceIo(Vec<disk::DeviceIo>),
     Temperature(Vec<Temperature>),
+    Fan(Vec<fan::Fan>),
+    Gpu(Vec<gpu::Gpu>),
+    Battery(Vec<battery::Battery>),
+    Throttling(Option<throttle::Throttling>),
     GeneralInfo(Vec<String>),
+    Pressure(Vec<pressure::Pressure>),
+    Reachability(reachability::Reachability),
+    Ping(Vec<ping::PingTarget>),
 }
 
-pub fn list_all_processes(tx: Sender<Message>, sys: Arc<Mutex<sysinfo::System>>){
-    tokio::spawn(async move {
+/// Starts every local collector, all of them publishing to `publisher`
+/// until `shutdown` is stopped.
+pub fn spawn_collectors(publisher: Publisher, config: &AppConfig, shutdown: &Shutdown) {
+    let adaptive = &config.adaptive;
+    let backoff = Backoff::new(
+        adaptive.enabled.unwrap(),
+        adaptive.min_interval.unwrap(),
+        adaptive.max_interval.unwrap(),
+    );
+    collect_system(publisher.clone(), config.process_refresh.unwrap(), config.disk_refresh.unwrap(), backoff, shutdown);
+    get_network_info(publisher.clone(), config.network_refresh.unwrap(), shutdown);
+    if PROCFS {
+        get_connections(publisher.clone(), config.connection_refresh.unwrap(), shutdown);
+        // Units are systemd's, which only runs on Linux
+        get_services(publisher.clone(), config.service_refresh.unwrap(), shutdown);
+        get_interrupts(publisher.clone(), config.process_refresh.unwrap(), shutdown);
+    }
+    if PROCFS && config.process_network.unwrap() {
+        get_process_traffic(publisher.clone(), config.process_refresh.unwrap(), shutdown);
+    }
+    get_disk_usage(
+        publisher.clone(),
+        config.disk_usage_refresh.unwrap(),
+        config.excluded_fs_types.clone().unwrap_or_default(),
+        shutdown,
+    );
+    #[cfg(feature = "gpu")]
+    gpu::get_gpu_info(publisher.clone(), config.gpu_refresh.unwrap(), shutdown);
+    if config.layout.places(Meter::Reachability) {
+        get_reachability(publisher.clone(), config.reachability_refresh.unwrap(), shutdown);
+    }
+    let ping_hosts = config.ping.hosts.clone().unwrap_or_default();
+    if config.layout.places(Meter::Ping) && !ping_hosts.is_empty() {
+        get_ping(publisher.clone(), ping_hosts, config.ping.refresh.unwrap(), shutdown);
+    }
+    if PROCFS {
+        get_battery_info(publisher.clone(), config.battery_refresh.unwrap(), shutdown);
+    }
+    // The firmware flags are a Raspberry Pi's
+    if PROCFS && cfg!(any(target_arch = "arm", target_arch = "aarch64")) {
+        get_throttling(publisher.clone(), config.temperature_refresh.unwrap(), shutdown);
+    }
+    get_temperature(publisher, config.temperature_refresh.unwrap(), shutdown);
+}
+
+/// Owns the `System` and refreshes it once per cycle for every metric
+/// derived from it: processes, CPU, memory, disk I/O per process and per device and the general info.
+pub fn collect_system(publisher: Publisher, process_refresh: Duration, disk_refresh: Duration, backoff: Backoff, shutdown: &Shutdown) {
+    shutdown.spawn(async move {
+        let mut sys = System::new_all();
         let users = Users::new_with_refreshed_list();
+        // Processes rarely change cgroup, so it is only read once per PID
+        let mut cgroups: HashMap<u32, Option<String>> = HashMap::new();
+        let mut scheduler = stat::SchedulerMeter::new();
+        let wsl = wsl::Wsl::detect();
+        let process_refresh_kind = ProcessRefreshKind::nothing()
+            .with_cpu()
+            .with_memory()
+            .with_user(UpdateKind::OnlyIfNotSet)
+            .with_cmd(UpdateKind::OnlyIfNotSet);
+        let disk_refresh_kind = ProcessRefreshKind::nothing().with_disk_usage();
+        let mut process_tick = tokio::time::interval(process_refresh);
+        let mut disk_tick = tokio::time::interval(disk_refresh);
+        let mut device_meter = disk::DiskStatsMeter::new();
+        let device_io = PROCFS && disk::DiskStatsMeter::available();
+        // Files that containers and VMs leave out don't appear later, they
+        // stop being read once they come back empty
+        let (mut numa, mut pressure) = (PROCFS, PROCFS);
+        // Time the disk counters cover, the disk tick is stretched under load
+        let mut last_disk_refresh = Instant::now();
+        let mut disk_elapsed = disk_refresh;
+        process_tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        disk_tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
         loop {
-            let mut sys = sys.lock().await;
-            let total_mem = sys.total_memory();
-            sys.refresh_all();
-            let mut vec_proc: Vec<process::Process> = Vec::new();
-            let total_mem_usage = (sys.used_memory() as f32 / total_mem as f32) * 100.0;
-            for (pid, process) in sys.processes() {
-                let user_id = process.user_id().unwrap();
-                let user = users.get_user_by_id(user_id).unwrap().name();
-                let mem_usage = (process.memory() as f32 / total_mem as f32) * 100.0;
-                let cpu_usage = process.cpu_usage() / sys.global_cpu_usage();
-                if cpu_usage <= 0.0 || mem_usage <= 0.0 {
-                    continue;
+            tokio::select! {
+                _ = process_tick.tick() => {
+                    sys.refresh_cpu_usage();
+                    sys.refresh_cpu_frequency();
+                    sys.refresh_memory();
+                    sys.refresh_processes_specifics(ProcessesToUpdate::All, true, process_refresh_kind);
+                    publisher.publish(Message::Processes(list_all_processes(&sys, &users, &mut cgroups, disk_elapsed)));
+                    let memory = Memory::new(sys.total_memory(), sys.used_memory())
+                        .with_swap(sys.total_swap(), sys.used_swap());
+                    publisher.publish(Message::MemUsage(if PROCFS { memory.with_breakdown() } else { memory }));
+                    if PROCFS {
+                        publisher.publish(Message::KernelMemory(KernelMemory::read()));
+                    }
+                    if numa {
+                        let nodes = numa::NumaNode::read_all();
+                        numa = !nodes.is_empty();
+                        publisher.publish(Message::Numa(nodes));
+                    }
+                    if pressure {
+                        let pressures = pressure::Pressure::read_all();
+                        pressure = !pressures.is_empty();
+                        publisher.publish(Message::Pressure(pressures));
+                    }
+                    publisher.publish(Message::CpuUsage(utils::cores_usage(&sys)));
+                    publisher.publish(Message::CpuFrequency(utils::cores_frequency(&sys)));
+                    publisher.publish(Message::GeneralInfo(get_general_info(&sys, &mut scheduler, wsl)));
+                    backoff::adjust(&mut process_tick, backoff.interval(process_refresh, sys.cpus().len()));
+                }
+                _ = disk_tick.tick() => {
+                    sys.refresh_processes_specifics(ProcessesToUpdate::All, true, disk_refresh_kind);
+                    disk_elapsed = last_disk_refresh.elapsed();
+                    last_disk_refresh = Instant::now();
+                    publisher.publish(Message::DiskIO(get_disk_io(&sys, disk_elapsed)));
+                    if device_io {
+                        publisher.publish(Message::DeviceIo(device_meter.measure(disk_elapsed)));
+                    }
+                    backoff::adjust(&mut disk_tick, backoff.interval(disk_refresh, sys.cpus().len()));
                 }
-                let proc = process::Process::default()
-                    .set_pid(pid.as_u32())
-                    .set_process_name(process.name().to_string_lossy().into_owned())
-                    .set_cpu_usage(cpu_usage)
-                    .set_mem_usage(mem_usage)
-                    .set_user(user.to_string())
-                    .build().unwrap();
-                vec_proc.push(proc);
             }
-            tx.send(Message::Processes(vec_proc)).unwrap();
-            tx.send(Message::MemUsage(total_mem_usage)).unwrap();
-            utils::send_cores_usage(&tx, &sys);
-            
-            tokio::time::sleep(Duration::from_secs(1)).await;
         }
     });
 }
 
-pub fn get_network_info(tx: Sender<Message>) {
+pub fn list_all_processes(
+    sys: &System,
+    users: &Users,
+    cgroups: &mut HashMap<u32, Option<String>>,
+    disk_elapsed: Duration,
+) -> Vec<process::Process> {
+    cgroups.retain(|pid, _| sys.process(Pid::from_u32(*pid)).is_some());
+    let total_mem = sys.total_memory();
+    let mut vec_proc: Vec<process::Process> = Vec::new();
+    for (pid, process) in sys.processes() {
+        let kernel_thread = process.thread_kind() == Some(ThreadKind::Kernel);
+        // Tasks of a process are listed next to it with the process as parent
+        let thread_of = match process.thread_kind() {
+            Some(ThreadKind::Userland) => process.parent().map(|parent| parent.as_u32()),
+            _ => None,
+        };
+        let user = match process.user_id() {
+            Some(user_id) => users
+                .get_user_by_id(user_id)
+                .map(|user| user.name().to_string())
+                .unwrap_or_else(|| user_id.to_string()),
+            None if kernel_thread => "[kernel]".to_string(),
+            // Windows hides the owner of its protected system processes
+            None if cfg!(windows) => "SYSTEM".to_string(),
+            None => "root".to_string(),
+        };
+        let cgroup = cgroups
+            .entry(pid.as_u32())
+            .or_insert_with(|| if PROCFS { container::cgroup_of(pid.as_u32()) } else { None })
+            .clone();
+        let mem_usage = (process.memory() as f32 / total_mem as f32) * 100.0;
+        // Irix mode, the UI normalizes by the core count when asked to
+        let cpu_usage = process.cpu_usage();
+        // Disk usage is refreshed by the disk tick and covers the time
+        // between its last two ticks
+        let disk_usage = process.disk_usage();
+        let interval = disk_elapsed.as_secs_f64().max(f64::EPSILON);
+        let proc = process::Process::default()
+            .set_pid(pid.as_u32())
+            .set_process_name(process.name().to_string_lossy().into_owned())
+            .set_cpu_usage(cpu_usage)
+            .set_mem_usage(mem_usage)
+            .set_memory(process.memory())
+            .set_user(user)
+            .set_kernel_thread(kernel_thread)
+            .set_container(cgroup.as_deref().and_then(container::container_of))
+            .set_cgroup(cgroup)
+            .set_command(process.cmd().iter().map(|arg| arg.to_string_lossy()).collect::<Vec<_>>().join(" "))
+            .set_state(state_code(process.status()).to_string())
+            .set_nice(control::nice(pid.as_u32()).unwrap_or_default())
+            .set_disk_io(disk_usage.read_bytes as f64 / interval, disk_usage.written_bytes as f64 / interval)
+            .set_start_time(process.start_time())
+            .set_run_time(process.run_time())
+            .set_parent(process.parent().map(|parent| parent.as_u32()))
+            .set_thread_of(thread_of)
+            .build().unwrap();
+        vec_proc.push(proc);
+    }
+    vec_proc
+}
+
+/// The letter `ps` shows for a process state.
+fn state_code(status: ProcessStatus) -> char {
+    match status {
+        ProcessStatus::Run => 'R',
+        ProcessStatus::Sleep => 'S',
+        ProcessStatus::Idle => 'I',
+        ProcessStatus::Zombie => 'Z',
+        ProcessStatus::Stop => 'T',
+        ProcessStatus::Tracing => 't',
+        ProcessStatus::Dead => 'X',
+        ProcessStatus::UninterruptibleDiskSleep => 'D',
+        ProcessStatus::Wakekill => 'K',
+        ProcessStatus::Waking => 'W',
+        ProcessStatus::Parked => 'P',
+        ProcessStatus::LockBlocked => 'L',
+        _ => '?',
+    }
+}
+
+pub fn get_network_info(publisher: Publisher, refresh: Duration, shutdown: &Shutdown) {
     let mut networks = sysinfo::Networks::new_with_refreshed_list();
     let mut net_data = Network::new();
+    let mut last_refresh = Instant::now();
+    // Bytes sent and received by every interface since the start
+    let mut session: HashMap<String, (u64, u64)> = HashMap::new();
     
-    tokio::spawn(async move {
+    shutdown.spawn(async move {
         tokio::time::sleep(Duration::from_millis(100)).await;
         loop {
             networks.refresh(true);
+            // The counters cover the time since the last refresh, which is
+            // longer than `refresh` whenever the task runs late
+            let elapsed = last_refresh.elapsed().as_secs_f64();
+            last_refresh = Instant::now();
             let mut upload_gb = 0.0;
             let mut download_gb = 0.0;
+            net_data.interfaces.clear();
             for (interface, network) in &networks {
-                if interface.contains("wlp") || interface.contains("enp") {
-                    // To Kilo bits per second
-                    upload_gb += network.transmitted() as f64 * 8.0 / 1_000.0;
-                    download_gb += network.received() as f64 * 8.0 / 1_000.0;
-                    net_data.update(upload_gb, download_gb);
-                    tx.send(Message::Network(net_data)).unwrap();
+                // To Kilo bits per second
+                let upload = network.transmitted() as f64 * 8.0 / 1_000.0 / elapsed;
+                let download = network.received() as f64 * 8.0 / 1_000.0 / elapsed;
+                let (session_transmitted, session_received) = session.entry(interface.clone()).or_default();
+                *session_transmitted += network.transmitted();
+                *session_received += network.received();
+                if Interface::counted_by_default(interface) {
+                    upload_gb += upload;
+                    download_gb += download;
                 }
+                net_data.interfaces.push(Interface {
+                    name: interface.clone(),
+                    mac_address: network.mac_address().to_string(),
+                    ip_addresses: ip_addresses(network.ip_networks()),
+                    upload,
+                    download,
+                    session_transmitted: *session_transmitted,
+                    session_received: *session_received,
+                    total_transmitted: network.total_transmitted(),
+                    total_received: network.total_received(),
+                });
             }
-            tokio::time::sleep(Duration::from_secs(1)).await;
+            net_data.interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+            net_data.update(upload_gb, download_gb);
+            publisher.publish(Message::Network(net_data.clone()));
+            tokio::time::sleep(refresh).await;
+        }
+    });
+}
+
+/// Addresses of an interface with their prefix length, IPv4 first as the
+/// ones users look for.
+fn ip_addresses(networks: &[sysinfo::IpNetwork]) -> Vec<String> {
+    let mut networks: Vec<&sysinfo::IpNetwork> = networks.iter().collect();
+    networks.sort_by_key(|ip| ip.addr.is_ipv6());
+    networks.iter().map(|ip| format!("{}/{}", ip.addr, ip.prefix)).collect()
+}
+
+/// Probes the default gateway and the DNS servers, each given `refresh` to
+/// answer.
+pub fn get_reachability(publisher: Publisher, refresh: Duration, shutdown: &Shutdown) {
+    shutdown.spawn(async move {
+        loop {
+            publisher.publish(Message::Reachability(reachability::Reachability::probe_all(refresh).await));
+            tokio::time::sleep(refresh).await;
         }
     });
 }
 
-pub fn get_disk_usage(tx: Sender<Message>) {
-    let sys_disks = Disks::new_with_refreshed_list();
-    let mut disks: Vec<Disk> = Vec::new(); 
- 
-     for disk in sys_disks.list() {
-         let disk = Disk::new(
-             disk.name().to_string_lossy().into_owned(), 
-             disk.total_space(), 
-             disk.available_space()
-         );
-         disks.push(disk);
-     }
-     tx.send(Message::DiskUsage(disks)).unwrap();
+/// Measures the round trip to every host in `hosts`, each given `refresh`
+/// to answer.
+pub fn get_ping(publisher: Publisher, hosts: Vec<String>, refresh: Duration, shutdown: &Shutdown) {
+    shutdown.spawn(async move {
+        let mut tick = tokio::time::interval(refresh);
+        tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            tick.tick().await;
+            publisher.publish(Message::Ping(ping::PingTarget::probe_all(&hosts, refresh).await));
+        }
+    });
+}
+
+/// Publishes every socket with the rates of the TCP ones, measured over
+/// `refresh`.
+pub fn get_connections(publisher: Publisher, refresh: Duration, shutdown: &Shutdown) {
+    shutdown.spawn(async move {
+        // Dropped when the socket counters can't be read, leaving no rates
+        let mut meter = Some(traffic::SocketMeter::new());
+        let mut last_measure = Instant::now();
+        loop {
+            let elapsed = last_measure.elapsed();
+            last_measure = Instant::now();
+            // Walks every /proc/<pid>/fd, kept off the async workers along
+            // with the meter and its previous counters
+            let measured = tokio::task::spawn_blocking(move || {
+                let mut connections = connection::Connection::read_all();
+                let rates = meter.as_mut().map(|meter| meter.measure(elapsed));
+                match rates {
+                    Some(Ok(rates)) => {
+                        for connection in connections.iter_mut().filter(|connection| connection.protocol.starts_with("tcp")) {
+                            connection.rates = rates.get(&connection.inode).copied();
+                        }
+                    }
+                    Some(Err(err)) => {
+                        log::warn!("Error reading socket counters, connections have no rates: {}", err);
+                        meter = None;
+                    }
+                    None => {}
+                }
+                (connections, meter)
+            }).await;
+            match measured {
+                Ok((connections, returned)) => {
+                    meter = returned;
+                    publisher.publish(Message::Connections(connections));
+                }
+                Err(err) => {
+                    log::warn!("Error reading connections: {}", err);
+                    meter = Some(traffic::SocketMeter::new());
+                }
+            }
+            tokio::time::sleep(refresh).await;
+        }
+    });
 }
 
-pub fn get_disk_io(tx: Sender<Message>, sys: Arc<Mutex<sysinfo::System>>) {
-    tokio::spawn(async move {
+/// Publishes the TCP traffic of every process, measured over `refresh`.
+pub fn get_process_traffic(publisher: Publisher, refresh: Duration, shutdown: &Shutdown) {
+    shutdown.spawn(async move {
+        let mut meter = traffic::TrafficMeter::new();
+        let mut last_measure = Instant::now();
         loop {
-            let mut sys = sys.lock().await;
-            sys.refresh_all();
-            let mut disk_io = DiskUsage::default();
-            for  (_, proc) in sys.processes() {
-                disk_io.read_bytes += proc.disk_usage().read_bytes;
-                disk_io.written_bytes += proc.disk_usage().written_bytes;
+            tokio::time::sleep(refresh).await;
+            let elapsed = last_measure.elapsed();
+            last_measure = Instant::now();
+            // The meter goes to the blocking pool and back, it keeps the previous counters
+            let measured = tokio::task::spawn_blocking(move || {
+                let traffic = meter.measure(elapsed);
+                (traffic, meter)
+            }).await;
+            match measured {
+                Ok((Ok(traffic), returned)) => {
+                    meter = returned;
+                    publisher.publish(Message::ProcessTraffic(traffic));
+                }
+                Ok((Err(err), _)) => {
+                    log::warn!("Error reading socket counters, per process traffic is disabled: {}", err);
+                    return;
+                }
+                Err(err) => {
+                    log::warn!("Error reading socket counters: {}", err);
+                    return;
+                }
             }
-            tx.send(Message::DiskIO(disk_io)).unwrap();
-            tokio::time::sleep(Duration::from_secs(1)).await;
         }
     });
 }
 
-pub fn get_temperature(tx: Sender<Message>) {
+/// Publishes the busiest interrupt sources, measured over `refresh`.
+pub fn get_interrupts(publisher: Publisher, refresh: Duration, shutdown: &Shutdown) {
+    if !interrupts::InterruptMeter::available() {
+        return;
+    }
+    shutdown.spawn(async move {
+        let mut meter = interrupts::InterruptMeter::new();
+        let mut last_measure = Instant::now();
+        loop {
+            let interrupts = meter.measure(last_measure.elapsed());
+            last_measure = Instant::now();
+            publisher.publish(Message::Interrupts(interrupts));
+            tokio::time::sleep(refresh).await;
+        }
+    });
+}
+
+/// Publishes the systemd services, once with an empty list where systemd
+/// is not the init system.
+pub fn get_services(publisher: Publisher, refresh: Duration, shutdown: &Shutdown) {
+    shutdown.spawn(async move {
+        if !service::Service::available() {
+            publisher.publish(Message::Services(Vec::new()));
+            return;
+        }
+        loop {
+            match tokio::task::spawn_blocking(service::Service::read_all).await {
+                Ok(Some(services)) => publisher.publish(Message::Services(services)),
+                // The last list stays up until systemctl answers again
+                Ok(None) => {}
+                Err(err) => log::warn!("Error reading services: {}", err),
+            }
+            tokio::time::sleep(refresh).await;
+        }
+    });
+}
+
+/// Publishes the space of every mounted disk, skipping empty devices, the
+/// file system types listed in `excluded_fs_types` and under WSL the mounts
+/// that repeat another disk.
+pub fn get_disk_usage(publisher: Publisher, refresh: Duration, excluded_fs_types: Vec<String>, shutdown: &Shutdown) {
+    let wsl = wsl::Wsl::detect().is_some();
+    shutdown.spawn(async move {
+        let mut sys_disks = Disks::new_with_refreshed_list_specifics(DiskRefreshKind::nothing().with_storage());
+        loop {
+            let mut disks: Vec<Disk> = Vec::new();
+            for disk in sys_disks.list() {
+                let file_system = disk.file_system().to_string_lossy().into_owned();
+                if disk.total_space() == 0 || excluded_fs_types.contains(&file_system) {
+                    continue;
+                }
+                if wsl && wsl::Wsl::hides_mount(&file_system, &disk.mount_point().to_string_lossy()) {
+                    continue;
+                }
+                let disk = Disk::new(
+                    disk.name().to_string_lossy().into_owned(),
+                    disk.total_space(),
+                    disk.available_space(),
+                    file_system,
+                ).with_mount_point(disk.mount_point());
+                disks.push(disk);
+            }
+            publisher.publish(Message::DiskUsage(disks));
+            tokio::time::sleep(refresh).await;
+            // Also picks up new mounts and drops unmounted ones
+            sys_disks.refresh_specifics(true, DiskRefreshKind::nothing().with_storage());
+        }
+    });
+}
+
+/// What the processes read and wrote over the `elapsed` the counters cover.
+pub fn get_disk_io(sys: &System, elapsed: Duration) -> disk::TotalIo {
+    let (mut read, mut written) = (0, 0);
+    for proc in sys.processes().values() {
+        read += proc.disk_usage().read_bytes;
+        written += proc.disk_usage().written_bytes;
+    }
+    let seconds = elapsed.as_secs_f64().max(f64::EPSILON);
+    disk::TotalIo { read: read as f64 / seconds, write: written as f64 / seconds }
+}
+
+/// Publishes the temperature sensors and the fans, stopping once neither
+/// has anything to read.
+pub fn get_temperature(publisher: Publisher, refresh: Duration, shutdown: &Shutdown) {
     let mut temperatures: Vec<Temperature> = Vec::new();
-    tokio::spawn(async  move {
+    shutdown.spawn(async move {
         let mut sys_components = Components::new_with_refreshed_list();
+        let mut fans = PROCFS;
         loop {
             temperatures.clear();
             sys_components.refresh(true);
@@ -132,41 +537,86 @@ pub fn get_temperature(tx: Sender<Message>) {
                 }
                 temperatures.push(temp);
             }
-            tx.send(Message::Temperature(temperatures.clone())).unwrap();
-            tokio::time::sleep(Duration::from_secs(5)).await;
+            publisher.publish(Message::Temperature(temperatures.clone()));
+            if fans {
+                let readings = fan::Fan::read_all();
+                fans = !readings.is_empty();
+                publisher.publish(Message::Fan(readings));
+            }
+            // Nothing to poll in most VMs and containers
+            if sys_components.is_empty() && !fans {
+                return;
+            }
+            tokio::time::sleep(refresh).await;
         }
     });
 }
 
-pub fn get_general_info(tx: Sender<Message>, sys: Arc<Mutex<sysinfo::System>>) {
-    tokio::spawn(async move {
+/// Publishes the firmware throttling flags along with the temperatures,
+/// once only on boards without them.
+pub fn get_throttling(publisher: Publisher, refresh: Duration, shutdown: &Shutdown) {
+    shutdown.spawn(async move {
         loop {
-            let mut sys = sys.lock().await;
-            sys.refresh_all();
-            let mut message: Vec<String> = Vec::new();
-            let mut status_counts: HashMap<ProcessStatus, u32> = HashMap::new();
-            let load_avg = System::load_average();
-            
-            for (_, proc) in sys.processes() {
-                *status_counts.entry(proc.status()).or_insert(0) += 1;
+            match tokio::task::spawn_blocking(throttle::Throttling::read).await {
+                Ok(throttling) => {
+                    publisher.publish(Message::Throttling(throttling));
+                    if throttling.is_none() {
+                        return;
+                    }
+                }
+                Err(err) => log::warn!("Error reading the throttling flags: {}", err),
             }
-            message.push(
-                format!("Uptime: {} \n", seconds_to_timestamp(System::uptime()))
-            );
-            message.push(
-                format!("Load averag: 1-minute: {}, 5-minute: {}, 15-minute: {}", load_avg.one, load_avg.five, load_avg.fifteen)
-            );
-            message.push(
-                format!("Tasks: {} total, {} running, {} sleep, {} stopped, {} zombie \n",
-                    status_counts.values().sum::<u32>(), 
-                    status_counts.get(&ProcessStatus::Run).unwrap_or(&0),
-                    status_counts.get(&ProcessStatus::Sleep).unwrap_or(&0),
-                    status_counts.get(&ProcessStatus::Stop).unwrap_or(&0),
-                    status_counts.get(&ProcessStatus::Zombie).unwrap_or(&0),
-                )
-            );
-            tx.send(Message::GeneralInfo(message)).unwrap();
-            tokio::time::sleep(Duration::from_secs(1)).await;
+            tokio::time::sleep(refresh).await;
         }
     });
-}
\ No newline at end of file
+}
+
+/// Publishes the batteries, once only on machines without one.
+pub fn get_battery_info(publisher: Publisher, refresh: Duration, shutdown: &Shutdown) {
+    shutdown.spawn(async move {
+        loop {
+            let batteries = battery::Battery::read_all();
+            let none = batteries.is_empty();
+            publisher.publish(Message::Battery(batteries));
+            if none {
+                return;
+            }
+            tokio::time::sleep(refresh).await;
+        }
+    });
+}
+
+pub fn get_general_info(sys: &System, scheduler: &mut stat::SchedulerMeter, wsl: Option<wsl::Wsl>) -> Vec<String> {
+    let mut message: Vec<String> = Vec::new();
+    let mut status_counts: HashMap<ProcessStatus, u32> = HashMap::new();
+    let load_avg = System::load_average();
+    
+    for proc in sys.processes().values() {
+        *status_counts.entry(proc.status()).or_insert(0) += 1;
+    }
+    message.push(
+        format!("Uptime: {} \n", seconds_to_timestamp(System::uptime()))
+    );
+    message.push(
+        format!("Load averag: 1-minute: {}, 5-minute: {}, 15-minute: {}", load_avg.one, load_avg.five, load_avg.fifteen)
+    );
+    message.push(
+        format!("Tasks: {} total, {} running, {} sleep, {} stopped, {} zombie \n",
+            status_counts.values().sum::<u32>(), 
+            status_counts.get(&ProcessStatus::Run).unwrap_or(&0),
+            status_counts.get(&ProcessStatus::Sleep).unwrap_or(&0),
+            status_counts.get(&ProcessStatus::Stop).unwrap_or(&0),
+            status_counts.get(&ProcessStatus::Zombie).unwrap_or(&0),
+        )
+    );
+    if let Some(rates) = scheduler.measure() {
+        message.push(
+            format!("Scheduler: {:.0} ctxsw/s, {:.0} forks/s, {} runnable, {} blocked",
+                rates.context_switches, rates.forks, rates.running, rates.blocked)
+        );
+    }
+    if let Some(wsl) = wsl {
+        message.push(format!("Environment: {}", wsl.name()));
+    }
+    message
+}
@@ -1,14 +1,39 @@
-#[derive(Debug, Clone, Copy)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Network {
     pub upload: f64,
     pub download: f64,
+    #[serde(default)]
+    pub interfaces: Vec<Interface>,
+}
+
+/// Rates of a single network interface, in Kbps like the totals, and the
+/// bytes it moved since the collector started and since its counters were
+/// last reset, usually at boot.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Interface {
+    pub name: String,
+    pub mac_address: String,
+    pub ip_addresses: Vec<String>,
+    pub upload: f64,
+    pub download: f64,
+    #[serde(default)]
+    pub session_transmitted: u64,
+    #[serde(default)]
+    pub session_received: u64,
+    #[serde(default)]
+    pub total_transmitted: u64,
+    #[serde(default)]
+    pub total_received: u64,
 }
 
 impl Network {
     pub fn new() -> Self {
         Self { 
             upload: 0.0, 
-            download: 0.0
+            download: 0.0,
+            interfaces: Vec::new(),
         }
     }
     
@@ -16,4 +41,30 @@ impl Network {
         self.upload = upload;
         self.download = download;
     }
-}
\ No newline at end of file
+}
+
+impl Interface {
+    /// Whether the interface counts towards the totals unless the user
+    /// included or excluded it: the wired and wireless adapters, leaving out
+    /// loopback, bridges and tunnels.
+    pub fn counted_by_default(name: &str) -> bool {
+        if cfg!(windows) {
+            // Adapters go by their friendly name, `vEthernet (WSL)` and the
+            // loopback pseudo interface don't start with these
+            name.starts_with("Ethernet") || name.starts_with("Wi-Fi") || name.starts_with("WLAN")
+        } else if cfg!(target_os = "macos") {
+            // Wi-Fi and Ethernet are `en0`, `en1` and so on, next to `utun`,
+            // `awdl`, `bridge` and `lo0`
+            name.starts_with("en")
+        } else if cfg!(target_os = "freebsd") {
+            // Named after their driver, such as `em0`, `igb0` or `vtnet0`,
+            // so the virtual ones are left out instead. The members of a
+            // `lagg` are counted rather than the aggregate itself
+            !["lo", "bridge", "tap", "tun", "epair", "pflog", "pfsync", "enc", "lagg", "vlan", "wg", "gif", "stf"]
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+        } else {
+            name.contains("wlp") || name.contains("enp")
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::{collections::HashMap, fs};
+use serde::{Deserialize, Serialize};
+
+const NODES: &str = "/sys/devices/system/node";
+
+/// Memory and allocation counters of a NUMA node, from sysfs.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct NumaNode {
+    pub id: u32,
+    pub cpus: Vec<usize>,
+    pub memory_total: u64,
+    /// Memory in use, without the page cache and reclaimable slab.
+    pub memory_used: u64,
+    /// Allocations served by this node as intended.
+    pub numa_hit: u64,
+    /// Allocations meant for another node that landed here.
+    pub numa_miss: u64,
+    /// Allocations meant for this node that landed elsewhere.
+    pub numa_foreign: u64,
+    pub local_node: u64,
+    pub other_node: u64,
+}
+
+impl NumaNode {
+    /// Returns an empty list on kernels without NUMA support.
+    pub fn read_all() -> Vec<NumaNode> {
+        let Ok(entries) = fs::read_dir(NODES) else {
+            return Vec::new();
+        };
+        let mut nodes: Vec<NumaNode> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let id = entry.file_name().to_str()?.strip_prefix("node")?.parse().ok()?;
+                Some(Self::read(id))
+            })
+            .collect();
+        nodes.sort_by_key(|node| node.id);
+        nodes
+    }
+
+    fn read(id: u32) -> NumaNode {
+        let path = format!("{}/node{}", NODES, id);
+        let cpus = fs::read_to_string(format!("{}/cpulist", path))
+            .map(|list| parse_cpu_list(list.trim()))
+            .unwrap_or_default();
+        // Lines look like "Node 0 MemTotal:  6147400 kB"
+        let meminfo: HashMap<String, u64> = fs::read_to_string(format!("{}/meminfo", path))
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace().skip(2);
+                let key = fields.next()?.trim_end_matches(':').to_string();
+                let kb: u64 = fields.next()?.parse().ok()?;
+                Some((key, kb * 1024))
+            })
+            .collect();
+        let numastat: HashMap<String, u64> = fs::read_to_string(format!("{}/numastat", path))
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| {
+                let (key, value) = line.split_once(' ')?;
+                Some((key.to_string(), value.trim().parse().ok()?))
+            })
+            .collect();
+        let memory = |key: &str| meminfo.get(key).copied().unwrap_or_default();
+        let stat = |key: &str| numastat.get(key).copied().unwrap_or_default();
+        let memory_total = memory("MemTotal");
+        NumaNode {
+            id,
+            cpus,
+            memory_total,
+            memory_used: memory_total
+                .saturating_sub(memory("MemFree"))
+                .saturating_sub(memory("FilePages"))
+                .saturating_sub(memory("SReclaimable")),
+            numa_hit: stat("numa_hit"),
+            numa_miss: stat("numa_miss"),
+            numa_foreign: stat("numa_foreign"),
+            local_node: stat("local_node"),
+            other_node: stat("other_node"),
+        }
+    }
+
+    pub fn percent_memory_used(&self) -> f32 {
+        if self.memory_total == 0 {
+            return 0.0;
+        }
+        self.memory_used as f32 * 100.0 / self.memory_total as f32
+    }
+
+    /// Share of the allocations of this node that missed it.
+    pub fn percent_miss(&self) -> f32 {
+        let total = self.numa_hit + self.numa_miss;
+        if total == 0 {
+            return 0.0;
+        }
+        self.numa_miss as f32 * 100.0 / total as f32
+    }
+}
+
+/// Parses a kernel CPU list such as `0-3,8-11`.
+fn parse_cpu_list(list: &str) -> Vec<usize> {
+    list.split(',')
+        .filter_map(|range| match range.split_once('-') {
+            Some((start, end)) => Some((start.parse().ok()?..=end.parse().ok()?).collect::<Vec<_>>()),
+            None => Some(vec![range.parse().ok()?]),
+        })
+        .flatten()
+        .collect()
+}
@@ -0,0 +1,72 @@
+use std::{fs, io};
+
+use crate::cmd::{no_procfs, PROCFS};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FileKind {
+    File,
+    Directory,
+    Device,
+    Socket,
+    Pipe,
+    Anonymous,
+}
+
+impl FileKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FileKind::File => "file",
+            FileKind::Directory => "dir",
+            FileKind::Device => "device",
+            FileKind::Socket => "socket",
+            FileKind::Pipe => "pipe",
+            FileKind::Anonymous => "anon",
+        }
+    }
+}
+
+/// A file descriptor held by a process, read from `/proc/<pid>/fd`.
+#[derive(Debug, Clone)]
+pub struct OpenFile {
+    pub fd: u32,
+    pub kind: FileKind,
+    pub target: String,
+}
+
+impl OpenFile {
+    /// Lists the descriptors of a process ordered by number. Only the owner
+    /// of the process and root may read them.
+    pub fn read_all(pid: u32) -> io::Result<Vec<OpenFile>> {
+        if !PROCFS {
+            return Err(no_procfs());
+        }
+        let mut files: Vec<OpenFile> = fs::read_dir(format!("/proc/{}/fd", pid))?
+            .flatten()
+            .filter_map(|entry| {
+                let fd = entry.file_name().to_str()?.parse().ok()?;
+                // The descriptor may be closed between listing and reading it
+                let target = fs::read_link(entry.path()).ok()?.to_string_lossy().into_owned();
+                let kind = Self::kind_of(&target, &entry.path());
+                Some(OpenFile { fd, kind, target })
+            })
+            .collect();
+        files.sort_by_key(|file| file.fd);
+        Ok(files)
+    }
+
+    fn kind_of(target: &str, path: &std::path::Path) -> FileKind {
+        if target.starts_with("socket:") {
+            FileKind::Socket
+        } else if target.starts_with("pipe:") {
+            FileKind::Pipe
+        } else if target.starts_with("anon_inode:") {
+            FileKind::Anonymous
+        } else if target.starts_with("/dev/") {
+            FileKind::Device
+        } else if fs::metadata(path).is_ok_and(|metadata| metadata.is_dir()) {
+            FileKind::Directory
+        } else {
+            FileKind::File
+        }
+    }
+}
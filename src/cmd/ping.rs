@@ -0,0 +1,43 @@
+//! Round trip times to the hosts listed under `ping.hosts`.
+use std::{net::{IpAddr, SocketAddr}, time::Duration};
+use serde::{Deserialize, Serialize};
+
+use crate::cmd::reachability;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PingTarget {
+    /// As written in the config.
+    pub host: String,
+    /// `None` when the host didn't answer or couldn't be resolved.
+    pub rtt: Option<Duration>,
+}
+
+/// Port probed when the host is given without one.
+const DEFAULT_PORT: u16 = 443;
+
+impl PingTarget {
+    /// Probes every host at once, each given `timeout` to answer.
+    pub async fn probe_all(hosts: &[String], timeout: Duration) -> Vec<PingTarget> {
+        let rtts = futures_util::future::join_all(hosts.iter().map(|host| async move {
+            let address = resolve(host).await?;
+            reachability::probe(address.ip(), address.port(), timeout).await
+        })).await;
+        hosts.iter()
+            .zip(rtts)
+            .map(|(host, rtt)| PingTarget { host: host.clone(), rtt })
+            .collect()
+    }
+}
+
+/// An address, a name or either with a port, such as `1.1.1.1`,
+/// `example.com:80` or `[::1]:22`.
+async fn resolve(host: &str) -> Option<SocketAddr> {
+    if let Ok(address) = host.parse::<IpAddr>() {
+        return Some(SocketAddr::new(address, DEFAULT_PORT));
+    }
+    let with_port = match host.rsplit_once(':') {
+        Some((_, port)) if port.parse::<u16>().is_ok() => host.to_string(),
+        _ => format!("{}:{}", host, DEFAULT_PORT),
+    };
+    tokio::net::lookup_host(with_port).await.ok()?.next()
+}
@@ -0,0 +1,77 @@
+use std::fs;
+use serde::{Deserialize, Serialize};
+
+const RESOURCES: [&str; 3] = ["cpu", "memory", "io"];
+
+/// Share of time some tasks were stalled on a resource, averaged over the
+/// last 10, 60 and 300 seconds, from `/proc/pressure/<resource>`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Pressure {
+    pub resource: String,
+    pub avg10: f32,
+    pub avg60: f32,
+    pub avg300: f32,
+}
+
+impl Pressure {
+    /// Returns an empty list on kernels built without PSI.
+    pub fn read_all() -> Vec<Pressure> {
+        RESOURCES.iter().filter_map(|resource| Self::read(resource)).collect()
+    }
+
+    fn read(resource: &str) -> Option<Pressure> {
+        let content = fs::read_to_string(format!("/proc/pressure/{}", resource)).ok()?;
+        Self::parse(resource, &content)
+    }
+
+    /// Reads the `some` line, the one every resource has.
+    fn parse(resource: &str, content: &str) -> Option<Pressure> {
+        let some = content.lines().find(|line| line.starts_with("some "))?;
+        let mut pressure = Pressure {
+            resource: resource.to_string(),
+            ..Default::default()
+        };
+        for field in some.split_whitespace().skip(1) {
+            let Some((key, value)) = field.split_once('=') else {
+                continue;
+            };
+            let value = value.parse().unwrap_or(0.0);
+            match key {
+                "avg10" => pressure.avg10 = value,
+                "avg60" => pressure.avg60 = value,
+                "avg300" => pressure.avg300 = value,
+                _ => {}
+            }
+        }
+        Some(pressure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn averages_come_from_the_some_line() {
+        let content = "some avg10=1.53 avg60=0.87 avg300=0.27 total=217453110\n\
+                       full avg10=0.80 avg60=0.41 avg300=0.12 total=119363445\n";
+        let pressure = Pressure::parse("memory", content).unwrap();
+        assert_eq!(pressure.resource, "memory");
+        assert_eq!((pressure.avg10, pressure.avg60, pressure.avg300), (1.53, 0.87, 0.27));
+    }
+
+    #[test]
+    fn cpu_without_a_full_line() {
+        // Kernels before 5.13 only have the some line for cpu
+        let pressure = Pressure::parse("cpu", "some avg10=12.00 avg60=3.50 avg300=0.00 total=5\n").unwrap();
+        assert_eq!((pressure.avg10, pressure.avg60, pressure.avg300), (12.0, 3.5, 0.0));
+    }
+
+    #[test]
+    fn unreadable_values_count_as_zero() {
+        let pressure = Pressure::parse("io", "some avg10=x avg60 avg300=2.00\n").unwrap();
+        assert_eq!((pressure.avg10, pressure.avg60, pressure.avg300), (0.0, 0.0, 2.0));
+        assert!(Pressure::parse("io", "full avg10=1.00 avg60=0.00 avg300=0.00 total=1\n").is_none());
+        assert!(Pressure::parse("io", "").is_none());
+    }
+}
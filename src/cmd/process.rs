@@ -1,10 +1,45 @@
-#[derive(Debug, Default, Clone)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Process {
     pub pid: u32,
     pub process_name: String,
     pub user: String,
     pub cpu_usage: f32,
     pub mem_usage: f32,
+    #[serde(default)]
+    pub memory: u64,
+    #[serde(default)]
+    pub kernel_thread: bool,
+    #[serde(default)]
+    pub container: Option<String>,
+    #[serde(default)]
+    pub cgroup: Option<String>,
+    /// Command line joined by spaces, empty for kernel threads.
+    #[serde(default)]
+    pub command: String,
+    /// One letter state as in `ps`, e.g. `R` running or `S` sleeping.
+    #[serde(default)]
+    pub state: String,
+    #[serde(default)]
+    pub nice: i32,
+    /// Bytes per second over the last disk refresh interval.
+    #[serde(default)]
+    pub disk_read: f64,
+    #[serde(default)]
+    pub disk_write: f64,
+    /// Seconds since the epoch.
+    #[serde(default)]
+    pub start_time: u64,
+    /// Seconds since the process started.
+    #[serde(default)]
+    pub run_time: u64,
+    #[serde(default)]
+    pub parent: Option<u32>,
+    /// PID of the process a userland thread belongs to, the thread's own
+    /// `pid` is its TID.
+    #[serde(default)]
+    pub thread_of: Option<u32>,
 }
 
 impl Process {    
@@ -33,19 +68,133 @@ impl Process {
         self
     }
     
+    pub fn set_memory(mut self, memory: u64) -> Self {
+        self.memory = memory;
+        self
+    }
+    
+    pub fn set_kernel_thread(mut self, kernel_thread: bool) -> Self {
+        self.kernel_thread = kernel_thread;
+        self
+    }
+    
+    pub fn set_container(mut self, container: Option<String>) -> Self {
+        self.container = container;
+        self
+    }
+    
+    pub fn set_cgroup(mut self, cgroup: Option<String>) -> Self {
+        self.cgroup = cgroup;
+        self
+    }
+    
+    pub fn set_command(mut self, command: String) -> Self {
+        self.command = command;
+        self
+    }
+    
+    pub fn set_state(mut self, state: String) -> Self {
+        self.state = state;
+        self
+    }
+    
+    pub fn set_nice(mut self, nice: i32) -> Self {
+        self.nice = nice;
+        self
+    }
+    
+    pub fn set_disk_io(mut self, disk_read: f64, disk_write: f64) -> Self {
+        self.disk_read = disk_read;
+        self.disk_write = disk_write;
+        self
+    }
+    
+    pub fn set_start_time(mut self, start_time: u64) -> Self {
+        self.start_time = start_time;
+        self
+    }
+    
+    pub fn set_run_time(mut self, run_time: u64) -> Self {
+        self.run_time = run_time;
+        self
+    }
+    
+    pub fn set_parent(mut self, parent: Option<u32>) -> Self {
+        self.parent = parent;
+        self
+    }
+    
+    pub fn set_thread_of(mut self, thread_of: Option<u32>) -> Self {
+        self.thread_of = thread_of;
+        self
+    }
+    
     pub fn build(self) -> Result<Process, ()> {
         Ok(Process {
             pid: self.pid,
             process_name: self.process_name,
             user: self.user,
             cpu_usage: self.cpu_usage,
-            mem_usage: self.mem_usage
+            mem_usage: self.mem_usage,
+            memory: self.memory,
+            kernel_thread: self.kernel_thread,
+            container: self.container,
+            cgroup: self.cgroup,
+            command: self.command,
+            state: self.state,
+            nice: self.nice,
+            disk_read: self.disk_read,
+            disk_write: self.disk_write,
+            start_time: self.start_time,
+            run_time: self.run_time,
+            parent: self.parent,
+            thread_of: self.thread_of,
         })
     }
     
-    pub fn sort_most_consume_cpu(processes: &mut Vec<Process>) {
-        processes.sort_by(|a, b| b.cpu_usage
-                            .partial_cmp(&a.cpu_usage)
-                            .unwrap_or(std::cmp::Ordering::Equal));
+    /// Copies the changed fields of a newer sample of the same process.
+    pub fn update(&mut self, process: Process) {
+        if self.process_name != process.process_name {
+            self.process_name = process.process_name;
+        }
+        if self.user != process.user {
+            self.user = process.user;
+        }
+        self.cpu_usage = process.cpu_usage;
+        self.mem_usage = process.mem_usage;
+        self.memory = process.memory;
+        self.kernel_thread = process.kernel_thread;
+        if self.container != process.container {
+            self.container = process.container;
+        }
+        if self.cgroup != process.cgroup {
+            self.cgroup = process.cgroup;
+        }
+        if self.command != process.command {
+            self.command = process.command;
+        }
+        if self.state != process.state {
+            self.state = process.state;
+        }
+        self.nice = process.nice;
+        self.disk_read = process.disk_read;
+        self.disk_write = process.disk_write;
+        self.start_time = process.start_time;
+        self.run_time = process.run_time;
+        self.parent = process.parent;
+        self.thread_of = process.thread_of;
+    }
+
+    /// The thread group, which is the PID itself for a process.
+    pub fn tgid(&self) -> u32 {
+        self.thread_of.unwrap_or(self.pid)
+    }
+
+    /// Kernel threads are bracketed as in `ps`, e.g. `[kworker/0:1]`.
+    pub fn display_name(&self) -> String {
+        match self.kernel_thread {
+            true => format!("[{}]", self.process_name),
+            false => self.process_name.clone(),
+        }
     }
 }
\ No newline at end of file
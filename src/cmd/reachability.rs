@@ -0,0 +1,81 @@
+//! Default gateway and DNS servers of the machine and whether they answer.
+use std::{fs, io, net::{IpAddr, Ipv4Addr, SocketAddr}, time::{Duration, Instant}};
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpStream;
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Reachability {
+    pub gateway: Option<Endpoint>,
+    pub dns: Vec<Endpoint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Endpoint {
+    pub address: String,
+    pub up: bool,
+}
+
+impl Reachability {
+    /// Port probed on the gateway and the DNS servers, which serve DNS over
+    /// TCP. A gateway that refuses the connection still counts as up.
+    const PORT: u16 = 53;
+
+    /// Reads the gateway and the DNS servers and probes all of them at once,
+    /// each given `timeout` to answer.
+    pub async fn probe_all(timeout: Duration) -> Self {
+        let gateway = default_gateway();
+        let dns = nameservers();
+        let (gateway_up, dns_up) = tokio::join!(
+            async {
+                match gateway {
+                    Some(address) => Some(probe(address, Self::PORT, timeout).await.is_some()),
+                    None => None,
+                }
+            },
+            futures_util::future::join_all(dns.iter().map(|address| probe(*address, Self::PORT, timeout))),
+        );
+        Self {
+            gateway: gateway.zip(gateway_up).map(|(address, up)| Endpoint { address: address.to_string(), up }),
+            dns: dns.iter()
+                .zip(dns_up)
+                .map(|(address, rtt)| Endpoint { address: address.to_string(), up: rtt.is_some() })
+                .collect(),
+        }
+    }
+}
+
+/// Time taken by `address` to answer a TCP connection on `port`, `None`
+/// when it doesn't within `timeout`. Refusing the connection is an answer,
+/// so this needs neither privileges nor a listening service.
+pub async fn probe(address: IpAddr, port: u16, timeout: Duration) -> Option<Duration> {
+    let start = Instant::now();
+    match tokio::time::timeout(timeout, TcpStream::connect(SocketAddr::new(address, port))).await {
+        Ok(Ok(_)) => Some(start.elapsed()),
+        Ok(Err(err)) if err.kind() == io::ErrorKind::ConnectionRefused => Some(start.elapsed()),
+        _ => None,
+    }
+}
+
+/// The gateway of the IPv4 default route in `/proc/net/route`, which
+/// prints addresses as hex numbers in host byte order.
+fn default_gateway() -> Option<IpAddr> {
+    let content = fs::read_to_string("/proc/net/route").ok()?;
+    content.lines().skip(1).find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.get(1) != Some(&"00000000") {
+            return None;
+        }
+        let gateway = u32::from_str_radix(fields.get(2)?, 16).ok()?;
+        Some(IpAddr::V4(Ipv4Addr::from(gateway.to_ne_bytes())))
+    })
+}
+
+fn nameservers() -> Vec<IpAddr> {
+    let Ok(content) = fs::read_to_string("/etc/resolv.conf") else {
+        return Vec::new();
+    };
+    content.lines()
+        .filter_map(|line| line.strip_prefix("nameserver"))
+        .filter_map(|address| address.trim().parse().ok())
+        .collect()
+}
@@ -0,0 +1,97 @@
+use std::{process::Stdio, time::Duration};
+use tokio::{io::{AsyncBufReadExt, AsyncRead, BufReader}, net::TcpStream, process::Command};
+
+use crate::cmd::{hub::Publisher, shutdown::Shutdown, Message};
+
+/// Where the data of a remote machine comes from.
+#[derive(Debug, Clone)]
+pub enum Remote {
+    /// An agent listening on `host:port`.
+    Agent(String),
+    /// An agent started over SSH on `user@host` with `command`, which writes
+    /// to its stdout.
+    Ssh { destination: String, command: String },
+}
+
+/// Time between attempts to reach an SSH host, longer than for an agent
+/// since every attempt logs in.
+const SSH_RETRY: Duration = Duration::from_secs(5);
+
+pub fn connect(publisher: Publisher, remote: Remote, shutdown: &Shutdown) {
+    match remote {
+        Remote::Agent(addr) => connect_remote(publisher, addr, shutdown),
+        Remote::Ssh { destination, command } => connect_ssh(publisher, destination, command, shutdown),
+    }
+}
+
+/// Receives messages from a remote agent, one JSON encoded `Message` per
+/// line, and publishes them locally. Reconnects when the connection drops.
+fn connect_remote(publisher: Publisher, addr: String, shutdown: &Shutdown) {
+    shutdown.spawn(async move {
+        loop {
+            match TcpStream::connect(&addr).await {
+                Ok(stream) => forward(&publisher, stream, &addr).await,
+                Err(err) => log::warn!("Error connecting to {}: {}", addr, err),
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+    });
+}
+
+/// Runs `command` on `destination` through `ssh` and publishes the messages
+/// it prints, starting it again when it exits. ssh runs in batch mode since
+/// a password prompt would draw over the UI, so the host needs key or agent
+/// authentication.
+fn connect_ssh(publisher: Publisher, destination: String, command: String, shutdown: &Shutdown) {
+    shutdown.spawn(async move {
+        loop {
+            let child = Command::new("ssh")
+                .args(["-T", "-o", "BatchMode=yes", &destination, &command])
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .kill_on_drop(true)
+                .spawn();
+            match child {
+                Ok(mut child) => {
+                    let stdout = child.stdout.take().unwrap();
+                    let stderr = child.stderr.take().unwrap();
+                    let (_, error) = tokio::join!(forward(&publisher, stdout, &destination), last_line(stderr));
+                    match child.wait().await {
+                        Ok(status) if !status.success() => {
+                            log::warn!("ssh to {} exited with {}: {}", destination, status, error.unwrap_or_default());
+                        }
+                        Ok(_) => {}
+                        Err(err) => log::warn!("Error waiting for ssh to {}: {}", destination, err),
+                    }
+                }
+                Err(err) => log::warn!("Error running ssh: {}", err),
+            }
+            tokio::time::sleep(SSH_RETRY).await;
+        }
+    });
+}
+
+/// Publishes every message read from `reader` until it closes.
+async fn forward<R: AsyncRead + Unpin>(publisher: &Publisher, reader: R, source: &str) {
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        match serde_json::from_str::<Message>(&line) {
+            Ok(msg) => publisher.publish(msg),
+            Err(err) => log::warn!("Invalid message from {}: {}", source, err),
+        }
+    }
+}
+
+/// Last line ssh or the remote command wrote to stderr, which explains why
+/// it exited.
+async fn last_line<R: AsyncRead + Unpin>(reader: R) -> Option<String> {
+    let mut lines = BufReader::new(reader).lines();
+    let mut last = None;
+    while let Ok(Some(line)) = lines.next_line().await {
+        if !line.trim().is_empty() {
+            last = Some(line);
+        }
+    }
+    last
+}
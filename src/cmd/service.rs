@@ -0,0 +1,120 @@
+use std::{path::Path, process::{Command, Stdio}};
+use serde::{Deserialize, Serialize};
+
+/// Created by systemd when it is the init system, as checked by
+/// `sd_booted(3)`.
+const SYSTEMD_RUNTIME: &str = "/run/systemd/system";
+/// Properties requested from `systemctl show` for every unit.
+const PROPERTIES: &str = "Id,Description,ActiveState,SubState,MainPID,MemoryCurrent,CPUUsageNSec";
+
+/// A systemd service unit, read by parsing `systemctl` output.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Service {
+    pub name: String,
+    pub description: String,
+    pub active_state: String,
+    pub sub_state: String,
+    pub main_pid: Option<u32>,
+    /// Memory of the whole unit cgroup in bytes, when accounting is enabled.
+    pub memory: Option<u64>,
+    /// CPU time of the whole unit cgroup in nanoseconds.
+    pub cpu_time: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ServiceAction {
+    Start,
+    Stop,
+    Restart,
+}
+
+impl ServiceAction {
+    pub fn verb(&self) -> &'static str {
+        match self {
+            ServiceAction::Start => "start",
+            ServiceAction::Stop => "stop",
+            ServiceAction::Restart => "restart",
+        }
+    }
+
+    /// Runs `systemctl <verb> <unit>` and returns its error output on failure.
+    pub async fn run(self, unit: String) -> Result<(), String> {
+        let output = tokio::process::Command::new("systemctl")
+            .arg(self.verb())
+            .arg("--no-ask-password")
+            .arg(&unit)
+            .stdin(Stdio::null())
+            .output()
+            .await
+            .map_err(|err| err.to_string())?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+        }
+    }
+}
+
+impl Service {
+    /// Whether systemd is the init system, false in most containers.
+    pub fn available() -> bool {
+        Path::new(SYSTEMD_RUNTIME).exists()
+    }
+
+    /// Lists every loaded service unit. None when a `systemctl` call fails.
+    pub fn read_all() -> Option<Vec<Service>> {
+        let names = systemctl(&["list-units", "--type=service", "--all", "--no-legend", "--plain", "--no-pager"])?
+            .lines()
+            .filter_map(|line| line.split_whitespace().next())
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        if names.is_empty() {
+            return Some(Vec::new());
+        }
+        let mut args = vec!["show", "--no-pager", "-p", PROPERTIES];
+        args.extend(names.iter().map(String::as_str));
+        let output = systemctl(&args)?;
+        // One block of key=value lines per unit, separated by blank lines
+        let mut services: Vec<Service> = output
+            .split("\n\n")
+            .filter_map(Self::parse)
+            .collect();
+        services.sort_by(|a, b| a.name.cmp(&b.name));
+        Some(services)
+    }
+
+    fn parse(block: &str) -> Option<Service> {
+        let mut service = Service::default();
+        for line in block.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            match key {
+                "Id" => service.name = value.to_string(),
+                "Description" => service.description = value.to_string(),
+                "ActiveState" => service.active_state = value.to_string(),
+                "SubState" => service.sub_state = value.to_string(),
+                "MainPID" => service.main_pid = value.parse().ok().filter(|pid| *pid != 0),
+                "MemoryCurrent" => service.memory = accounted(value),
+                "CPUUsageNSec" => service.cpu_time = accounted(value),
+                _ => {}
+            }
+        }
+        (!service.name.is_empty()).then_some(service)
+    }
+}
+
+/// systemd reports `[not set]` or `u64::MAX` when accounting is disabled.
+fn accounted(value: &str) -> Option<u64> {
+    value.parse().ok().filter(|value| *value != u64::MAX)
+}
+
+fn systemctl(args: &[&str]) -> Option<String> {
+    let output = Command::new("systemctl")
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,37 @@
+use std::future::Future;
+use tokio_util::{sync::CancellationToken, task::TaskTracker};
+
+/// Tracks the collector tasks so they can be cancelled together and awaited
+/// before the runtime exits. Tasks are dropped at their next `.await` once
+/// `stop` is called.
+#[derive(Clone, Default)]
+pub struct Shutdown {
+    token: CancellationToken,
+    tracker: TaskTracker,
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn spawn<F>(&self, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let token = self.token.clone();
+        self.tracker.spawn(async move {
+            tokio::select! {
+                _ = token.cancelled() => {}
+                _ = task => {}
+            }
+        });
+    }
+
+    /// Cancels every task and waits for them to finish.
+    pub async fn stop(&self) {
+        self.token.cancel();
+        self.tracker.close();
+        self.tracker.wait().await;
+    }
+}
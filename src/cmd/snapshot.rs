@@ -0,0 +1,63 @@
+use serde::Serialize;
+
+use crate::cmd::{battery::Battery, connection::Connection, disk::{DeviceIo, Disk, TotalIo}, fan::Fan, gpu::Gpu, interrupts::Interrupt, memory::{KernelMemory, Memory}, network::Network, numa::NumaNode, ping::PingTarget, pressure::Pressure, process::Process, reachability::Reachability, service::Service, temperature::Temperature, throttle::Throttling, traffic::ProcessTraffic, Message};
+
+/// Latest value received from every collector, as seen by consumers outside
+/// the TUI such as the metrics servers.
+#[derive(Default, Clone, Serialize)]
+pub struct Snapshot {
+    pub processes: Vec<Process>,
+    pub cores_usage: Vec<f32>,
+    pub cores_frequency: Vec<u64>,
+    pub mem_usage: Memory,
+    pub kernel_memory: KernelMemory,
+    pub numa_nodes: Vec<NumaNode>,
+    pub interrupts: Vec<Interrupt>,
+    pub network: Network,
+    pub connections: Vec<Connection>,
+    pub services: Vec<Service>,
+    pub process_traffic: Vec<ProcessTraffic>,
+    pub disks: Vec<Disk>,
+    pub disk_io: TotalIo,
+    pub device_io: Vec<DeviceIo>,
+    pub temperatures: Vec<Temperature>,
+    pub fans: Vec<Fan>,
+    pub gpus: Vec<Gpu>,
+    pub batteries: Vec<Battery>,
+    pub throttling: Option<Throttling>,
+    pub general_infos: Vec<String>,
+    pub pressures: Vec<Pressure>,
+    pub reachability: Reachability,
+    pub ping: Vec<PingTarget>,
+}
+
+impl Snapshot {
+    /// Messages that bring a fresh consumer up to the current state.
+    pub fn messages(&self) -> Vec<Message> {
+        vec![
+            Message::Processes(self.processes.clone()),
+            Message::CpuUsage(self.cores_usage.clone()),
+            Message::CpuFrequency(self.cores_frequency.clone()),
+            Message::MemUsage(self.mem_usage),
+            Message::KernelMemory(self.kernel_memory),
+            Message::Numa(self.numa_nodes.clone()),
+            Message::Interrupts(self.interrupts.clone()),
+            Message::Network(self.network.clone()),
+            Message::Connections(self.connections.clone()),
+            Message::Services(self.services.clone()),
+            Message::ProcessTraffic(self.process_traffic.clone()),
+            Message::DiskUsage(self.disks.clone()),
+            Message::DiskIO(self.disk_io),
+            Message::DeviceIo(self.device_io.clone()),
+            Message::Temperature(self.temperatures.clone()),
+            Message::Fan(self.fans.clone()),
+            Message::Gpu(self.gpus.clone()),
+            Message::Battery(self.batteries.clone()),
+            Message::Throttling(self.throttling),
+            Message::GeneralInfo(self.general_infos.clone()),
+            Message::Pressure(self.pressures.clone()),
+            Message::Reachability(self.reachability.clone()),
+            Message::Ping(self.ping.clone()),
+        ]
+    }
+}
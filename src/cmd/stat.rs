@@ -0,0 +1,63 @@
+use std::{fs, time::Instant};
+
+/// Scheduler counters of `/proc/stat`.
+#[derive(Debug, Clone, Copy)]
+struct KernelStat {
+    context_switches: u64,
+    forks: u64,
+    running: u64,
+    blocked: u64,
+}
+
+impl KernelStat {
+    fn read() -> Option<Self> {
+        let content = fs::read_to_string("/proc/stat").ok()?;
+        let field = |key: &str| {
+            content.lines()
+                .find_map(|line| line.strip_prefix(key))
+                .and_then(|value| value.trim().parse().ok())
+        };
+        Some(Self {
+            context_switches: field("ctxt ")?,
+            forks: field("processes ")?,
+            running: field("procs_running ")?,
+            blocked: field("procs_blocked ")?,
+        })
+    }
+}
+
+/// Scheduler activity since the previous sample.
+pub struct SchedulerRates {
+    pub context_switches: f64,
+    pub forks: f64,
+    /// Tasks on a run queue and tasks waiting on I/O right now.
+    pub running: u64,
+    pub blocked: u64,
+}
+
+/// Keeps the previous `/proc/stat` sample to turn its counters into rates.
+#[derive(Default)]
+pub struct SchedulerMeter {
+    previous: Option<(KernelStat, Instant)>,
+}
+
+impl SchedulerMeter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `None` on the first sample and where `/proc/stat` is missing.
+    pub fn measure(&mut self) -> Option<SchedulerRates> {
+        let stat = KernelStat::read()?;
+        let now = Instant::now();
+        let previous = self.previous.replace((stat, now));
+        let (previous, at) = previous?;
+        let seconds = now.duration_since(at).as_secs_f64().max(f64::EPSILON);
+        Some(SchedulerRates {
+            context_switches: stat.context_switches.saturating_sub(previous.context_switches) as f64 / seconds,
+            forks: stat.forks.saturating_sub(previous.forks) as f64 / seconds,
+            running: stat.running,
+            blocked: stat.blocked,
+        })
+    }
+}
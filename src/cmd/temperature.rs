@@ -1,4 +1,6 @@
-#[derive(Default, Clone)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct Temperature {
     pub label: String,
     pub value: f32,
@@ -6,6 +8,14 @@ pub struct Temperature {
     pub critical: f32,
 }
 
+/// How hot a reading is compared to the limits it is checked against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Normal,
+    High,
+    Critical,
+}
+
 impl Temperature {
     pub fn new(label: String, value: f32, max: f32, critical: f32) -> Self {
         Self {
@@ -15,4 +25,17 @@ impl Temperature {
             critical
         }
     }
+
+    /// `High` from `threshold`, `Critical` from the critical value of the
+    /// sensor. `max` isn't a limit: sysinfo raises it to the highest
+    /// reading seen.
+    pub fn level(&self, threshold: f32) -> Level {
+        if self.critical > 0.0 && self.value >= self.critical {
+            Level::Critical
+        } else if self.value >= threshold {
+            Level::High
+        } else {
+            Level::Normal
+        }
+    }
 }
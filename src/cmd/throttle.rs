@@ -0,0 +1,64 @@
+use std::{fs, process::{Command, Stdio}};
+use serde::{Deserialize, Serialize};
+
+/// Flags of the Raspberry Pi firmware, exposed by its kernel driver.
+const GET_THROTTLED: &str = "/sys/devices/platform/soc/soc:firmware/get_throttled";
+/// The bit of a condition that is active now. The same bit shifted by this
+/// much is set once it has happened since boot.
+const OCCURRED_SHIFT: u32 = 16;
+const CONDITIONS: [(u32, &str); 4] = [
+    (0, "under-voltage"),
+    (1, "frequency capped"),
+    (2, "throttled"),
+    (3, "soft temperature limit"),
+];
+
+/// Throttling reported by the firmware of a Raspberry Pi, which slows the
+/// CPU down for low voltage or heat long before the temperature looks
+/// alarming.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Throttling {
+    pub flags: u32,
+}
+
+impl Throttling {
+    /// Reads the flags from sysfs, or from `vcgencmd get_throttled` on
+    /// kernels without the attribute. None on other machines.
+    pub fn read() -> Option<Throttling> {
+        let flags = match fs::read_to_string(GET_THROTTLED) {
+            Ok(flags) => u32::from_str_radix(flags.trim(), 16).ok()?,
+            Err(_) => vcgencmd()?,
+        };
+        Some(Throttling { flags })
+    }
+
+    /// Conditions in effect right now.
+    pub fn active(&self) -> Vec<&'static str> {
+        CONDITIONS.iter()
+            .filter(|(bit, _)| self.flags & (1 << bit) != 0)
+            .map(|(_, name)| *name)
+            .collect()
+    }
+
+    /// Conditions that happened since boot but are over.
+    pub fn occurred(&self) -> Vec<&'static str> {
+        CONDITIONS.iter()
+            .filter(|(bit, _)| self.flags & (1 << bit) == 0 && self.flags & (1 << (bit + OCCURRED_SHIFT)) != 0)
+            .map(|(_, name)| *name)
+            .collect()
+    }
+}
+
+/// Parses `throttled=0x50005`.
+fn vcgencmd() -> Option<u32> {
+    let output = Command::new("vcgencmd")
+        .arg("get_throttled")
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let output = String::from_utf8_lossy(&output.stdout);
+    let flags = output.trim().strip_prefix("throttled=0x")?;
+    u32::from_str_radix(flags, 16).ok()
+}
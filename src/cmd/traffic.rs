@@ -0,0 +1,288 @@
+//! Network traffic per process. The kernel keeps byte counters for every
+//! TCP socket, read here with a `sock_diag` netlink dump and summed per
+//! owning process. UDP sockets keep no such counters and are not counted.
+//! Other kernels have no `sock_diag`, there per process traffic stays off.
+use std::{collections::HashMap, io, time::Duration};
+#[cfg(target_os = "linux")]
+use std::mem;
+use serde::{Deserialize, Serialize};
+
+use crate::cmd::connection::socket_owners;
+
+#[cfg(target_os = "linux")]
+const SOCK_DIAG_BY_FAMILY: u16 = 20;
+#[cfg(target_os = "linux")]
+const INET_DIAG_INFO: u16 = 2;
+#[cfg(target_os = "linux")]
+const NLMSG_ERROR: u16 = 2;
+#[cfg(target_os = "linux")]
+const NLMSG_DONE: u16 = 3;
+/// Size of `struct nlmsghdr`.
+#[cfg(target_os = "linux")]
+const HEADER_LEN: usize = 16;
+/// Size of `struct inet_diag_msg`, the inode is its last field.
+#[cfg(target_os = "linux")]
+const DIAG_MSG_LEN: usize = 72;
+#[cfg(target_os = "linux")]
+const DIAG_MSG_INODE: usize = 68;
+/// Offsets of `tcpi_bytes_acked` and `tcpi_bytes_received` in `struct tcp_info`.
+#[cfg(target_os = "linux")]
+const TCP_INFO_BYTES_ACKED: usize = 120;
+#[cfg(target_os = "linux")]
+const TCP_INFO_BYTES_RECEIVED: usize = 128;
+
+/// Download and upload rate of a process in bytes per second.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct ProcessTraffic {
+    pub pid: u32,
+    pub rx: f64,
+    pub tx: f64,
+}
+
+/// Byte counters of a socket, `(received, sent)`.
+type Counters = (u64, u64);
+
+/// Rates of a socket in bytes per second, `(received, sent)`.
+pub type Rates = (f64, f64);
+
+/// Turns the counters of consecutive socket dumps into rates per socket.
+#[derive(Default)]
+pub struct SocketMeter {
+    previous: Option<HashMap<u64, Counters>>,
+}
+
+impl SocketMeter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Dumps the TCP sockets and returns their rates since the previous
+    /// call, `elapsed` apart, by inode. Sockets opened in between count
+    /// from zero and the first call only records the counters.
+    pub fn measure(&mut self, elapsed: Duration) -> io::Result<HashMap<u64, Rates>> {
+        let sockets = dump_tcp()?;
+        let seconds = elapsed.as_secs_f64().max(f64::EPSILON);
+        let rates = match self.previous.as_ref() {
+            None => HashMap::new(),
+            Some(previous) => sockets.iter()
+                .map(|(inode, (received, sent))| {
+                    let (previous_received, previous_sent) = previous.get(inode).copied().unwrap_or_default();
+                    let rates = (
+                        received.saturating_sub(previous_received) as f64 / seconds,
+                        sent.saturating_sub(previous_sent) as f64 / seconds,
+                    );
+                    (*inode, rates)
+                })
+                .collect(),
+        };
+        self.previous = Some(sockets);
+        Ok(rates)
+    }
+}
+
+/// Sums the socket rates per owning process.
+#[derive(Default)]
+pub struct TrafficMeter {
+    sockets: SocketMeter,
+}
+
+impl TrafficMeter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Traffic of every process since the previous call, `elapsed` apart.
+    pub fn measure(&mut self, elapsed: Duration) -> io::Result<Vec<ProcessTraffic>> {
+        let rates = self.sockets.measure(elapsed)?;
+        let owners = socket_owners();
+        let mut traffic: HashMap<u32, ProcessTraffic> = HashMap::new();
+        for (inode, (rx, tx)) in &rates {
+            let Some((pid, _)) = owners.get(inode) else {
+                continue;
+            };
+            let entry = traffic.entry(*pid).or_insert(ProcessTraffic { pid: *pid, ..Default::default() });
+            entry.rx += rx;
+            entry.tx += tx;
+        }
+        Ok(traffic.into_values().collect())
+    }
+}
+
+/// Counters of every TCP socket over IPv4 and IPv6, by inode.
+#[cfg(target_os = "linux")]
+fn dump_tcp() -> io::Result<HashMap<u64, Counters>> {
+    let mut sockets = dump_tcp_family(libc::AF_INET as u8)?;
+    sockets.extend(dump_tcp_family(libc::AF_INET6 as u8)?);
+    Ok(sockets)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn dump_tcp() -> io::Result<HashMap<u64, Counters>> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "socket counters are only readable on Linux"))
+}
+
+/// Counters of every TCP socket of one address family, by inode.
+#[cfg(target_os = "linux")]
+fn dump_tcp_family(family: u8) -> io::Result<HashMap<u64, Counters>> {
+    // SAFETY: plain socket syscalls on a descriptor owned by this function
+    let fd = unsafe { libc::socket(libc::AF_NETLINK, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, libc::NETLINK_SOCK_DIAG) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let result = send_request(fd, family).and_then(|()| read_dump(fd));
+    // SAFETY: fd was opened above and is not used afterwards
+    unsafe { libc::close(fd) };
+    result
+}
+
+#[cfg(target_os = "linux")]
+fn send_request(fd: i32, family: u8) -> io::Result<()> {
+    // nlmsghdr followed by inet_diag_req_v2 with a zeroed socket id
+    let mut request = [0u8; HEADER_LEN + 56];
+    let len = request.len() as u32;
+    request[0..4].copy_from_slice(&len.to_ne_bytes());
+    request[4..6].copy_from_slice(&SOCK_DIAG_BY_FAMILY.to_ne_bytes());
+    let flags = (libc::NLM_F_REQUEST | libc::NLM_F_DUMP) as u16;
+    request[6..8].copy_from_slice(&flags.to_ne_bytes());
+    request[HEADER_LEN] = family;
+    request[HEADER_LEN + 1] = libc::IPPROTO_TCP as u8;
+    request[HEADER_LEN + 2] = 1 << (INET_DIAG_INFO - 1);
+    request[HEADER_LEN + 4..HEADER_LEN + 8].copy_from_slice(&u32::MAX.to_ne_bytes());
+    // SAFETY: the buffer outlives the call and its length is passed along
+    let sent = unsafe { libc::send(fd, request.as_ptr().cast(), request.len(), 0) };
+    if sent < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn read_dump(fd: i32) -> io::Result<HashMap<u64, Counters>> {
+    let mut sockets = HashMap::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        // SAFETY: the kernel writes at most buffer.len() bytes into it
+        let received = unsafe { libc::recv(fd, buffer.as_mut_ptr().cast(), buffer.len(), 0) };
+        if received < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let mut messages = &buffer[..received as usize];
+        while messages.len() >= HEADER_LEN {
+            let len = u32_at(messages, 0) as usize;
+            let kind = u16::from_ne_bytes([messages[4], messages[5]]);
+            if len < HEADER_LEN || len > messages.len() {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "truncated netlink message"));
+            }
+            match kind {
+                NLMSG_DONE => return Ok(sockets),
+                NLMSG_ERROR => {
+                    let errno = u32_at(messages, HEADER_LEN) as i32;
+                    return Err(io::Error::from_raw_os_error(-errno));
+                }
+                SOCK_DIAG_BY_FAMILY => {
+                    if let Some((inode, counters)) = parse_socket(&messages[HEADER_LEN..len]) {
+                        sockets.insert(inode, counters);
+                    }
+                }
+                _ => {}
+            }
+            messages = &messages[align(len).min(messages.len())..];
+        }
+    }
+}
+
+/// Reads the inode and the counters of an `inet_diag_msg` and its attributes.
+#[cfg(target_os = "linux")]
+fn parse_socket(message: &[u8]) -> Option<(u64, Counters)> {
+    if message.len() < DIAG_MSG_LEN {
+        return None;
+    }
+    let inode = u32_at(message, DIAG_MSG_INODE) as u64;
+    let mut attributes = &message[DIAG_MSG_LEN..];
+    while attributes.len() >= 4 {
+        let len = u16::from_ne_bytes([attributes[0], attributes[1]]) as usize;
+        let kind = u16::from_ne_bytes([attributes[2], attributes[3]]);
+        if len < 4 || len > attributes.len() {
+            return None;
+        }
+        let payload = &attributes[4..len];
+        // Kernels before 4.1 send a tcp_info without the byte counters
+        if kind == INET_DIAG_INFO && payload.len() >= TCP_INFO_BYTES_RECEIVED + 8 {
+            return Some((inode, (u64_at(payload, TCP_INFO_BYTES_RECEIVED), u64_at(payload, TCP_INFO_BYTES_ACKED))));
+        }
+        attributes = &attributes[align(len).min(attributes.len())..];
+    }
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn align(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+#[cfg(target_os = "linux")]
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    let mut value = [0u8; 4];
+    value.copy_from_slice(&bytes[offset..offset + mem::size_of::<u32>()]);
+    u32::from_ne_bytes(value)
+}
+
+#[cfg(target_os = "linux")]
+fn u64_at(bytes: &[u8], offset: usize) -> u64 {
+    let mut value = [0u8; 8];
+    value.copy_from_slice(&bytes[offset..offset + mem::size_of::<u64>()]);
+    u64::from_ne_bytes(value)
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    /// An `inet_diag_msg` for `inode` followed by the attributes.
+    fn message(inode: u32, attributes: &[(u16, Vec<u8>)]) -> Vec<u8> {
+        let mut message = vec![0u8; DIAG_MSG_LEN];
+        message[DIAG_MSG_INODE..].copy_from_slice(&inode.to_ne_bytes());
+        for (kind, payload) in attributes {
+            let len = 4 + payload.len();
+            message.extend_from_slice(&(len as u16).to_ne_bytes());
+            message.extend_from_slice(&kind.to_ne_bytes());
+            message.extend_from_slice(payload);
+            message.resize(message.len() + align(len) - len, 0);
+        }
+        message
+    }
+
+    fn tcp_info(received: u64, acked: u64) -> Vec<u8> {
+        let mut info = vec![0u8; mem::size_of::<libc::tcp_info>()];
+        info[TCP_INFO_BYTES_ACKED..TCP_INFO_BYTES_ACKED + 8].copy_from_slice(&acked.to_ne_bytes());
+        info[TCP_INFO_BYTES_RECEIVED..TCP_INFO_BYTES_RECEIVED + 8].copy_from_slice(&received.to_ne_bytes());
+        info
+    }
+
+    #[test]
+    fn offsets_match_the_kernel_structs() {
+        assert_eq!(mem::offset_of!(libc::tcp_info, tcpi_bytes_acked), TCP_INFO_BYTES_ACKED);
+        assert_eq!(mem::offset_of!(libc::tcp_info, tcpi_bytes_received), TCP_INFO_BYTES_RECEIVED);
+        assert_eq!(mem::size_of::<libc::nlmsghdr>(), HEADER_LEN);
+    }
+
+    #[test]
+    fn counters_come_from_the_info_attribute() {
+        // INET_DIAG_MEMINFO, then a padded attribute, before the info
+        let message = message(98765, &[(1, vec![0; 16]), (8, vec![1]), (INET_DIAG_INFO, tcp_info(4096, 1500))]);
+        assert_eq!(parse_socket(&message), Some((98765, (4096, 1500))));
+    }
+
+    #[test]
+    fn sockets_without_counters_are_skipped() {
+        // A tcp_info from before Linux 4.1 ends before the byte counters
+        let message = message(1, &[(INET_DIAG_INFO, vec![0; 104])]);
+        assert_eq!(parse_socket(&message), None);
+        assert_eq!(parse_socket(&self::message(1, &[])), None);
+        assert_eq!(parse_socket(&[0; DIAG_MSG_LEN - 1]), None);
+        // An attribute longer than the message
+        let mut message = self::message(1, &[(INET_DIAG_INFO, tcp_info(1, 1))]);
+        message.truncate(message.len() - 8);
+        assert_eq!(parse_socket(&message), None);
+    }
+}
@@ -0,0 +1,82 @@
+//! Human readable sizes and rates. Everything is scaled by powers of 1000 so
+//! the panels agree with disk vendors and network link speeds.
+
+const BYTE_UNITS: [&str; 6] = ["B", "KB", "MB", "GB", "TB", "PB"];
+const BIT_RATE_UNITS: [&str; 5] = ["bps", "Kbps", "Mbps", "Gbps", "Tbps"];
+
+fn scale(value: f64, units: &[&str]) -> String {
+    let mut value = value;
+    let mut unit = 0;
+    // Compared as rounded for display, so 999.96 KB shows as 1.0 MB rather
+    // than 1000.0 KB
+    while value >= if unit == 0 { 999.5 } else { 999.95 } && unit < units.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{:.0} {}", value, units[unit])
+    } else {
+        format!("{:.1} {}", value, units[unit])
+    }
+}
+
+/// Formats a byte count, e.g. `931.5 GB`.
+pub fn bytes(bytes: u64) -> String {
+    scale(bytes as f64, &BYTE_UNITS)
+}
+
+/// Formats a throughput given in bytes per second, e.g. `12.3 MB/s`.
+pub fn bytes_per_second(bytes_per_second: f64) -> String {
+    format!("{}/s", scale(bytes_per_second, &BYTE_UNITS))
+}
+
+/// Formats a network rate given in kilobits per second, the unit the
+/// network collector reports, e.g. `1.2 Gbps`.
+pub fn kilobits_per_second(kbps: f64) -> String {
+    scale(kbps * 1000.0, &BIT_RATE_UNITS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_below_a_kilobyte_have_no_decimals() {
+        assert_eq!(bytes(0), "0 B");
+        assert_eq!(bytes(1), "1 B");
+        assert_eq!(bytes(999), "999 B");
+    }
+
+    #[test]
+    fn bytes_scale_by_1000() {
+        assert_eq!(bytes(1000), "1.0 KB");
+        assert_eq!(bytes(1024), "1.0 KB");
+        assert_eq!(bytes(1_500_000), "1.5 MB");
+        assert_eq!(bytes(931_500_000_000), "931.5 GB");
+        assert_eq!(bytes(2_000_000_000_000), "2.0 TB");
+    }
+
+    #[test]
+    fn rounding_up_moves_to_the_next_unit() {
+        assert_eq!(bytes(999_940), "999.9 KB");
+        assert_eq!(bytes(999_960), "1.0 MB");
+        assert_eq!(bytes_per_second(999.4), "999 B/s");
+        assert_eq!(bytes_per_second(999.6), "1.0 KB/s");
+    }
+
+    #[test]
+    fn the_largest_unit_keeps_growing() {
+        assert_eq!(bytes(u64::MAX), "18446.7 PB");
+        assert_eq!(kilobits_per_second(5e12), "5000.0 Tbps");
+    }
+
+    #[test]
+    fn rates() {
+        assert_eq!(bytes_per_second(0.0), "0 B/s");
+        assert_eq!(bytes_per_second(12_300_000.0), "12.3 MB/s");
+        assert_eq!(kilobits_per_second(0.0), "0 bps");
+        assert_eq!(kilobits_per_second(0.5), "500 bps");
+        assert_eq!(kilobits_per_second(1.0), "1.0 Kbps");
+        assert_eq!(kilobits_per_second(1_200_000.0), "1.2 Gbps");
+    }
+}
@@ -1,20 +1,83 @@
-use std::sync::mpsc::Sender;
+use std::ffi::CStr;
+#[cfg(unix)]
+use libc::strftime;
 use sysinfo::System;
 
-use crate::cmd::Message;
-
-pub fn send_cores_usage(tx: &Sender<Message>, sys: &System) {
+pub fn cores_usage(sys: &System) -> Vec<f32> {
     let mut usages: Vec<f32> = Vec::new();
     for cpu in sys.cpus().iter() {
         usages.push(cpu.cpu_usage());
     }
-    tx.send(Message::CpuUsage(usages)).unwrap();
+    usages
+}
+
+/// Current clock of every core in MHz.
+pub fn cores_frequency(sys: &System) -> Vec<u64> {
+    sys.cpus().iter().map(|cpu| cpu.frequency()).collect()
 }
 
+/// `3 days 4:05:06`, or `4:05:06` under a day.
 pub fn seconds_to_timestamp(total_seconds: u64) -> String {
-    let hours = total_seconds / 3600;
-    let days = hours /  24;
-    let minutes = (total_seconds % 3600) / 60;
+    let days = total_seconds / 86400;
+    let hours = total_seconds % 86400 / 3600;
+    let minutes = total_seconds % 3600 / 60;
     let seconds = total_seconds % 60;
-    format!("{} days {}:{}:{}", days, hours, minutes, seconds)
-}
\ No newline at end of file
+    match days {
+        0 => format!("{}:{:02}:{:02}", hours, minutes, seconds),
+        1 => format!("1 day {}:{:02}:{:02}", hours, minutes, seconds),
+        _ => format!("{} days {}:{:02}:{:02}", days, hours, minutes, seconds),
+    }
+}
+
+/// Seconds since the epoch in local time, `format` as in `strftime(3)`.
+pub fn local_time(epoch: u64, format: &CStr) -> String {
+    let time = epoch as libc::time_t;
+    let mut buffer = [0u8; 64];
+    // SAFETY: localtime_r fills the zeroed tm, strftime writes at most
+    // buffer.len() bytes and returns how many it wrote
+    let written = unsafe {
+        let mut tm: libc::tm = std::mem::zeroed();
+        #[cfg(unix)]
+        if libc::localtime_r(&time, &mut tm).is_null() {
+            return String::new();
+        }
+        #[cfg(windows)]
+        if libc::localtime_s(&mut tm, &time) != 0 {
+            return String::new();
+        }
+        strftime(buffer.as_mut_ptr().cast(), buffer.len(), format.as_ptr(), &tm)
+    };
+    String::from_utf8_lossy(&buffer[..written]).into_owned()
+}
+
+// The C runtime of Windows has it too, the libc crate just doesn't declare it
+#[cfg(windows)]
+unsafe extern "C" {
+    fn strftime(s: *mut libc::c_char, max: usize, format: *const libc::c_char, tm: *const libc::tm) -> usize;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn under_an_hour() {
+        assert_eq!(seconds_to_timestamp(0), "0:00:00");
+        assert_eq!(seconds_to_timestamp(59), "0:00:59");
+        assert_eq!(seconds_to_timestamp(3599), "0:59:59");
+    }
+
+    #[test]
+    fn hours_stay_within_the_day() {
+        assert_eq!(seconds_to_timestamp(3600), "1:00:00");
+        assert_eq!(seconds_to_timestamp(3 * 3600 + 4 * 60 + 5), "3:04:05");
+        assert_eq!(seconds_to_timestamp(86399), "23:59:59");
+    }
+
+    #[test]
+    fn days() {
+        assert_eq!(seconds_to_timestamp(86400), "1 day 0:00:00");
+        assert_eq!(seconds_to_timestamp(86400 + 7200 + 61), "1 day 2:01:01");
+        assert_eq!(seconds_to_timestamp(3 * 86400 + 4 * 3600 + 5 * 60 + 6), "3 days 4:05:06");
+    }
+}
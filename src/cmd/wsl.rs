@@ -0,0 +1,51 @@
+//! Windows Subsystem for Linux, told apart by the kernel release.
+use std::fs;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Wsl {
+    /// Linux system calls translated by Windows, with the Windows drives
+    /// mounted as `drvfs`.
+    V1,
+    /// A Linux kernel in a lightweight VM, with the Windows drives shared
+    /// over 9p.
+    V2,
+}
+
+impl Wsl {
+    /// WSL 2 kernels are released as `5.15.153.1-microsoft-standard-WSL2`,
+    /// WSL 1 reports the Windows build, such as `4.4.0-19041-Microsoft`.
+    pub fn detect() -> Option<Wsl> {
+        if !cfg!(target_os = "linux") {
+            return None;
+        }
+        let release = fs::read_to_string("/proc/sys/kernel/osrelease").ok()?;
+        let release = release.trim();
+        if release.contains("WSL2") || release.contains("microsoft-standard") {
+            Some(Wsl::V2)
+        } else if release.contains("Microsoft") {
+            Some(Wsl::V1)
+        } else {
+            None
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Wsl::V1 => "WSL 1",
+            Wsl::V2 => "WSL 2",
+        }
+    }
+
+    /// Mounts that aren't disks of their own: WSL's mounts for its drivers
+    /// and GUI, which repeat the root file system, and the shares of Windows
+    /// other than its drives under `/mnt/<letter>`.
+    pub fn hides_mount(file_system: &str, mount_point: &str) -> bool {
+        let internal = ["/mnt/wsl", "/mnt/wslg", "/usr/lib/wsl", "/init"].iter().any(|prefix| {
+            mount_point == *prefix || mount_point.starts_with(&format!("{}/", prefix))
+        });
+        let drive = mount_point.strip_prefix("/mnt/").is_some_and(|drive| {
+            drive.len() == 1 && drive.chars().all(|letter| letter.is_ascii_alphabetic())
+        });
+        internal || (matches!(file_system, "9p" | "drvfs") && !drive)
+    }
+}
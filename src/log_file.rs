@@ -0,0 +1,49 @@
+//! Writes log records to a file, since stderr is the terminal the UI draws
+//! on.
+use std::{fs::{File, OpenOptions}, io::{self, Write}, sync::Mutex, time::{SystemTime, UNIX_EPOCH}};
+use log::{Level, Log, Metadata, Record};
+
+use crate::cmd::utils::local_time;
+
+struct FileLogger {
+    file: Mutex<File>,
+    level: Level,
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let line = format!(
+            "{} {:<5} [{}] {}\n",
+            local_time(now, c"%Y-%m-%d %H:%M:%S"), record.level(), record.target(), record.args()
+        );
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.write_all(line.as_bytes());
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// Appends every record at `level` or more severe to `path`.
+pub fn init(path: &str, level: Level) -> io::Result<()> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    log::set_boxed_logger(Box::new(FileLogger { file: Mutex::new(file), level }))
+        .map_err(io::Error::other)?;
+    log::set_max_level(level.to_level_filter());
+    Ok(())
+}
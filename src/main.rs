@@ -1,13 +1,122 @@
+mod alert;
 mod app;
+mod cli;
 mod cmd;
+mod log_file;
+mod server;
 
+use std::net::SocketAddr;
+use clap::Parser;
 use log::Level;
 
+use crate::{app::{config::AppConfig, metrics_log::MetricsLog}, cmd::remote::Remote};
+
 #[tokio::main]
 async fn main() -> Result<(), std::io::Error> {
-    simple_logger::init_with_level(Level::Debug).unwrap();
+    let args = cli::Args::parse();
+    if args.check_config {
+        let issues = AppConfig::check(&args.config);
+        if issues.is_empty() {
+            println!("{}: OK", args.config);
+            return Ok(());
+        }
+        for issue in issues {
+            println!("{}", issue);
+        }
+        std::process::exit(1);
+    }
+    // The UI owns the terminal, so it only logs to a file when given one
+    match &args.log_file {
+        Some(path) => {
+            if let Err(err) = log_file::init(path, Level::Warn) {
+                eprintln!("Error opening log file {}: {}", path, err);
+                std::process::exit(1);
+            }
+        }
+        None if args.agent.is_some() => simple_logger::init_with_level(Level::Debug).unwrap(),
+        None => {}
+    }
+    let config = AppConfig::new(&args.config);
+    if let Some(addr) = &args.agent {
+        let (publisher, subscriber) = cmd::hub::channel();
+        let shutdown = cmd::shutdown::Shutdown::new();
+        cmd::spawn_collectors(publisher, &config, &shutdown);
+        server::mqtt::spawn(config.mqtt.clone(), subscriber.clone());
+        server::influx::spawn(config.influx.clone(), subscriber.clone());
+        server::statsd::spawn(config.statsd.clone(), subscriber.clone());
+        if let Some(addr) = &args.serve {
+            server::rest::serve(addr, subscriber.clone()).await?;
+        }
+        let serve = async {
+            match addr.as_str() {
+                "-" => server::agent::serve_stdout(subscriber).await,
+                _ => server::agent::serve(addr, subscriber).await,
+            }
+        };
+        let result = tokio::select! {
+            result = serve => result,
+            _ = tokio::signal::ctrl_c() => Ok(()),
+        };
+        shutdown.stop().await;
+        return result;
+    }
+    let mqtt = config.mqtt.clone();
+    let influx = config.influx.clone();
+    let statsd = config.statsd.clone();
+    let mut app = app::App::new(config).set_config_path(args.config.clone());
+    if let Some(path) = &args.log_metrics {
+        match MetricsLog::open(path) {
+            Ok(metrics_log) => app = app.set_metrics_log(metrics_log),
+            Err(err) => {
+                eprintln!("Error opening metrics log {}: {}", path, err);
+                std::process::exit(1);
+            }
+        }
+    }
+    #[cfg(feature = "history")]
+    if let Some(path) = &args.history_db {
+        match app::history::History::open(path) {
+            Ok(history) => app = app.set_history(history),
+            Err(err) => {
+                eprintln!("Error opening history database {}: {}", path, err);
+                std::process::exit(1);
+            }
+        }
+    }
+    if let Some(addr) = args.remote {
+        app = app.set_remote(Remote::Agent(addr));
+    }
+    if let Some(destination) = args.ssh {
+        app = app.set_remote(Remote::Ssh { destination, command: args.ssh_command });
+    }
+    if let Some(port) = args.prometheus_port
+        && let Err(err) = server::prometheus::serve(args.prometheus_addr, port, app.subscribe()).await {
+        eprintln!("Error starting Prometheus exporter on {}: {}", SocketAddr::new(args.prometheus_addr, port), err);
+        std::process::exit(1);
+    }
+    if let Some(addr) = &args.serve
+        && let Err(err) = server::rest::serve(addr, app.subscribe()).await {
+        eprintln!("Error starting REST API on {}: {}", addr, err);
+        std::process::exit(1);
+    }
+    server::mqtt::spawn(mqtt, app.subscribe());
+    server::influx::spawn(influx, app.subscribe());
+    server::statsd::spawn(statsd, app.subscribe());
     let terminal = ratatui::init();
-    let result = app::App::new().run(terminal).await;
+    install_panic_hook();
+    let result = app.run(terminal).await;
     ratatui::restore();
     result
-}
\ No newline at end of file
+}
+
+/// Restores the terminal before the panic message is printed. A panic in a
+/// collector task would otherwise leave the UI running on a restored
+/// terminal, so the process exits instead.
+fn install_panic_hook() {
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        ratatui::restore();
+        hook(info);
+        std::process::exit(101);
+    }));
+}
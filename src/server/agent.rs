@@ -0,0 +1,94 @@
+use std::sync::Arc;
+use tokio::{
+    io::{AsyncWrite, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::broadcast::{self, error::RecvError},
+};
+
+use crate::cmd::hub::Subscriber;
+
+/// Serves collector messages to remote clients as newline delimited JSON.
+/// A new client first receives the latest value of every collector, then
+/// every message as it arrives.
+pub async fn serve(addr: &str, subscriber: Subscriber) -> Result<(), std::io::Error> {
+    let listener = TcpListener::bind(addr).await?;
+    let (lines_tx, _) = broadcast::channel::<Arc<String>>(256);
+
+    let mut updates = subscriber.clone();
+    let pump_tx = lines_tx.clone();
+    tokio::spawn(async move {
+        while let Some(msg) = updates.next().await {
+            match serde_json::to_string(&msg) {
+                Ok(line) => {
+                    let _ = pump_tx.send(Arc::new(line));
+                }
+                Err(err) => log::warn!("Error serializing message: {}", err),
+            }
+        }
+    });
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let lines_rx = lines_tx.subscribe();
+        let initial = subscriber
+            .snapshot()
+            .messages()
+            .iter()
+            .filter_map(|msg| serde_json::to_string(msg).ok())
+            .collect();
+        tokio::spawn(handle_client(stream, initial, lines_rx));
+    }
+}
+
+/// Writes collector messages to stdout the way `serve` does to a client,
+/// for an agent started over SSH. Returns once stdout is closed.
+pub async fn serve_stdout(subscriber: Subscriber) -> Result<(), std::io::Error> {
+    let mut stdout = tokio::io::stdout();
+    for msg in subscriber.snapshot().messages() {
+        let Ok(line) = serde_json::to_string(&msg) else { continue };
+        if write_line(&mut stdout, &line).await.is_err() {
+            return Ok(());
+        }
+    }
+    let mut updates = subscriber;
+    while let Some(msg) = updates.next().await {
+        match serde_json::to_string(&msg) {
+            Ok(line) => {
+                if write_line(&mut stdout, &line).await.is_err() {
+                    return Ok(());
+                }
+            }
+            Err(err) => log::warn!("Error serializing message: {}", err),
+        }
+    }
+    Ok(())
+}
+
+async fn handle_client(
+    mut stream: TcpStream,
+    initial: Vec<String>,
+    mut lines_rx: broadcast::Receiver<Arc<String>>,
+) {
+    for line in initial {
+        if write_line(&mut stream, &line).await.is_err() {
+            return;
+        }
+    }
+    loop {
+        match lines_rx.recv().await {
+            Ok(line) => {
+                if write_line(&mut stream, &line).await.is_err() {
+                    return;
+                }
+            }
+            Err(RecvError::Lagged(_)) => continue,
+            Err(RecvError::Closed) => return,
+        }
+    }
+}
+
+async fn write_line<W: AsyncWrite + Unpin>(stream: &mut W, line: &str) -> Result<(), std::io::Error> {
+    stream.write_all(line.as_bytes()).await?;
+    stream.write_all(b"\n").await?;
+    stream.flush().await
+}
@@ -0,0 +1,107 @@
+//! Writes readings in InfluxDB line protocol, one point per line such as
+//! `disk,host=nas,mount=/ percent_used=41,available=1024i 1700000000000000000`.
+use std::{fmt::Write as _, time::{SystemTime, UNIX_EPOCH}};
+use tokio::{fs::OpenOptions, io::AsyncWriteExt, time::Instant};
+
+use crate::{app::config::InfluxConfig, cmd::{hub::Subscriber, snapshot::Snapshot}};
+
+/// Writes the latest readings every `influx.interval` from a background task.
+/// Does nothing unless `influx.file` or `influx.url` is set.
+pub fn spawn(config: InfluxConfig, subscriber: Subscriber) {
+    if config.file.is_none() && config.url.is_none() {
+        return;
+    }
+    let host = config.host.clone().unwrap_or_else(|| {
+        sysinfo::System::host_name().unwrap_or_else(|| "localhost".to_string())
+    });
+    let token = std::env::var("HTOPUI_INFLUX_TOKEN").ok();
+    let client = reqwest::Client::new();
+    let interval = config.interval.unwrap();
+    tokio::spawn(async move {
+        let mut tick = tokio::time::interval_at(Instant::now() + interval, interval);
+        loop {
+            tick.tick().await;
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos();
+            let lines = lines(&subscriber.snapshot(), &escape_tag(&host), timestamp);
+            if let Some(path) = &config.file {
+                let file = OpenOptions::new().create(true).append(true).open(path).await;
+                let result = match file {
+                    Ok(mut file) => file.write_all(lines.as_bytes()).await,
+                    Err(err) => Err(err),
+                };
+                if let Err(err) = result {
+                    log::warn!("Error writing line protocol to {}: {}", path, err);
+                }
+            }
+            if let Some(url) = &config.url {
+                let mut request = client.post(url).body(lines);
+                if let Some(token) = &token {
+                    request = request.header("Authorization", format!("Token {}", token));
+                }
+                match request.send().await {
+                    Ok(response) if !response.status().is_success() => {
+                        log::warn!("InfluxDB at {} responded with {}", url, response.status());
+                    }
+                    Err(err) => log::warn!("Error writing to InfluxDB at {}: {}", url, err),
+                    _ => {}
+                }
+            }
+        }
+    });
+}
+
+/// A point per reading, all stamped with `timestamp` in nanoseconds.
+fn lines(snapshot: &Snapshot, host: &str, timestamp: u128) -> String {
+    let mut out = String::new();
+    let mut point = |measurement: &str, tags: &[(&str, &str)], fields: &str| {
+        let _ = write!(out, "{},host={}", measurement, host);
+        for (key, value) in tags {
+            let _ = write!(out, ",{}={}", key, escape_tag(value));
+        }
+        let _ = writeln!(out, " {} {}", fields, timestamp);
+    };
+
+    let cores = &snapshot.cores_usage;
+    if !cores.is_empty() {
+        point("cpu", &[], &format!("usage={}", cores.iter().sum::<f32>() / cores.len() as f32));
+        for (core, usage) in cores.iter().enumerate() {
+            point("cpu", &[("core", &core.to_string())], &format!("usage={}", usage));
+        }
+    }
+    let memory = &snapshot.mem_usage;
+    if memory.total > 0 {
+        point("memory", &[], &format!(
+            "percent_used={},used={}i,total={}i,swap_used={}i,swap_total={}i",
+            memory.percent_used(), memory.used, memory.total, memory.swap_used, memory.swap_total
+        ));
+    }
+    for disk in snapshot.disks.iter() {
+        point("disk", &[("mount", &disk.mount_point)], &format!(
+            "percent_used={},available={}i,total={}i",
+            disk.percent_used_space(), disk.available_space, disk.total_space
+        ));
+    }
+    let network = &snapshot.network;
+    point("network", &[], &format!("upload_kbps={},download_kbps={}", network.upload, network.download));
+    for interface in network.interfaces.iter() {
+        point("network", &[("interface", &interface.name)], &format!(
+            "upload_kbps={},download_kbps={}", interface.upload, interface.download
+        ));
+    }
+    for temperature in snapshot.temperatures.iter() {
+        point("temperature", &[("sensor", &temperature.label)], &format!("celsius={}", temperature.value));
+    }
+    out
+}
+
+/// Tag values can't hold unescaped commas, equal signs or spaces, nor be
+/// empty.
+fn escape_tag(value: &str) -> String {
+    if value.is_empty() {
+        return "none".to_string();
+    }
+    value.replace('\\', "\\\\").replace(',', "\\,").replace('=', "\\=").replace(' ', "\\ ")
+}
@@ -0,0 +1,7 @@
+pub mod agent;
+pub mod influx;
+pub mod mqtt;
+pub mod prometheus;
+pub mod rest;
+pub mod statsd;
+pub mod websocket;
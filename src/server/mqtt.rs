@@ -0,0 +1,435 @@
+//! Publishes readings to an MQTT broker, speaking just enough MQTT 3.1.1 to
+//! connect, publish at QoS 0 and ping.
+use std::{collections::BTreeMap, io, sync::Arc, time::Duration};
+use rustls_platform_verifier::ConfigVerifierExt;
+use serde_json::{json, Value};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadHalf, WriteHalf},
+    net::TcpStream,
+    time::Instant,
+};
+use tokio_rustls::{rustls::{pki_types::ServerName, ClientConfig}, TlsConnector};
+
+use crate::{app::config::MqttConfig, cmd::{hub::Subscriber, snapshot::Snapshot}};
+
+/// Port used when the broker is given without one.
+const DEFAULT_PORT: u16 = 1883;
+const DEFAULT_TLS_PORT: u16 = 8883;
+/// Time the broker gets to accept a connection.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Longest remaining length the fixed header can encode.
+const MAX_REMAINING_LENGTH: usize = 268_435_455;
+const PINGREQ: [u8; 2] = [0xc0, 0x00];
+const PINGRESP: u8 = 0xd0;
+const DISCONNECT: u8 = 0xe0;
+
+trait Stream: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<S: AsyncRead + AsyncWrite + Unpin + Send> Stream for S {}
+
+/// Publishes the latest readings every `mqtt.interval` from a background
+/// task, reconnecting on the next round when the broker drops the
+/// connection or stops answering pings. Does nothing unless `mqtt.broker`
+/// is set.
+pub fn spawn(config: MqttConfig, subscriber: Subscriber) {
+    let Some(broker) = config.broker.clone() else {
+        return;
+    };
+    let tls = config.tls.unwrap();
+    let (host, port) = match broker.rsplit_once(':') {
+        Some((host, port)) if port.parse::<u16>().is_ok() => (host.to_string(), port.parse().unwrap()),
+        _ => (broker, if tls { DEFAULT_TLS_PORT } else { DEFAULT_PORT }),
+    };
+    let client_id = config.client_id.clone().unwrap_or_else(|| {
+        format!("htop-ui-{}", sysinfo::System::host_name().unwrap_or_else(|| "localhost".to_string()))
+    });
+    let credentials = std::env::var("HTOPUI_MQTT_USERNAME")
+        .ok()
+        .zip(std::env::var("HTOPUI_MQTT_PASSWORD").ok());
+    let interval = config.interval.unwrap();
+    // A ping goes out every round and must be answered by the next one, so
+    // the broker hears from us well within the keep alive
+    let keep_alive = (interval.as_secs() * 2 + 1).min(u16::MAX as u64) as u16;
+    tokio::spawn(async move {
+        let mut tick = tokio::time::interval_at(Instant::now() + interval, interval);
+        let mut connection: Option<Connection> = None;
+        loop {
+            let result = match connection.as_mut() {
+                Some(connection) => tokio::select! {
+                    _ = tick.tick() => connection.round(&config, &subscriber.snapshot()).await,
+                    received = connection.receive() => received,
+                },
+                None => {
+                    tick.tick().await;
+                    let connected = connect(&host, port, tls, &client_id, credentials.as_ref(), keep_alive).await;
+                    match connected {
+                        Ok(connected) => connection.insert(connected).round(&config, &subscriber.snapshot()).await,
+                        Err(err) => {
+                            log::warn!("Error connecting to MQTT broker {}:{}: {}", host, port, err);
+                            continue;
+                        }
+                    }
+                }
+            };
+            if let Err(err) = result {
+                log::warn!("Error talking to MQTT broker {}:{}: {}", host, port, err);
+                connection = None;
+            }
+        }
+    });
+}
+
+struct Connection {
+    reader: ReadHalf<Box<dyn Stream>>,
+    writer: WriteHalf<Box<dyn Stream>>,
+    /// Bytes read that don't make a whole packet yet.
+    incoming: Vec<u8>,
+    /// Set by a PINGREQ until its PINGRESP arrives.
+    awaiting_pong: bool,
+}
+
+impl Connection {
+    fn new(stream: Box<dyn Stream>) -> Self {
+        let (reader, writer) = tokio::io::split(stream);
+        Self { reader, writer, incoming: Vec::new(), awaiting_pong: false }
+    }
+
+    /// Publishes every reading and pings the broker, failing when the last
+    /// ping went unanswered.
+    async fn round(&mut self, config: &MqttConfig, snapshot: &Snapshot) -> io::Result<()> {
+        if self.awaiting_pong {
+            return Err(io::Error::new(io::ErrorKind::TimedOut, "no answer to PINGREQ"));
+        }
+        let topics = &config.topics;
+        let retain = config.retain.unwrap();
+        for (topic, payload) in [
+            (&topics.cpu, cpu(snapshot)),
+            (&topics.memory, memory(snapshot)),
+            (&topics.disk, disk(snapshot)),
+            (&topics.network, network(snapshot)),
+            (&topics.temperature, temperature(snapshot)),
+        ] {
+            let packet = publish(topic.as_deref().unwrap(), payload.to_string().as_bytes(), retain)?;
+            self.writer.write_all(&packet).await?;
+        }
+        self.writer.write_all(&PINGREQ).await?;
+        self.awaiting_pong = true;
+        self.writer.flush().await
+    }
+
+    /// Reads what the broker sent, failing once it closes the connection.
+    /// Cancel safe, as the only await is a single read.
+    async fn receive(&mut self) -> io::Result<()> {
+        let mut buffer = [0u8; 512];
+        let read = self.reader.read(&mut buffer).await?;
+        if read == 0 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "broker closed the connection"));
+        }
+        self.incoming.extend_from_slice(&buffer[..read]);
+        while let Some(kind) = take_packet(&mut self.incoming)? {
+            match kind & 0xf0 {
+                PINGRESP => self.awaiting_pong = false,
+                DISCONNECT => return Err(io::Error::other("broker sent DISCONNECT")),
+                // Nothing else is expected without subscriptions or QoS
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+fn cpu(snapshot: &Snapshot) -> Value {
+    let cores = &snapshot.cores_usage;
+    let usage = match cores.is_empty() {
+        true => 0.0,
+        false => cores.iter().sum::<f32>() / cores.len() as f32,
+    };
+    json!({ "usage": usage, "cores": cores })
+}
+
+fn memory(snapshot: &Snapshot) -> Value {
+    let memory = &snapshot.mem_usage;
+    json!({
+        "percent_used": memory.percent_used(),
+        "used": memory.used,
+        "total": memory.total,
+        "swap_used": memory.swap_used,
+        "swap_total": memory.swap_total,
+    })
+}
+
+/// Keyed by mount point, so that a template can pick `value_json['/']`.
+fn disk(snapshot: &Snapshot) -> Value {
+    let disks: BTreeMap<&str, Value> = snapshot.disks.iter()
+        .map(|disk| (disk.mount_point.as_str(), json!({
+            "percent_used": disk.percent_used_space(),
+            "available": disk.available_space,
+            "total": disk.total_space,
+        })))
+        .collect();
+    json!(disks)
+}
+
+fn network(snapshot: &Snapshot) -> Value {
+    let network = &snapshot.network;
+    let interfaces: BTreeMap<&str, Value> = network.interfaces.iter()
+        .map(|interface| (interface.name.as_str(), json!({
+            "upload_kbps": interface.upload,
+            "download_kbps": interface.download,
+        })))
+        .collect();
+    json!({
+        "upload_kbps": network.upload,
+        "download_kbps": network.download,
+        "interfaces": interfaces,
+    })
+}
+
+/// Degrees Celsius keyed by sensor label.
+fn temperature(snapshot: &Snapshot) -> Value {
+    let temperatures: BTreeMap<&str, f32> = snapshot.temperatures.iter()
+        .map(|temperature| (temperature.label.as_str(), temperature.value))
+        .collect();
+    json!(temperatures)
+}
+
+/// Opens a clean session and waits for the broker to accept it.
+async fn connect(
+    host: &str,
+    port: u16,
+    tls: bool,
+    client_id: &str,
+    credentials: Option<&(String, String)>,
+    keep_alive: u16,
+) -> io::Result<Connection> {
+    let stream = tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect((host, port)))
+        .await
+        .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "connection timed out"))??;
+    let mut stream: Box<dyn Stream> = match tls {
+        true => Box::new(tls_stream(host, stream).await?),
+        false => Box::new(stream),
+    };
+    stream.write_all(&connect_packet(client_id, credentials, keep_alive)?).await?;
+
+    let mut connack = [0u8; 4];
+    tokio::time::timeout(CONNECT_TIMEOUT, stream.read_exact(&mut connack))
+        .await
+        .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "no answer to CONNECT"))??;
+    accepted(connack)?;
+    Ok(Connection::new(stream))
+}
+
+async fn tls_stream(host: &str, stream: TcpStream) -> io::Result<tokio_rustls::client::TlsStream<TcpStream>> {
+    let config = ClientConfig::with_platform_verifier().map_err(io::Error::other)?;
+    let name = ServerName::try_from(host.to_string()).map_err(io::Error::other)?;
+    TlsConnector::from(Arc::new(config)).connect(name, stream).await
+}
+
+fn connect_packet(client_id: &str, credentials: Option<&(String, String)>, keep_alive: u16) -> io::Result<Vec<u8>> {
+    let mut flags = 0x02;
+    let mut body = Vec::new();
+    string(&mut body, "MQTT")?;
+    body.push(4);
+    if credentials.is_some() {
+        flags |= 0xc0;
+    }
+    body.push(flags);
+    body.extend_from_slice(&keep_alive.to_be_bytes());
+    string(&mut body, client_id)?;
+    if let Some((username, password)) = credentials {
+        string(&mut body, username)?;
+        string(&mut body, password)?;
+    }
+    packet(0x10, &body)
+}
+
+fn accepted(connack: [u8; 4]) -> io::Result<()> {
+    match connack {
+        [0x20, 0x02, _, 0] => Ok(()),
+        [0x20, 0x02, _, 4] => Err(io::Error::other("bad username or password")),
+        [0x20, 0x02, _, 5] => Err(io::Error::other("not authorized")),
+        [0x20, 0x02, _, code] => Err(io::Error::other(format!("connection refused with code {}", code))),
+        _ => Err(io::Error::other("unexpected answer to CONNECT")),
+    }
+}
+
+fn publish(topic: &str, payload: &[u8], retain: bool) -> io::Result<Vec<u8>> {
+    let mut body = Vec::with_capacity(topic.len() + payload.len() + 2);
+    string(&mut body, topic)?;
+    body.extend_from_slice(payload);
+    packet(0x30 | retain as u8, &body)
+}
+
+/// A fixed header, the remaining length 7 bits at a time and the body.
+fn packet(kind: u8, body: &[u8]) -> io::Result<Vec<u8>> {
+    if body.len() > MAX_REMAINING_LENGTH {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "packet too large"));
+    }
+    let mut packet = vec![kind];
+    let mut remaining = body.len();
+    loop {
+        let mut byte = (remaining % 128) as u8;
+        remaining /= 128;
+        if remaining > 0 {
+            byte |= 0x80;
+        }
+        packet.push(byte);
+        if remaining == 0 {
+            break;
+        }
+    }
+    packet.extend_from_slice(body);
+    Ok(packet)
+}
+
+/// Removes the first whole packet from `buffer` and returns its first byte,
+/// None until enough has arrived.
+fn take_packet(buffer: &mut Vec<u8>) -> io::Result<Option<u8>> {
+    let mut remaining = 0;
+    for (index, byte) in buffer.iter().skip(1).take(4).enumerate() {
+        remaining |= ((byte & 0x7f) as usize) << (7 * index);
+        if byte & 0x80 == 0 {
+            let length = 2 + index + remaining;
+            if buffer.len() < length {
+                return Ok(None);
+            }
+            let kind = buffer[0];
+            buffer.drain(..length);
+            return Ok(Some(kind));
+        }
+    }
+    match buffer.len() > 4 {
+        true => Err(io::Error::new(io::ErrorKind::InvalidData, "bad remaining length from broker")),
+        false => Ok(None),
+    }
+}
+
+/// A length-prefixed UTF-8 string, at most 65535 bytes.
+fn string(out: &mut Vec<u8>, value: &str) -> io::Result<()> {
+    let length = u16::try_from(value.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "string longer than 65535 bytes"))?;
+    out.extend_from_slice(&length.to_be_bytes());
+    out.extend_from_slice(value.as_bytes());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::config::MqttTopics;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn remaining_length_takes_seven_bits_a_byte() {
+        for (length, encoded) in [
+            (0, vec![0x00]),
+            (127, vec![0x7f]),
+            (128, vec![0x80, 0x01]),
+            (16_383, vec![0xff, 0x7f]),
+            (16_384, vec![0x80, 0x80, 0x01]),
+            (2_097_152, vec![0x80, 0x80, 0x80, 0x01]),
+        ] {
+            let packet = packet(0x30, &vec![0; length]).unwrap();
+            assert_eq!(packet[1..1 + encoded.len()], encoded, "length {}", length);
+            assert_eq!(packet.len(), 1 + encoded.len() + length);
+        }
+        assert!(packet(0x30, &vec![0; MAX_REMAINING_LENGTH + 1]).is_err());
+    }
+
+    #[test]
+    fn publish_prefixes_the_topic_with_its_length() {
+        let packet = publish("a/b", b"{}", true).unwrap();
+        assert_eq!(packet, [0x31, 7, 0, 3, b'a', b'/', b'b', b'{', b'}']);
+        assert_eq!(publish("a/b", b"{}", false).unwrap()[0], 0x30);
+    }
+
+    #[test]
+    fn strings_over_64_kib_are_refused() {
+        assert!(publish(&"x".repeat(65_535), b"", false).is_ok());
+        assert!(publish(&"x".repeat(65_536), b"", false).is_err());
+        let credentials = ("user".to_string(), "x".repeat(70_000));
+        assert!(connect_packet("htop-ui", Some(&credentials), 21).is_err());
+    }
+
+    #[test]
+    fn connect_asks_for_a_clean_session() {
+        let packet = connect_packet("id", None, 21).unwrap();
+        assert_eq!(packet, [0x10, 14, 0, 4, b'M', b'Q', b'T', b'T', 4, 0x02, 0, 21, 0, 2, b'i', b'd']);
+        let credentials = ("u".to_string(), "p".to_string());
+        let packet = connect_packet("id", Some(&credentials), 21).unwrap();
+        assert_eq!(packet[9], 0xc2);
+        assert_eq!(packet[packet.len() - 6..], [0, 1, b'u', 0, 1, b'p']);
+    }
+
+    #[test]
+    fn connack_codes() {
+        assert!(accepted([0x20, 0x02, 0, 0]).is_ok());
+        assert_eq!(accepted([0x20, 0x02, 0, 4]).unwrap_err().to_string(), "bad username or password");
+        assert_eq!(accepted([0x20, 0x02, 0, 2]).unwrap_err().to_string(), "connection refused with code 2");
+        assert!(accepted([0x30, 0x02, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn packets_are_taken_once_whole() {
+        let mut buffer = vec![PINGRESP];
+        assert_eq!(take_packet(&mut buffer).unwrap(), None);
+        buffer.extend_from_slice(&[0x00, 0x30, 0x80]);
+        assert_eq!(take_packet(&mut buffer).unwrap(), Some(PINGRESP));
+        assert_eq!(take_packet(&mut buffer).unwrap(), None);
+        buffer.extend_from_slice(&[0x01]);
+        buffer.extend_from_slice(&[0; 127]);
+        assert_eq!(take_packet(&mut buffer).unwrap(), None);
+        buffer.push(0);
+        assert_eq!(take_packet(&mut buffer).unwrap(), Some(0x30));
+        assert!(buffer.is_empty());
+        let mut buffer = vec![0x30, 0xff, 0xff, 0xff, 0xff];
+        assert!(take_packet(&mut buffer).is_err());
+    }
+
+    #[tokio::test]
+    async fn a_broker_closing_the_connection_is_noticed() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let broker = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut connect = [0u8; 16];
+            stream.read_exact(&mut connect).await.unwrap();
+            stream.write_all(&[0x20, 0x02, 0, 0]).await.unwrap();
+            // Answers the ping after the readings, then hangs up
+            let mut received = Vec::new();
+            while !received.ends_with(&PINGREQ) {
+                let mut buffer = [0u8; 1024];
+                let read = stream.read(&mut buffer).await.unwrap();
+                received.extend_from_slice(&buffer[..read]);
+            }
+            stream.write_all(&[PINGRESP, 0x00]).await.unwrap();
+            (connect, received)
+        });
+        let mut connection = connect("127.0.0.1", port, false, "id", None, 21).await.unwrap();
+        let topic = |name: &str| Some(format!("htop-ui/{}", name));
+        let config = MqttConfig {
+            retain: Some(false),
+            topics: MqttTopics {
+                cpu: topic("cpu"),
+                memory: topic("memory"),
+                disk: topic("disk"),
+                network: topic("network"),
+                temperature: topic("temperature"),
+            },
+            ..Default::default()
+        };
+        connection.round(&config, &Snapshot::default()).await.unwrap();
+        assert!(connection.awaiting_pong);
+        connection.receive().await.unwrap();
+        assert!(!connection.awaiting_pong);
+        let (connect, mut received) = broker.await.unwrap();
+        assert_eq!(connect, connect_packet("id", None, 21).unwrap()[..]);
+        let mut kinds = Vec::new();
+        while let Some(kind) = take_packet(&mut received).unwrap() {
+            kinds.push(kind);
+        }
+        assert_eq!(kinds, [0x30, 0x30, 0x30, 0x30, 0x30, PINGREQ[0]]);
+        let err = connection.receive().await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -0,0 +1,150 @@
+use std::{fmt::Write, net::IpAddr};
+use axum::{extract::State, routing::get, Router};
+use tokio::net::TcpListener;
+
+use crate::cmd::{hub::Subscriber, snapshot::Snapshot};
+
+const TOP_PROCESSES: usize = 10;
+
+/// Binds `addr:port` and serves the latest collector values in the
+/// Prometheus text format on `/metrics` from a background task.
+pub async fn serve(addr: IpAddr, port: u16, subscriber: Subscriber) -> Result<(), std::io::Error> {
+    let listener = TcpListener::bind((addr, port)).await?;
+    let router = Router::new()
+        .route("/metrics", get(metrics))
+        .with_state(subscriber);
+    tokio::spawn(async move {
+        if let Err(err) = axum::serve(listener, router).await {
+            log::error!("Prometheus exporter stopped: {}", err);
+        }
+    });
+    Ok(())
+}
+
+async fn metrics(State(subscriber): State<Subscriber>) -> String {
+    render(&subscriber.snapshot())
+}
+
+fn render(snapshot: &Snapshot) -> String {
+    let mut out = String::new();
+    header(&mut out, "htop_ui_cpu_usage_percent", "CPU usage per core.");
+    for (idx, usage) in snapshot.cores_usage.iter().enumerate() {
+        let _ = writeln!(out, "htop_ui_cpu_usage_percent{{core=\"{}\"}} {}", idx, usage);
+    }
+    header(&mut out, "htop_ui_cpu_frequency_mhz", "Clock of every core.");
+    for (idx, frequency) in snapshot.cores_frequency.iter().enumerate() {
+        let _ = writeln!(out, "htop_ui_cpu_frequency_mhz{{core=\"{}\"}} {}", idx, frequency);
+    }
+    header(&mut out, "htop_ui_pressure_some_avg10_percent", "Share of time some tasks stalled on a resource.");
+    for pressure in snapshot.pressures.iter() {
+        let _ = writeln!(out, "htop_ui_pressure_some_avg10_percent{{resource=\"{}\"}} {}", pressure.resource, pressure.avg10);
+    }
+    header(&mut out, "htop_ui_memory_usage_percent", "Used memory.");
+    let _ = writeln!(out, "htop_ui_memory_usage_percent {}", snapshot.mem_usage.percent_used());
+
+    let mut processes: Vec<_> = snapshot.processes.iter().collect();
+    processes.sort_by(|a, b| b.cpu_usage
+                        .partial_cmp(&a.cpu_usage)
+                        .unwrap_or(std::cmp::Ordering::Equal));
+    processes.truncate(TOP_PROCESSES);
+    header(&mut out, "htop_ui_process_cpu_usage_percent", "CPU usage of the top processes.");
+    for process in processes.iter() {
+        let _ = writeln!(
+            out,
+            "htop_ui_process_cpu_usage_percent{{pid=\"{}\",name=\"{}\",user=\"{}\"}} {}",
+            process.pid, escape(&process.process_name), escape(&process.user), process.cpu_usage
+        );
+    }
+    header(&mut out, "htop_ui_process_memory_usage_percent", "Memory usage of the top processes.");
+    for process in processes.iter() {
+        let _ = writeln!(
+            out,
+            "htop_ui_process_memory_usage_percent{{pid=\"{}\",name=\"{}\",user=\"{}\"}} {}",
+            process.pid, escape(&process.process_name), escape(&process.user), process.mem_usage
+        );
+    }
+
+    header(&mut out, "htop_ui_disk_total_bytes", "Total disk space.");
+    for disk in snapshot.disks.iter() {
+        let _ = writeln!(out, "htop_ui_disk_total_bytes{{disk=\"{}\"}} {}", escape(&disk.name), disk.total_space);
+    }
+    header(&mut out, "htop_ui_disk_available_bytes", "Available disk space.");
+    for disk in snapshot.disks.iter() {
+        let _ = writeln!(out, "htop_ui_disk_available_bytes{{disk=\"{}\"}} {}", escape(&disk.name), disk.available_space);
+    }
+    header(&mut out, "htop_ui_disk_inodes_total", "Total inodes, zero where the file system doesn't count them.");
+    for disk in snapshot.disks.iter() {
+        let _ = writeln!(out, "htop_ui_disk_inodes_total{{disk=\"{}\"}} {}", escape(&disk.name), disk.inodes_total);
+    }
+    header(&mut out, "htop_ui_disk_inodes_free_total", "Free inodes.");
+    for disk in snapshot.disks.iter() {
+        let _ = writeln!(out, "htop_ui_disk_inodes_free_total{{disk=\"{}\"}} {}", escape(&disk.name), disk.inodes_free);
+    }
+    header(&mut out, "htop_ui_disk_read_bytes_per_second", "Read rate of all processes.");
+    let _ = writeln!(out, "htop_ui_disk_read_bytes_per_second {}", snapshot.disk_io.read);
+    header(&mut out, "htop_ui_disk_written_bytes_per_second", "Write rate of all processes.");
+    let _ = writeln!(out, "htop_ui_disk_written_bytes_per_second {}", snapshot.disk_io.write);
+    header(&mut out, "htop_ui_device_read_bytes_per_second", "Block device read rate.");
+    for device in snapshot.device_io.iter() {
+        let _ = writeln!(out, "htop_ui_device_read_bytes_per_second{{device=\"{}\"}} {}", escape(&device.name), device.read);
+    }
+    header(&mut out, "htop_ui_device_written_bytes_per_second", "Block device write rate.");
+    for device in snapshot.device_io.iter() {
+        let _ = writeln!(out, "htop_ui_device_written_bytes_per_second{{device=\"{}\"}} {}", escape(&device.name), device.write);
+    }
+
+    header(&mut out, "htop_ui_network_upload_kbps", "Network upload rate.");
+    let _ = writeln!(out, "htop_ui_network_upload_kbps {}", snapshot.network.upload);
+    header(&mut out, "htop_ui_network_download_kbps", "Network download rate.");
+    let _ = writeln!(out, "htop_ui_network_download_kbps {}", snapshot.network.download);
+    header(&mut out, "htop_ui_network_transmitted_bytes", "Bytes sent since the interface counters were reset.");
+    for interface in snapshot.network.interfaces.iter() {
+        let _ = writeln!(out, "htop_ui_network_transmitted_bytes{{interface=\"{}\"}} {}", escape(&interface.name), interface.total_transmitted);
+    }
+    header(&mut out, "htop_ui_network_received_bytes", "Bytes received since the interface counters were reset.");
+    for interface in snapshot.network.interfaces.iter() {
+        let _ = writeln!(out, "htop_ui_network_received_bytes{{interface=\"{}\"}} {}", escape(&interface.name), interface.total_received);
+    }
+
+    header(&mut out, "htop_ui_ping_rtt_seconds", "Round trip to a ping host, left out when it didn't answer.");
+    for target in snapshot.ping.iter() {
+        if let Some(rtt) = target.rtt {
+            let _ = writeln!(out, "htop_ui_ping_rtt_seconds{{host=\"{}\"}} {}", escape(&target.host), rtt.as_secs_f64());
+        }
+    }
+
+    header(&mut out, "htop_ui_temperature_celsius", "Sensor temperature.");
+    for temperature in snapshot.temperatures.iter() {
+        let _ = writeln!(
+            out,
+            "htop_ui_temperature_celsius{{sensor=\"{}\"}} {}",
+            escape(&temperature.label), temperature.value
+        );
+    }
+
+    header(&mut out, "htop_ui_gpu_usage_percent", "GPU utilization.");
+    for gpu in snapshot.gpus.iter() {
+        let _ = writeln!(out, "htop_ui_gpu_usage_percent{{gpu=\"{}\"}} {}", escape(&gpu.name), gpu.utilization);
+    }
+    header(&mut out, "htop_ui_gpu_memory_used_bytes", "Used GPU memory.");
+    for gpu in snapshot.gpus.iter() {
+        let _ = writeln!(out, "htop_ui_gpu_memory_used_bytes{{gpu=\"{}\"}} {}", escape(&gpu.name), gpu.memory_used);
+    }
+    header(&mut out, "htop_ui_gpu_temperature_celsius", "GPU temperature.");
+    for gpu in snapshot.gpus.iter() {
+        let _ = writeln!(out, "htop_ui_gpu_temperature_celsius{{gpu=\"{}\"}} {}", escape(&gpu.name), gpu.temperature);
+    }
+    out
+}
+
+fn header(out: &mut String, name: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} gauge", name);
+}
+
+fn escape(label: &str) -> String {
+    label
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
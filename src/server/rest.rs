@@ -0,0 +1,61 @@
+use axum::{extract::State, routing::get, Json, Router};
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+
+use crate::{server::websocket, cmd::{disk::Disk, hub::Subscriber, memory::Memory, network::Network, process::Process, snapshot::Snapshot, temperature::Temperature}};
+
+/// Binds `addr` and answers queries for the latest collector values as JSON
+/// from a background task, one endpoint per kind of reading and `/snapshot`
+/// for all of them. `/stream` pushes every update over a WebSocket.
+pub async fn serve(addr: &str, subscriber: Subscriber) -> Result<(), std::io::Error> {
+    let listener = TcpListener::bind(addr).await?;
+    let router = Router::new()
+        .route("/processes", get(processes))
+        .route("/cpu", get(cpu))
+        .route("/memory", get(memory))
+        .route("/disks", get(disks))
+        .route("/network", get(network))
+        .route("/temperatures", get(temperatures))
+        .route("/snapshot", get(snapshot))
+        .route("/stream", get(websocket::upgrade))
+        .with_state(subscriber);
+    tokio::spawn(async move {
+        if let Err(err) = axum::serve(listener, router).await {
+            log::error!("REST API stopped: {}", err);
+        }
+    });
+    Ok(())
+}
+
+async fn processes(State(subscriber): State<Subscriber>) -> Json<Vec<Process>> {
+    Json(subscriber.snapshot().processes)
+}
+
+/// Usage in percent and clock in MHz, per core.
+async fn cpu(State(subscriber): State<Subscriber>) -> Json<Value> {
+    let snapshot = subscriber.snapshot();
+    Json(json!({
+        "usage": snapshot.cores_usage,
+        "frequency": snapshot.cores_frequency,
+    }))
+}
+
+async fn memory(State(subscriber): State<Subscriber>) -> Json<Memory> {
+    Json(subscriber.snapshot().mem_usage)
+}
+
+async fn disks(State(subscriber): State<Subscriber>) -> Json<Vec<Disk>> {
+    Json(subscriber.snapshot().disks)
+}
+
+async fn network(State(subscriber): State<Subscriber>) -> Json<Network> {
+    Json(subscriber.snapshot().network)
+}
+
+async fn temperatures(State(subscriber): State<Subscriber>) -> Json<Vec<Temperature>> {
+    Json(subscriber.snapshot().temperatures)
+}
+
+async fn snapshot(State(subscriber): State<Subscriber>) -> Json<Snapshot> {
+    Json(subscriber.snapshot())
+}
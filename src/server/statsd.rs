@@ -0,0 +1,120 @@
+//! Sends readings as StatsD gauges, `<name>:<value>|g`, several per
+//! datagram.
+use tokio::{net::UdpSocket, time::Instant};
+
+use crate::{app::config::StatsdConfig, cmd::{hub::Subscriber, snapshot::Snapshot}};
+
+/// Port used when the address is given without one.
+const DEFAULT_PORT: u16 = 8125;
+/// Largest datagram sent, which fits the MTU of most networks without
+/// fragmenting.
+const MAX_DATAGRAM: usize = 1432;
+
+/// Sends the latest readings every `statsd.interval` from a background task.
+/// Does nothing unless `statsd.address` is set.
+pub fn spawn(config: StatsdConfig, subscriber: Subscriber) {
+    let Some(address) = config.address.clone() else {
+        return;
+    };
+    let address = match address.rsplit_once(':') {
+        Some((_, port)) if port.parse::<u16>().is_ok() => address,
+        _ => format!("{}:{}", address, DEFAULT_PORT),
+    };
+    let prefix = config.prefix.clone().unwrap_or_else(|| {
+        let host = sysinfo::System::host_name().unwrap_or_else(|| "localhost".to_string());
+        format!("htop-ui.{}", name(&host))
+    });
+    let interval = config.interval.unwrap();
+    tokio::spawn(async move {
+        // An IPv6 address is written in brackets, as in `[::1]:8125`
+        let local = if address.starts_with('[') { "[::]:0" } else { "0.0.0.0:0" };
+        let socket = match UdpSocket::bind(local).await {
+            Ok(socket) => socket,
+            Err(err) => {
+                log::warn!("Error opening a UDP socket for StatsD: {}", err);
+                return;
+            }
+        };
+        let mut tick = tokio::time::interval_at(Instant::now() + interval, interval);
+        loop {
+            tick.tick().await;
+            // Resolved every round, so that a server that moves is followed
+            for datagram in datagrams(&gauges(&subscriber.snapshot(), &prefix)) {
+                if let Err(err) = socket.send_to(datagram.as_bytes(), &address).await {
+                    log::warn!("Error sending StatsD gauges to {}: {}", address, err);
+                    break;
+                }
+            }
+        }
+    });
+}
+
+fn gauges(snapshot: &Snapshot, prefix: &str) -> Vec<String> {
+    let mut gauges = Vec::new();
+    let mut gauge = |metric: String, value: String| {
+        // A signed value changes the gauge instead of setting it, so a
+        // negative one, such as an outdoor temperature, starts from zero
+        if value.starts_with('-') {
+            gauges.push(format!("{}.{}:0|g", prefix, metric));
+        }
+        gauges.push(format!("{}.{}:{}|g", prefix, metric, value));
+    };
+
+    let cores = &snapshot.cores_usage;
+    if !cores.is_empty() {
+        gauge("cpu.usage".to_string(), (cores.iter().sum::<f32>() / cores.len() as f32).to_string());
+        for (core, usage) in cores.iter().enumerate() {
+            gauge(format!("cpu.core{}.usage", core), usage.to_string());
+        }
+    }
+    let memory = &snapshot.mem_usage;
+    if memory.total > 0 {
+        gauge("memory.percent_used".to_string(), memory.percent_used().to_string());
+        gauge("memory.used".to_string(), memory.used.to_string());
+        gauge("memory.total".to_string(), memory.total.to_string());
+        gauge("swap.used".to_string(), memory.swap_used.to_string());
+        gauge("swap.total".to_string(), memory.swap_total.to_string());
+    }
+    for disk in snapshot.disks.iter() {
+        let mount = match disk.mount_point.as_str() {
+            "/" => "root".to_string(),
+            mount => name(mount.trim_start_matches('/')),
+        };
+        gauge(format!("disk.{}.percent_used", mount), disk.percent_used_space().to_string());
+        gauge(format!("disk.{}.available", mount), disk.available_space.to_string());
+    }
+    let network = &snapshot.network;
+    gauge("network.upload_kbps".to_string(), network.upload.to_string());
+    gauge("network.download_kbps".to_string(), network.download.to_string());
+    for interface in network.interfaces.iter() {
+        gauge(format!("network.{}.upload_kbps", name(&interface.name)), interface.upload.to_string());
+        gauge(format!("network.{}.download_kbps", name(&interface.name)), interface.download.to_string());
+    }
+    for temperature in snapshot.temperatures.iter() {
+        gauge(format!("temperature.{}", name(&temperature.label)), temperature.value.to_string());
+    }
+    gauges
+}
+
+/// Gauges joined by newlines, as many per datagram as fit.
+fn datagrams(gauges: &[String]) -> Vec<String> {
+    let mut datagrams: Vec<String> = Vec::new();
+    for gauge in gauges {
+        match datagrams.last_mut() {
+            Some(datagram) if datagram.len() + 1 + gauge.len() <= MAX_DATAGRAM => {
+                datagram.push('\n');
+                datagram.push_str(gauge);
+            }
+            _ => datagrams.push(gauge.clone()),
+        }
+    }
+    datagrams
+}
+
+/// A single Graphite path component, with dots and anything StatsD or
+/// Graphite would read differently replaced by `_`.
+fn name(value: &str) -> String {
+    value.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect()
+}
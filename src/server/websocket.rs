@@ -0,0 +1,332 @@
+//! Pushes every collector message to browsers over a WebSocket, with just
+//! enough of RFC 6455 to send text frames and answer pings and closes.
+use aws_lc_rs::digest;
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use base64::Engine;
+use hyper_util::rt::TokioIo;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    sync::mpsc,
+};
+
+use crate::cmd::hub::Subscriber;
+
+/// Appended to the client key before hashing it into the accept header.
+const GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+/// Largest frame read from a client, which only ever sends control frames.
+const MAX_FRAME: u64 = 64 * 1024;
+/// Largest payload of a control frame.
+const MAX_CONTROL: u64 = 125;
+
+const CONTINUATION: u8 = 0x0;
+const TEXT: u8 = 0x1;
+const BINARY: u8 = 0x2;
+const CLOSE: u8 = 0x8;
+const PING: u8 = 0x9;
+const PONG: u8 = 0xa;
+
+/// Upgrades the request and streams messages on it the way `--agent` does,
+/// the latest value of every collector first, one JSON `Message` per frame.
+pub async fn upgrade(State(subscriber): State<Subscriber>, mut request: Request) -> Response {
+    let Some(accept) = accept_key(request.headers()) else {
+        return (StatusCode::BAD_REQUEST, "expected a WebSocket upgrade").into_response();
+    };
+    let on_upgrade = hyper::upgrade::on(&mut request);
+    tokio::spawn(async move {
+        match on_upgrade.await {
+            Ok(upgraded) => stream(TokioIo::new(upgraded), subscriber).await,
+            Err(err) => log::warn!("Error upgrading to a WebSocket: {}", err),
+        }
+    });
+    Response::builder()
+        .status(StatusCode::SWITCHING_PROTOCOLS)
+        .header(header::UPGRADE, "websocket")
+        .header(header::CONNECTION, "upgrade")
+        .header(header::SEC_WEBSOCKET_ACCEPT, accept)
+        .body(Body::empty())
+        .unwrap()
+}
+
+fn accept_key(headers: &HeaderMap) -> Option<String> {
+    let upgrade = headers.get(header::UPGRADE)?.to_str().ok()?;
+    if !upgrade.eq_ignore_ascii_case("websocket")
+        || headers.get(header::SEC_WEBSOCKET_VERSION)?.as_bytes() != b"13" {
+        return None;
+    }
+    let key = headers.get(header::SEC_WEBSOCKET_KEY)?.as_bytes();
+    let mut context = digest::Context::new(&digest::SHA1_FOR_LEGACY_USE_ONLY);
+    context.update(key);
+    context.update(GUID.as_bytes());
+    Some(base64::engine::general_purpose::STANDARD.encode(context.finish()))
+}
+
+/// Status codes sent in a close frame.
+const NORMAL_CLOSURE: u16 = 1000;
+const PROTOCOL_ERROR: u16 = 1002;
+
+/// Control frames the reader wants sent back.
+#[derive(Debug, PartialEq)]
+enum Control {
+    Pong(Vec<u8>),
+    /// A close frame with the status code, or None once the connection is
+    /// gone and nothing can be sent.
+    Close(Option<u16>),
+}
+
+async fn stream<S: AsyncRead + AsyncWrite + Send + 'static>(socket: S, mut subscriber: Subscriber) {
+    let (reader, mut writer) = tokio::io::split(socket);
+    let (control_tx, mut control_rx) = mpsc::channel(8);
+    tokio::spawn(read_frames(reader, control_tx));
+
+    for msg in subscriber.snapshot().messages() {
+        let Ok(text) = serde_json::to_string(&msg) else { continue };
+        if write_frame(&mut writer, TEXT, text.as_bytes()).await.is_err() {
+            return;
+        }
+    }
+    loop {
+        tokio::select! {
+            msg = subscriber.next() => {
+                let Some(msg) = msg else { break };
+                match serde_json::to_string(&msg) {
+                    Ok(text) => {
+                        if write_frame(&mut writer, TEXT, text.as_bytes()).await.is_err() {
+                            return;
+                        }
+                    }
+                    Err(err) => log::warn!("Error serializing message: {}", err),
+                }
+            }
+            control = control_rx.recv() => match control {
+                Some(Control::Pong(payload)) => {
+                    if write_frame(&mut writer, PONG, &payload).await.is_err() {
+                        return;
+                    }
+                }
+                // The client closed the connection or broke the protocol
+                Some(Control::Close(Some(code))) => {
+                    let _ = write_frame(&mut writer, CLOSE, &code.to_be_bytes()).await;
+                    return;
+                }
+                Some(Control::Close(None)) | None => return,
+            },
+        }
+    }
+    let _ = write_frame(&mut writer, CLOSE, &NORMAL_CLOSURE.to_be_bytes()).await;
+}
+
+/// Reads client frames until the connection closes, ignoring data frames
+/// but checking that their fragments come in order.
+async fn read_frames<S: AsyncRead + Unpin>(mut reader: S, control_tx: mpsc::Sender<Control>) {
+    let mut fragmented = false;
+    loop {
+        let control = match read_frame(&mut reader).await {
+            Ok(frame) => match frame.opcode {
+                PING => Control::Pong(frame.payload),
+                CLOSE => Control::Close(Some(NORMAL_CLOSURE)),
+                PONG => continue,
+                // A fragmented message goes on with continuation frames only
+                CONTINUATION if fragmented => {
+                    fragmented = !frame.fin;
+                    continue;
+                }
+                TEXT | BINARY if !fragmented => {
+                    fragmented = !frame.fin;
+                    continue;
+                }
+                _ => Control::Close(Some(PROTOCOL_ERROR)),
+            },
+            Err(err) if err.kind() == std::io::ErrorKind::InvalidData => Control::Close(Some(PROTOCOL_ERROR)),
+            Err(_) => Control::Close(None),
+        };
+        let closing = matches!(control, Control::Close(_));
+        if control_tx.send(control).await.is_err() || closing {
+            return;
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Frame {
+    fin: bool,
+    opcode: u8,
+    /// Unmasked.
+    payload: Vec<u8>,
+}
+
+/// Reads a frame, failing with `InvalidData` on frames a client must not
+/// send: reserved bits or opcodes, no mask, or a control frame that is
+/// fragmented or over 125 bytes.
+async fn read_frame<S: AsyncRead + Unpin>(reader: &mut S) -> std::io::Result<Frame> {
+    let invalid = |reason: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, reason.to_string());
+    let mut head = [0u8; 2];
+    reader.read_exact(&mut head).await?;
+    let fin = head[0] & 0x80 != 0;
+    let opcode = head[0] & 0x0f;
+    if head[0] & 0x70 != 0 {
+        return Err(invalid("reserved bits set"));
+    }
+    if !matches!(opcode, CONTINUATION | TEXT | BINARY | CLOSE | PING | PONG) {
+        return Err(invalid("reserved opcode"));
+    }
+    // Clients mask every frame they send
+    if head[1] & 0x80 == 0 {
+        return Err(invalid("unmasked frame"));
+    }
+    let length = match head[1] & 0x7f {
+        126 => reader.read_u16().await? as u64,
+        127 => reader.read_u64().await?,
+        length => length as u64,
+    };
+    if opcode & 0x8 != 0 && (length > MAX_CONTROL || !fin) {
+        return Err(invalid("fragmented or oversized control frame"));
+    }
+    if length > MAX_FRAME {
+        return Err(invalid("frame too large"));
+    }
+    let mut mask = [0u8; 4];
+    reader.read_exact(&mut mask).await?;
+    let mut payload = vec![0u8; length as usize];
+    reader.read_exact(&mut payload).await?;
+    for (idx, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[idx % 4];
+    }
+    Ok(Frame { fin, opcode, payload })
+}
+
+async fn write_frame<S: AsyncWrite + Unpin>(writer: &mut S, opcode: u8, payload: &[u8]) -> std::io::Result<()> {
+    let mut frame = vec![0x80 | opcode];
+    match payload.len() {
+        length if length < 126 => frame.push(length as u8),
+        length if length <= u16::MAX as usize => {
+            frame.push(126);
+            frame.extend_from_slice(&(length as u16).to_be_bytes());
+        }
+        length => {
+            frame.push(127);
+            frame.extend_from_slice(&(length as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    writer.write_all(&frame).await?;
+    writer.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    /// A frame as a client sends it, masked with a fixed key.
+    fn masked(first: u8, payload: &[u8]) -> Vec<u8> {
+        let mask = [0x37, 0xfa, 0x21, 0x3d];
+        let mut frame = vec![first];
+        match payload.len() {
+            length if length < 126 => frame.push(0x80 | length as u8),
+            length => {
+                frame.push(0x80 | 126);
+                frame.extend_from_slice(&(length as u16).to_be_bytes());
+            }
+        }
+        frame.extend_from_slice(&mask);
+        frame.extend(payload.iter().enumerate().map(|(idx, byte)| byte ^ mask[idx % 4]));
+        frame
+    }
+
+    async fn read(bytes: &[u8]) -> std::io::Result<Frame> {
+        read_frame(&mut &bytes[..]).await
+    }
+
+    async fn controls(bytes: Vec<u8>) -> Vec<Control> {
+        let (control_tx, mut control_rx) = mpsc::channel(8);
+        read_frames(&bytes[..], control_tx).await;
+        let mut controls = Vec::new();
+        while let Some(control) = control_rx.recv().await {
+            controls.push(control);
+        }
+        controls
+    }
+
+    #[test]
+    fn accept_key_of_the_rfc_example() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::UPGRADE, HeaderValue::from_static("WebSocket"));
+        headers.insert(header::SEC_WEBSOCKET_VERSION, HeaderValue::from_static("13"));
+        headers.insert(header::SEC_WEBSOCKET_KEY, HeaderValue::from_static("dGhlIHNhbXBsZSBub25jZQ=="));
+        assert_eq!(accept_key(&headers).as_deref(), Some("s3pPLMBiTxaQ9kYGzzhZRbK+xOo="));
+        headers.insert(header::SEC_WEBSOCKET_VERSION, HeaderValue::from_static("8"));
+        assert_eq!(accept_key(&headers), None);
+    }
+
+    #[tokio::test]
+    async fn masked_frames_are_unmasked() {
+        let frame = read(&[0x81, 0x85, 0x37, 0xfa, 0x21, 0x3d, 0x7f, 0x9f, 0x4d, 0x51, 0x58]).await.unwrap();
+        assert!(frame.fin);
+        assert_eq!(frame.opcode, TEXT);
+        assert_eq!(frame.payload, b"Hello");
+        let payload = vec![7; 300];
+        let frame = read(&masked(0x82, &payload)).await.unwrap();
+        assert_eq!(frame.opcode, BINARY);
+        assert_eq!(frame.payload, payload);
+    }
+
+    #[tokio::test]
+    async fn frames_a_client_must_not_send_are_refused() {
+        let refused = [
+            // No mask
+            vec![0x81, 0x05, b'H', b'e', b'l', b'l', b'o'],
+            // RSV1 without an extension for it
+            masked(0xc1, b"Hello"),
+            // Reserved opcode
+            masked(0x83, b""),
+            // Fragmented ping
+            masked(0x09, b""),
+            // Control frame over 125 bytes
+            masked(0x89, &[0; 126]),
+        ];
+        for frame in refused {
+            let err = read(&frame).await.unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidData, "{:02x?}", &frame[..2]);
+        }
+        assert!(read(&masked(0x89, &[0; 125])).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn pings_between_fragments_are_answered() {
+        let mut bytes = masked(0x01, b"Hel");
+        bytes.extend(masked(0x89, b"hi"));
+        bytes.extend(masked(0x80, b"lo"));
+        bytes.extend(masked(0x88, &NORMAL_CLOSURE.to_be_bytes()));
+        assert_eq!(controls(bytes).await, [Control::Pong(b"hi".to_vec()), Control::Close(Some(NORMAL_CLOSURE))]);
+    }
+
+    #[tokio::test]
+    async fn fragments_out_of_order_close_with_a_protocol_error() {
+        assert_eq!(controls(masked(0x80, b"lo")).await, [Control::Close(Some(PROTOCOL_ERROR))]);
+        let mut bytes = masked(0x01, b"Hel");
+        bytes.extend(masked(0x81, b"lo"));
+        assert_eq!(controls(bytes).await, [Control::Close(Some(PROTOCOL_ERROR))]);
+        assert_eq!(controls(masked(0xc1, b"")).await, [Control::Close(Some(PROTOCOL_ERROR))]);
+        // Nothing can be sent once the client is gone
+        assert_eq!(controls(masked(0x81, b"Hello")).await, [Control::Close(None)]);
+    }
+
+    #[tokio::test]
+    async fn server_frames_are_unmasked_and_sized() {
+        let mut frame = Vec::new();
+        write_frame(&mut frame, TEXT, b"Hello").await.unwrap();
+        assert_eq!(frame, [0x81, 0x05, b'H', b'e', b'l', b'l', b'o']);
+        let mut frame = Vec::new();
+        write_frame(&mut frame, TEXT, &[b'x'; 300]).await.unwrap();
+        assert_eq!(frame[..4], [0x81, 126, 0x01, 0x2c]);
+        assert_eq!(frame.len(), 304);
+        let mut frame = Vec::new();
+        write_frame(&mut frame, TEXT, &vec![b'x'; 70_000]).await.unwrap();
+        assert_eq!(frame[..10], [0x81, 127, 0, 0, 0, 0, 0, 0x01, 0x11, 0x70]);
+    }
+}